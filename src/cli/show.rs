@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use nipart::{
-    NetworkState, NipartClient, NipartInterface, NipartNoDaemon,
-    NipartQueryOption,
+    NetworkState, NipartClient, NipartInterface, NipartIpFamily,
+    NipartNoDaemon, NipartQueryOption, NipartRouteFilter, NipartRouteProtocol,
 };
 
 use crate::CliError;
@@ -43,25 +43,73 @@ impl CommandShow {
                     .action(clap::ArgAction::SetTrue)
                     .help("Show secrets(hide by default)"),
             )
+            .arg(
+                clap::Arg::new("ROUTE_PROTOCOL")
+                    .long("route-protocol")
+                    .action(clap::ArgAction::Append)
+                    .value_parser([
+                        "boot",
+                        "static",
+                        "ra",
+                        "dhcp",
+                        "mrouted",
+                        "keepalived",
+                        "babel",
+                    ])
+                    .help("Only show routes created by specified protocol"),
+            )
+            .arg(
+                clap::Arg::new("ROUTE_TABLE")
+                    .long("route-table")
+                    .action(clap::ArgAction::Append)
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Only show routes in specified route table"),
+            )
+            .arg(
+                clap::Arg::new("ROUTE_FAMILY")
+                    .long("route-family")
+                    .action(clap::ArgAction::Append)
+                    .value_parser(["ipv4", "ipv6"])
+                    .help("Only show routes of specified IP family"),
+            )
+            .arg(
+                clap::Arg::new("MAX_ROUTES")
+                    .long("max-routes")
+                    .value_parser(clap::value_parser!(u32))
+                    .help(
+                        "Maximum number of running and config routes to show, \
+                         this does not limit routes dumped from kernel",
+                    ),
+            )
+            .arg(
+                clap::Arg::new("NO_RUNNING_ROUTES")
+                    .long("no-running-routes")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Do not show running routes"),
+            )
     }
 
     pub(crate) async fn handle(
         matches: &clap::ArgMatches,
     ) -> Result<(), CliError> {
+        let rt_filter = route_filter_from_matches(matches)?;
         let net_state = if matches.get_flag("NO_DAEMON") {
             if matches.get_flag("SAVED") {
                 return Err("--no-daemon or --kernel cannot be used with \
                             --saved argument"
                     .into());
             }
-            NipartNoDaemon::query_network_state(Default::default()).await?
+            let mut opt = NipartQueryOption::running();
+            opt.route_filter = rt_filter;
+            NipartNoDaemon::query_network_state(opt).await?
         } else {
             let mut cli = NipartClient::new().await?;
-            let opt = if matches.get_flag("SAVED") {
+            let mut opt = if matches.get_flag("SAVED") {
                 NipartQueryOption::saved()
             } else {
                 NipartQueryOption::running()
             };
+            opt.route_filter = rt_filter;
             cli.query_network_state(opt).await?
         };
         let mut net_state =
@@ -81,6 +129,36 @@ impl CommandShow {
     }
 }
 
+fn route_filter_from_matches(
+    matches: &clap::ArgMatches,
+) -> Result<Option<NipartRouteFilter>, CliError> {
+    let mut rt_filter = NipartRouteFilter::new();
+    if let Some(protocols) = matches.get_many::<String>("ROUTE_PROTOCOL") {
+        let mut values = Vec::new();
+        for protocol in protocols {
+            values.push(serde_yaml::from_str::<NipartRouteProtocol>(protocol)?);
+        }
+        rt_filter = rt_filter.protocols(values);
+    }
+    if let Some(tables) = matches.get_many::<u32>("ROUTE_TABLE") {
+        rt_filter = rt_filter.tables(tables.copied().collect());
+    }
+    if let Some(families) = matches.get_many::<String>("ROUTE_FAMILY") {
+        let mut values = Vec::new();
+        for family in families {
+            values.push(serde_yaml::from_str::<NipartIpFamily>(family)?);
+        }
+        rt_filter = rt_filter.families(values);
+    }
+    if let Some(max_entries) = matches.get_one::<u32>("MAX_ROUTES") {
+        rt_filter = rt_filter.max_entries(*max_entries);
+    }
+    if matches.get_flag("NO_RUNNING_ROUTES") {
+        rt_filter = rt_filter.skip_running();
+    }
+    Ok((rt_filter != NipartRouteFilter::default()).then_some(rt_filter))
+}
+
 fn filter_net_state(
     net_state: &NetworkState,
     iface_name: &str,
@@ -172,12 +172,18 @@ impl NipartCommander {
 
         let apply_state = merged_state.gen_state_for_apply();
 
-        NipartNoDaemon::apply_merged_state(&merged_state).await?;
+        let mut merged_state_for_no_daemon = merged_state.clone();
+        remove_route_metric_policy(&mut merged_state_for_no_daemon);
+
+        NipartNoDaemon::apply_merged_state(&merged_state_for_no_daemon).await?;
         self.plugin_manager
             .apply_network_state(&apply_state, &opt)
             .await?;
 
         self.dhcpv4_manager
+            .apply_dhcp_config(conn.as_deref_mut(), &merged_state)
+            .await?;
+        self.dhcpv6_manager
             .apply_dhcp_config(conn, &merged_state)
             .await?;
 
@@ -256,6 +262,7 @@ impl NipartCommander {
         let mut merged_state_for_no_daemon = merged_state.clone();
         // Remove interfaces for conditional activating
         merged_state_for_no_daemon.remove_conditional_activation();
+        remove_route_metric_policy(&mut merged_state_for_no_daemon);
 
         NipartNoDaemon::apply_merged_state(&merged_state_for_no_daemon).await?;
         self.plugin_manager
@@ -265,6 +272,9 @@ impl NipartCommander {
         self.dhcpv4_manager
             .apply_dhcp_config(conn.as_deref_mut(), merged_state)
             .await?;
+        self.dhcpv6_manager
+            .apply_dhcp_config(conn.as_deref_mut(), merged_state)
+            .await?;
 
         let mut result: Result<(), NipartError> = Ok(());
         if !merged_state.option.no_verify {
@@ -294,6 +304,12 @@ impl NipartCommander {
     }
 }
 
+// Route metric policy is persisted in saved state and passed to DHCP workers
+// by daemon, hence should not be stored by NipartNoDaemon.
+fn remove_route_metric_policy(merged_state: &mut MergedNetworkState) {
+    merged_state.route_metric_policy.for_apply = None;
+}
+
 fn remove_undesired_ifaces(
     merged_desired_state: &mut NetworkState,
     desired_state: &NetworkState,
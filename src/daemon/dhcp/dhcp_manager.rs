@@ -2,7 +2,7 @@
 
 use nipart::{
     BaseInterface, MergedNetworkState, NetworkState, NipartError,
-    NipartInterface, NipartIpcConnection,
+    NipartInterface, NipartIpcConnection, NipartNoDaemon, RouteMetricPolicy,
 };
 
 use super::{NipartDhcpCmd, NipartDhcpReply, NipartDhcpV4Worker};
@@ -18,6 +18,7 @@ pub(crate) struct NipartDhcpV4Manager {
 // `MutexGuard` will cause function not `Send`.
 impl NipartDhcpV4Manager {
     pub(crate) async fn new() -> Result<Self, NipartError> {
+        NipartNoDaemon::init_dhcp_addrs()?;
         Ok(Self {
             mgr: TaskManager::new::<NipartDhcpV4Worker>("dhcp").await?,
         })
@@ -31,7 +32,7 @@ impl NipartDhcpV4Manager {
         if let NipartDhcpReply::QueryReply(mut dhcp_states) =
             self.mgr.exec(NipartDhcpCmd::Query).await?
         {
-            for (iface_name, dhcp_state) in dhcp_states.drain() {
+            for (iface_name, dhcp_ipv4) in dhcp_states.drain() {
                 if let Some(iface) =
                     net_state.ifaces.kernel_ifaces.get_mut(iface_name.as_str())
                 {
@@ -40,8 +41,15 @@ impl NipartDhcpV4Manager {
                         .ipv4
                         .get_or_insert(Default::default());
                     ipv4_conf.enabled = Some(true);
-                    ipv4_conf.dhcp = Some(true);
-                    ipv4_conf.dhcp_state = Some(dhcp_state);
+                    ipv4_conf.link_local = dhcp_ipv4.link_local;
+                    if dhcp_ipv4.is_auto() {
+                        ipv4_conf.dhcp = Some(true);
+                        ipv4_conf.dhcp_state = dhcp_ipv4.dhcp_state;
+                        ipv4_conf.dhcp_lease_expiry =
+                            dhcp_ipv4.dhcp_lease_expiry;
+                        ipv4_conf.copy_auto_options(&dhcp_ipv4);
+                        ipv4_conf.copy_dhcp_id_options(&dhcp_ipv4);
+                    }
                 }
             }
         }
@@ -51,9 +59,13 @@ impl NipartDhcpV4Manager {
     async fn start_iface_dhcp(
         &mut self,
         base_iface: &BaseInterface,
+        policy: &RouteMetricPolicy,
     ) -> Result<(), NipartError> {
         self.mgr
-            .exec(NipartDhcpCmd::StartIfaceDhcp(Box::new(base_iface.clone())))
+            .exec(NipartDhcpCmd::StartIfaceDhcp(
+                Box::new(base_iface.clone()),
+                Box::new(policy.clone()),
+            ))
             .await?;
         Ok(())
     }
@@ -61,9 +73,13 @@ impl NipartDhcpV4Manager {
     async fn stop_iface_dhcp(
         &mut self,
         iface_name: &str,
+        remove_lease: bool,
     ) -> Result<(), NipartError> {
         self.mgr
-            .exec(NipartDhcpCmd::StopIfaceDhcp(iface_name.to_string()))
+            .exec(NipartDhcpCmd::StopIfaceDhcp(
+                iface_name.to_string(),
+                remove_lease,
+            ))
             .await?;
         Ok(())
     }
@@ -75,6 +91,7 @@ impl NipartDhcpV4Manager {
         mut conn: Option<&mut NipartIpcConnection>,
         merged_state: &MergedNetworkState,
     ) -> Result<(), NipartError> {
+        let route_metric_policy = merged_state.route_metric_policy.merged();
         for merged_iface in merged_state
             .ifaces
             .iter()
@@ -92,9 +109,28 @@ impl NipartDhcpV4Manager {
             }
             apply_iface.base_iface_mut().iface_index =
                 merged_iface.merged.base_iface().iface_index;
+            // Route metric policy is based on interface type
+            apply_iface.base_iface_mut().iface_type =
+                merged_iface.merged.iface_type().clone();
+            // DHCP thread need full `auto-*`, client identity and link-local
+            // options
+            if let Some(apply_ipv4) = apply_iface.base_iface_mut().ipv4.as_mut()
+                && let Some(merged_ipv4) =
+                    merged_iface.merged.base_iface().ipv4.as_ref()
+            {
+                apply_ipv4.copy_auto_options(merged_ipv4);
+                apply_ipv4.copy_dhcp_id_options(merged_ipv4);
+                if apply_ipv4.is_enabled() {
+                    apply_ipv4.link_local = merged_ipv4.link_local;
+                }
+            }
             if apply_iface.is_up() {
-                if let Some(dhcp_enabled) =
-                    apply_iface.base_iface().ipv4.as_ref().map(|i| i.is_auto())
+                // IPv4 link-local is also managed by DHCP thread
+                if let Some(dhcp_enabled) = apply_iface
+                    .base_iface()
+                    .ipv4
+                    .as_ref()
+                    .map(|i| i.is_auto() || i.is_link_local())
                 {
                     if dhcp_enabled {
                         log_debug(
@@ -106,7 +142,11 @@ impl NipartDhcpV4Manager {
                             ),
                         )
                         .await;
-                        self.start_iface_dhcp(apply_iface.base_iface()).await?;
+                        self.start_iface_dhcp(
+                            apply_iface.base_iface(),
+                            &route_metric_policy,
+                        )
+                        .await?;
                     } else {
                         log_debug(
                             conn.as_deref_mut(),
@@ -117,7 +157,7 @@ impl NipartDhcpV4Manager {
                             ),
                         )
                         .await;
-                        self.stop_iface_dhcp(apply_iface.name()).await?;
+                        self.stop_iface_dhcp(apply_iface.name(), true).await?;
                         log_debug(
                             conn.as_deref_mut(),
                             format!(
@@ -130,10 +170,25 @@ impl NipartDhcpV4Manager {
                     }
                 }
             } else {
-                self.stop_iface_dhcp(apply_iface.name()).await?;
+                // Keep the lease for interface been set to down
+                self.stop_iface_dhcp(
+                    apply_iface.name(),
+                    apply_iface.is_absent(),
+                )
+                .await?;
             }
         }
 
+        // Interfaces not changed in this apply might still hold routes
+        // generated by previous route metric policy
+        if merged_state.route_metric_policy.is_changed() {
+            self.mgr
+                .exec(NipartDhcpCmd::ApplyRouteMetricPolicy(Box::new(
+                    route_metric_policy,
+                )))
+                .await?;
+        }
+
         Ok(())
     }
 }
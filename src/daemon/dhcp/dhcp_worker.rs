@@ -2,7 +2,9 @@
 
 use std::{
     collections::HashMap,
+    net::Ipv4Addr,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use futures_channel::{
@@ -13,33 +15,47 @@ use futures_util::StreamExt;
 use mozim::{DhcpV4Client, DhcpV4Config, DhcpV4Lease, DhcpV4State};
 use nipart::{
     BaseInterface, DhcpState, ErrorKind, Interface, InterfaceIpAddr,
-    InterfaceIpv4, NetworkState, NipartApplyOption, NipartError,
-    NipartNoDaemon, RouteEntry, Routes,
+    InterfaceIpv4, InterfaceIpv6, NetworkState, NipartApplyOption, NipartError,
+    NipartNoDaemon, RouteMetricPolicy, RouteState, Routes,
 };
+use tokio::task::JoinHandle;
 
+use super::{
+    ipv4ll::NipartIpv4LinkLocal, lease_store::NipartStoredDhcpV4Lease,
+};
 use crate::TaskWorker;
 
-const DEFAULT_ROUTE_TABLE_ID: u32 = 254;
+// Start IPv4 link-local address selection in fallback mode if no DHCPv4 lease
+// acquired in this time after link carrier up.
+const IPV4_LL_FALLBACK_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum NipartDhcpCmd {
-    StartIfaceDhcp(Box<BaseInterface>),
-    StopIfaceDhcp(String),
+    StartIfaceDhcp(Box<BaseInterface>, Box<RouteMetricPolicy>),
+    /// Stop DHCP on specified interface, remove the stored lease also when
+    /// set to true.
+    StopIfaceDhcp(String, bool),
     Query,
+    /// Regenerate routes of running DHCP sessions with new route metric
+    /// policy.
+    ApplyRouteMetricPolicy(Box<RouteMetricPolicy>),
 }
 
 impl std::fmt::Display for NipartDhcpCmd {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::StartIfaceDhcp(base_iface) => {
+            Self::StartIfaceDhcp(base_iface, _) => {
                 write!(f, "start-iface-dhcp:{}", base_iface.name)
             }
-            Self::StopIfaceDhcp(iface) => {
+            Self::StopIfaceDhcp(iface, _) => {
                 write!(f, "stop-iface-dhcp:{iface}")
             }
             Self::Query => {
                 write!(f, "query-dhcp")
             }
+            Self::ApplyRouteMetricPolicy(_) => {
+                write!(f, "apply-route-metric-policy")
+            }
         }
     }
 }
@@ -47,7 +63,12 @@ impl std::fmt::Display for NipartDhcpCmd {
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum NipartDhcpReply {
     None,
-    QueryReply(HashMap<String, DhcpState>),
+    /// HashMap of interface name to IPv4 configuration holding DHCP state,
+    /// lease expiry time and `auto-*` options.
+    QueryReply(HashMap<String, InterfaceIpv4>),
+    /// HashMap of interface name to IPv6 configuration holding DHCPv6 mode,
+    /// state and `auto-*` options.
+    QueryV6Reply(HashMap<String, InterfaceIpv6>),
 }
 
 type FromManager =
@@ -84,15 +105,19 @@ impl TaskWorker for NipartDhcpV4Worker {
         cmd: NipartDhcpCmd,
     ) -> Result<NipartDhcpReply, NipartError> {
         match cmd {
-            NipartDhcpCmd::StartIfaceDhcp(base_iface) => {
+            NipartDhcpCmd::StartIfaceDhcp(base_iface, policy) => {
                 let iface_name = base_iface.name.clone();
-                let thread = NipartDhcpV4Thread::new(*base_iface).await?;
+                let thread =
+                    NipartDhcpV4Thread::new(*base_iface, *policy).await?;
                 self.threads.insert(iface_name.clone(), thread);
                 log::debug!("DHCP thread started on interface {iface_name}");
                 Ok(NipartDhcpReply::None)
             }
-            NipartDhcpCmd::StopIfaceDhcp(iface) => {
+            NipartDhcpCmd::StopIfaceDhcp(iface, remove_lease) => {
                 self.threads.remove(&iface);
+                if remove_lease {
+                    NipartStoredDhcpV4Lease::remove(&iface).await;
+                }
                 Ok(NipartDhcpReply::None)
             }
             NipartDhcpCmd::Query => {
@@ -103,6 +128,12 @@ impl TaskWorker for NipartDhcpV4Worker {
 
                 Ok(NipartDhcpReply::QueryReply(ret))
             }
+            NipartDhcpCmd::ApplyRouteMetricPolicy(policy) => {
+                for thread in self.threads.values() {
+                    thread.apply_route_metric_policy(&policy).await?;
+                }
+                Ok(NipartDhcpReply::None)
+            }
         }
     }
 }
@@ -110,6 +141,10 @@ impl TaskWorker for NipartDhcpV4Worker {
 #[derive(Debug, Default)]
 struct NipartDhcpShareData {
     state: DhcpState,
+    lease_expiry: Option<String>,
+    link_local_addr: Option<Ipv4Addr>,
+    lease: Option<DhcpV4Lease>,
+    route_metric_policy: RouteMetricPolicy,
 }
 
 #[derive(Debug)]
@@ -124,12 +159,16 @@ pub(crate) struct NipartDhcpV4Thread {
 impl NipartDhcpV4Thread {
     pub(crate) async fn new(
         base_iface: BaseInterface,
+        route_metric_policy: RouteMetricPolicy,
     ) -> Result<Self, NipartError> {
         let (sender, receiver) = unbounded();
         let ret = Self {
             base_iface: base_iface.clone(),
             _quit_notifer: sender,
-            share_data: Arc::new(Mutex::new(NipartDhcpShareData::default())),
+            share_data: Arc::new(Mutex::new(NipartDhcpShareData {
+                route_metric_policy,
+                ..Default::default()
+            })),
         };
         let mac_addr = match base_iface.mac_address.as_deref() {
             Some(m) => m,
@@ -155,6 +194,26 @@ impl NipartDhcpV4Thread {
                 ));
             }
         };
+        let ipv4 = base_iface.ipv4.clone().unwrap_or_default();
+        // IPv4 link-local only without DHCP
+        if !ipv4.is_auto() {
+            let share_data = ret.share_data.clone();
+            tokio::spawn(async move {
+                if let Err(e) = dhcp_thread(
+                    None,
+                    base_iface,
+                    String::new(),
+                    receiver,
+                    share_data,
+                )
+                .await
+                {
+                    log::error!("{e}");
+                }
+            });
+            return Ok(ret);
+        }
+
         let mut dhcp_config = DhcpV4Config::new(base_iface.name.as_str());
         dhcp_config
             .set_iface_index(iface_index)
@@ -167,24 +226,67 @@ impl NipartDhcpV4Thread {
                         base_iface.name, base_iface.iface_type, mac_addr,
                     ),
                 )
-            })?
-            .use_mac_as_client_id();
-        // TODO(Gris Ge): Support loading previous stored lease
-        let dhcp_client =
-            DhcpV4Client::init(dhcp_config, None).await.map_err(|e| {
-                NipartError::new(
-                    ErrorKind::Bug,
-                    format!(
-                        "Failed to start DHCPv4 client on iface {}/{}: {e}",
-                        base_iface.name, base_iface.iface_type,
-                    ),
-                )
             })?;
+        let (client_id_type, client_id) = NipartNoDaemon::get_dhcp_client_id(
+            base_iface.name.as_str(),
+            Some(mac_addr),
+            &ipv4,
+        )?;
+        dhcp_config.set_client_id(client_id_type, &client_id);
+        if let Some(hostname) =
+            NipartNoDaemon::get_static_hostname().filter(|h| h != "localhost")
+        {
+            dhcp_config.set_host_name(&hostname);
+            // Use FQDN option(81) instead of host name option(12) for fully
+            // qualified domain name
+            if hostname.contains('.') {
+                dhcp_config.use_host_name_as_fqdn();
+            }
+        }
+        let client_id = lease_client_id(client_id_type, &client_id);
+        let stored_lease =
+            NipartStoredDhcpV4Lease::load(base_iface.name.as_str(), &client_id)
+                .await;
+        if let Some(stored_lease) = stored_lease.as_ref() {
+            log::info!(
+                "Reusing stored DHCPv4 lease {} expiring at {} on interface \
+                 {}({})",
+                stored_lease.lease.yiaddr,
+                stored_lease.expiry_str(),
+                base_iface.name,
+                base_iface.iface_type
+            );
+            if let Ok(mut share_data) = ret.share_data.lock() {
+                share_data.lease_expiry = Some(stored_lease.expiry_str());
+            }
+        }
+        // With previous lease provided, DHCP client will start from
+        // INIT-REBOOT state requesting the same address.
+        let dhcp_client = DhcpV4Client::init(
+            dhcp_config,
+            stored_lease.map(|stored_lease| stored_lease.lease),
+        )
+        .await
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to start DHCPv4 client on iface {}/{}: {e}",
+                    base_iface.name, base_iface.iface_type,
+                ),
+            )
+        })?;
 
         let share_data = ret.share_data.clone();
         tokio::spawn(async move {
-            if let Err(e) =
-                dhcp_thread(dhcp_client, base_iface, receiver, share_data).await
+            if let Err(e) = dhcp_thread(
+                Some(dhcp_client),
+                base_iface,
+                client_id,
+                receiver,
+                share_data,
+            )
+            .await
             {
                 log::error!("{e}");
             }
@@ -192,9 +294,25 @@ impl NipartDhcpV4Thread {
         Ok(ret)
     }
 
-    pub(crate) fn get_state(&self) -> Result<DhcpState, NipartError> {
+    /// Return IPv4 configuration holding DHCP state, lease expiry time and
+    /// `auto-*` options.
+    pub(crate) fn get_state(&self) -> Result<InterfaceIpv4, NipartError> {
         match self.share_data.lock() {
-            Ok(data) => Ok(data.state.clone()),
+            Ok(data) => {
+                let mut ipv4_conf = InterfaceIpv4::default();
+                ipv4_conf.enabled = Some(true);
+                if let Some(cur_ipv4) = self.base_iface.ipv4.as_ref() {
+                    ipv4_conf.link_local = cur_ipv4.link_local;
+                    if cur_ipv4.is_auto() {
+                        ipv4_conf.dhcp = Some(true);
+                        ipv4_conf.dhcp_state = Some(data.state.clone());
+                        ipv4_conf.dhcp_lease_expiry = data.lease_expiry.clone();
+                        ipv4_conf.copy_auto_options(cur_ipv4);
+                        ipv4_conf.copy_dhcp_id_options(cur_ipv4);
+                    }
+                }
+                Ok(ipv4_conf)
+            }
             Err(e) => Err(NipartError::new(
                 ErrorKind::Bug,
                 format!(
@@ -205,11 +323,65 @@ impl NipartDhcpV4Thread {
             )),
         }
     }
+
+    /// Store new route metric policy for further lease and regenerate routes
+    /// of current lease if their metric changed.
+    pub(crate) async fn apply_route_metric_policy(
+        &self,
+        policy: &RouteMetricPolicy,
+    ) -> Result<(), NipartError> {
+        let (old_policy, lease) = match self.share_data.lock() {
+            Ok(mut data) => (
+                std::mem::replace(
+                    &mut data.route_metric_policy,
+                    policy.clone(),
+                ),
+                data.lease.clone(),
+            ),
+            Err(e) => {
+                return Err(NipartError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Failed to lock share data of DHCP thread for \
+                         interface {}: {e}",
+                        self.base_iface.name
+                    ),
+                ));
+            }
+        };
+        let iface_type = &self.base_iface.iface_type;
+        let Some(lease) = lease.filter(|_| {
+            self.base_iface
+                .ipv4
+                .as_ref()
+                .is_none_or(|i| i.auto_route_metric.is_none())
+                && old_policy.base_metric(iface_type)
+                    != policy.base_metric(iface_type)
+        }) else {
+            return Ok(());
+        };
+        log::info!(
+            "Regenerating DHCPv4 routes of {}({}) for route metric policy \
+             change",
+            self.base_iface.name,
+            iface_type
+        );
+        replace_dhcp_routes(
+            NipartNoDaemon::gen_dhcpv4_routes(
+                &lease,
+                &self.base_iface,
+                &old_policy,
+            ),
+            NipartNoDaemon::gen_dhcpv4_routes(&lease, &self.base_iface, policy),
+        )
+        .await
+    }
 }
 
 async fn dhcp_thread(
-    mut dhcp_client: DhcpV4Client,
+    dhcp_client: Option<DhcpV4Client>,
     base_iface: BaseInterface,
+    client_id: String,
     mut quit_indicator: UnboundedReceiver<()>,
     share_data: Arc<Mutex<NipartDhcpShareData>>,
 ) -> Result<(), NipartError> {
@@ -238,6 +410,22 @@ async fn dhcp_thread(
             ));
         }
     }
+    let ipv4 = base_iface.ipv4.clone().unwrap_or_default();
+    let mut ipv4_ll_task = None;
+    if ipv4.is_link_local() {
+        ipv4_ll_task = Some(start_ipv4_ll(&base_iface, share_data.clone()));
+    }
+
+    let Some(mut dhcp_client) = dhcp_client else {
+        let _ = quit_indicator.next().await;
+        stop_ipv4_ll(&base_iface, ipv4_ll_task, &share_data).await;
+        return Ok(());
+    };
+
+    let mut has_lease = false;
+    let fallback_timeout = tokio::time::sleep(IPV4_LL_FALLBACK_TIMEOUT);
+    tokio::pin!(fallback_timeout);
+
     let result = loop {
         tokio::select! {
             result = dhcp_client.run() => {
@@ -249,9 +437,14 @@ async fn dhcp_thread(
                             base_iface.iface_type,
                             lease.yiaddr,
                         );
+                        let stored_lease =
+                            NipartStoredDhcpV4Lease::new(lease.as_ref().clone());
                         match share_data.lock() {
                             Ok(mut share_data) => {
                                 share_data.state = DhcpState::Done;
+                                share_data.lease_expiry =
+                                    Some(stored_lease.expiry_str());
+                                share_data.lease = Some(lease.as_ref().clone());
                             }
                             Err(e) => {
                                 break Err::<(), NipartError>(NipartError::new(
@@ -260,6 +453,17 @@ async fn dhcp_thread(
                                 ));
                             }
                         }
+                        // Failing to store lease should not stop DHCP
+                        if let Err(e) = stored_lease.save(
+                            base_iface.name.as_str(),
+                            &client_id,
+                        ).await {
+                            log::warn!(
+                                "Failed to store DHCPv4 lease of {}({}): {e}",
+                                base_iface.name,
+                                base_iface.iface_type,
+                            );
+                        }
                         if let Err(e) = apply_lease(
                             &base_iface,
                             &lease,
@@ -267,6 +471,14 @@ async fn dhcp_thread(
                         ).await {
                             break Err(e);
                         }
+                        has_lease = true;
+                        if ipv4.is_link_local_fallback() {
+                            stop_ipv4_ll(
+                                &base_iface,
+                                ipv4_ll_task.take(),
+                                &share_data,
+                            ).await;
+                        }
                     }
                     Ok(dhcp_state) => {
                         log::info!(
@@ -284,12 +496,42 @@ async fn dhcp_thread(
                     }
                 }
             }
+            _ = &mut fallback_timeout, if ipv4.is_link_local_fallback()
+                && !has_lease
+                && ipv4_ll_task.is_none() => {
+                log::info!(
+                    "DHCPv4 on {}({}) got no lease in {} seconds, falling \
+                     back to IPv4 link-local",
+                    base_iface.name,
+                    base_iface.iface_type,
+                    IPV4_LL_FALLBACK_TIMEOUT.as_secs(),
+                );
+                ipv4_ll_task =
+                    Some(start_ipv4_ll(&base_iface, share_data.clone()));
+            }
             _ = quit_indicator.next() => {
                 log::info!(
                     "Stopped DHCPv4 on {}({})",
                     base_iface.name,
                     base_iface.iface_type,
                 );
+                stop_ipv4_ll(&base_iface, ipv4_ll_task.take(), &share_data)
+                    .await;
+                NipartNoDaemon::apply_dhcp_dns(
+                    base_iface.name.as_str(),
+                    false,
+                    None,
+                )
+                .await?;
+                NipartNoDaemon::apply_dhcp_hostname(
+                    base_iface.name.as_str(),
+                    None,
+                )?;
+                NipartNoDaemon::apply_dhcp_addrs(
+                    base_iface.name.as_str(),
+                    false,
+                    &[],
+                )?;
                 return Ok(());
             }
         }
@@ -310,15 +552,109 @@ async fn dhcp_thread(
                 ));
             }
         }
+        if ipv4.is_link_local_fallback() && ipv4_ll_task.is_none() {
+            log::info!(
+                "DHCPv4 on {}({}) failed, falling back to IPv4 link-local",
+                base_iface.name,
+                base_iface.iface_type,
+            );
+            ipv4_ll_task = Some(start_ipv4_ll(&base_iface, share_data.clone()));
+        }
+    }
+    // Keep IPv4 link-local address till DHCP stopped
+    if ipv4_ll_task.is_some() {
+        let _ = quit_indicator.next().await;
+        stop_ipv4_ll(&base_iface, ipv4_ll_task, &share_data).await;
     }
     Ok(())
 }
 
+fn start_ipv4_ll(
+    base_iface: &BaseInterface,
+    share_data: Arc<Mutex<NipartDhcpShareData>>,
+) -> JoinHandle<()> {
+    let base_iface = base_iface.clone();
+    tokio::spawn(async move {
+        if let Err(e) = ipv4_ll_thread(&base_iface, share_data).await {
+            log::error!(
+                "IPv4 link-local on {}({}) failed: {e}",
+                base_iface.name,
+                base_iface.iface_type
+            );
+        }
+    })
+}
+
+async fn ipv4_ll_thread(
+    base_iface: &BaseInterface,
+    share_data: Arc<Mutex<NipartDhcpShareData>>,
+) -> Result<(), NipartError> {
+    let mut ipv4_ll = NipartIpv4LinkLocal::new(base_iface)?;
+    loop {
+        let addr = ipv4_ll.select().await?;
+        log::info!(
+            "Assigning IPv4 link-local address {addr} to {}({})",
+            base_iface.name,
+            base_iface.iface_type
+        );
+        NipartNoDaemon::apply_ipv4_link_local_addr(
+            base_iface.name.as_str(),
+            addr,
+            false,
+        )
+        .await?;
+        if let Ok(mut share_data) = share_data.lock() {
+            share_data.link_local_addr = Some(addr);
+        }
+        ipv4_ll.defend(addr).await?;
+        // Lost the address to other host, select another one
+        NipartNoDaemon::apply_ipv4_link_local_addr(
+            base_iface.name.as_str(),
+            addr,
+            true,
+        )
+        .await?;
+        if let Ok(mut share_data) = share_data.lock() {
+            share_data.link_local_addr = None;
+        }
+    }
+}
+
+async fn stop_ipv4_ll(
+    base_iface: &BaseInterface,
+    task: Option<JoinHandle<()>>,
+    share_data: &Arc<Mutex<NipartDhcpShareData>>,
+) {
+    let Some(task) = task else {
+        return;
+    };
+    task.abort();
+    let addr = share_data
+        .lock()
+        .ok()
+        .and_then(|mut share_data| share_data.link_local_addr.take());
+    if let Some(addr) = addr {
+        log::info!(
+            "Removing IPv4 link-local address {addr} from {}({})",
+            base_iface.name,
+            base_iface.iface_type
+        );
+        if let Err(e) = NipartNoDaemon::apply_ipv4_link_local_addr(
+            base_iface.name.as_str(),
+            addr,
+            true,
+        )
+        .await
+        {
+            log::warn!("{e}");
+        }
+    }
+}
+
 async fn apply_lease(
     base_iface: &BaseInterface,
     lease: &DhcpV4Lease,
-    // TODO: Support hostname, systemd-resolved, /etc/resolv.conf
-    _share_data: Arc<Mutex<NipartDhcpShareData>>,
+    share_data: Arc<Mutex<NipartDhcpShareData>>,
 ) -> Result<(), NipartError> {
     log::debug!(
         "Applying DHCPv4 lease {}/{} to interface {}({})",
@@ -333,6 +669,12 @@ async fn apply_lease(
     ip_addr.preferred_life_time = Some(format!("{}sec", lease.lease_time_sec));
     ip_addr.valid_life_time = Some(format!("{}sec", lease.lease_time_sec));
 
+    NipartNoDaemon::apply_dhcp_addrs(
+        base_iface.name.as_str(),
+        false,
+        &[lease.yiaddr.into()],
+    )?;
+
     let mut ipv4_conf = InterfaceIpv4::default();
     ipv4_conf.enabled = Some(true);
     ipv4_conf.dhcp = Some(true);
@@ -349,37 +691,73 @@ async fn apply_lease(
     let mut net_state = NetworkState::new();
     net_state.ifaces.push(iface_state);
 
-    net_state.routes = gen_routes(lease, base_iface);
+    let policy = share_data
+        .lock()
+        .map(|data| data.route_metric_policy.clone())
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to lock DHCPv4 {}({}) share data: {e}",
+                    base_iface.name, base_iface.iface_type,
+                ),
+            )
+        })?;
+    net_state.routes =
+        NipartNoDaemon::gen_dhcpv4_routes(lease, base_iface, &policy);
 
     let apply_opt = NipartApplyOption::new().memory_only().no_verify();
     NipartNoDaemon::apply_network_state(net_state, apply_opt).await?;
+
+    if base_iface
+        .ipv4
+        .as_ref()
+        .is_none_or(|ipv4_conf| ipv4_conf.is_auto_dns())
+    {
+        NipartNoDaemon::apply_dhcp_dns(
+            base_iface.name.as_str(),
+            false,
+            NipartNoDaemon::gen_dhcpv4_dns(lease).as_ref(),
+        )
+        .await?;
+    }
+    NipartNoDaemon::apply_dhcp_hostname(
+        base_iface.name.as_str(),
+        lease.host_name.as_deref(),
+    )?;
     Ok(())
 }
 
-// TODO:
-//  * Handle `auto-routes: false`
-//  * Handle `auto-gateways: false`
-//  * Handle `classless_routes`
-fn gen_routes(lease: &DhcpV4Lease, base_iface: &BaseInterface) -> Routes {
-    let mut conf_routes: Vec<RouteEntry> = Vec::new();
-    // TODO: Handle multiple addresses of router
-    if let Some(gateways) = lease.gateways.as_ref() {
-        for (index, gateway) in gateways.iter().enumerate() {
-            let mut route = RouteEntry::default();
-            route.destination = Some("0.0.0.0/0".to_string());
-            route.next_hop_iface = Some(base_iface.name.to_string());
-            route.next_hop_addr = Some(gateway.to_string());
-            route.table_id = Some(DEFAULT_ROUTE_TABLE_ID);
-            // TODO: Be consistent on metric?
-            // TODO: Priority ethernet over wifi/VPN/etc ?
-            route.metric = base_iface
-                .iface_index
-                .map(|iface_index| 100i64 * iface_index as i64 + index as i64);
-            conf_routes.push(route);
+// Route metric is ignored when matching desired routes with current ones,
+// hence routes generated with previous metric have to be removed before
+// adding the new ones.
+pub(crate) async fn replace_dhcp_routes(
+    old_routes: Routes,
+    new_routes: Routes,
+) -> Result<(), NipartError> {
+    let apply_opt = NipartApplyOption::new().memory_only().no_verify();
+    let mut absent_routes = old_routes.config.unwrap_or_default();
+    if !absent_routes.is_empty() {
+        for route in absent_routes.iter_mut() {
+            route.state = Some(RouteState::Absent);
         }
+        let mut net_state = NetworkState::new();
+        net_state.routes.config = Some(absent_routes);
+        NipartNoDaemon::apply_network_state(net_state, apply_opt.clone())
+            .await?;
     }
+    let mut net_state = NetworkState::new();
+    net_state.routes = new_routes;
+    NipartNoDaemon::apply_network_state(net_state, apply_opt).await?;
+    Ok(())
+}
 
-    let mut routes = Routes::default();
-    routes.config = Some(conf_routes);
-    routes
+// Hex string of client identifier type followed by data, see RFC 2132
+// section 9.14. For MAC based client identifier, it is hardware type
+// 1(Ethernet) followed by MAC address.
+fn lease_client_id(client_id_type: u8, client_id: &[u8]) -> String {
+    std::iter::once(&client_id_type)
+        .chain(client_id)
+        .map(|b| format!("{b:02x}"))
+        .collect()
 }
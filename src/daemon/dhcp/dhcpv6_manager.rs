@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nipart::{
+    BaseInterface, MergedNetworkState, NetworkState, NipartError,
+    NipartInterface, NipartIpcConnection, RouteMetricPolicy,
+};
+
+use super::{NipartDhcpCmd, NipartDhcpReply, NipartDhcpV6Worker};
+use crate::{TaskManager, log_debug};
+
+#[derive(Debug, Clone)]
+pub(crate) struct NipartDhcpV6Manager {
+    mgr: TaskManager<NipartDhcpCmd, NipartDhcpReply>,
+}
+
+// Do not add `async` function to NipartDhcpV6Manager because it will be stored
+// into Mutex protected `NipartDaemonShareData`. The
+// `MutexGuard` will cause function not `Send`.
+impl NipartDhcpV6Manager {
+    pub(crate) async fn new() -> Result<Self, NipartError> {
+        Ok(Self {
+            mgr: TaskManager::new::<NipartDhcpV6Worker>("dhcpv6").await?,
+        })
+    }
+
+    /// Fill the NetworkState with DHCPv6 states
+    pub(crate) async fn fill_dhcp_states(
+        &mut self,
+        net_state: &mut NetworkState,
+    ) -> Result<(), NipartError> {
+        if let NipartDhcpReply::QueryV6Reply(mut dhcp_states) =
+            self.mgr.exec(NipartDhcpCmd::Query).await?
+        {
+            for (iface_name, dhcp_ipv6) in dhcp_states.drain() {
+                if let Some(iface) =
+                    net_state.ifaces.kernel_ifaces.get_mut(iface_name.as_str())
+                {
+                    let ipv6_conf = iface
+                        .base_iface_mut()
+                        .ipv6
+                        .get_or_insert(Default::default());
+                    ipv6_conf.enabled = Some(true);
+                    ipv6_conf.dhcp = Some(true);
+                    ipv6_conf.dhcp_mode = dhcp_ipv6.dhcp_mode;
+                    ipv6_conf.dhcp_state = dhcp_ipv6.dhcp_state;
+                    ipv6_conf.copy_auto_options(&dhcp_ipv6);
+                    ipv6_conf.copy_dhcp_id_options(&dhcp_ipv6);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn start_iface_dhcp(
+        &mut self,
+        base_iface: &BaseInterface,
+        policy: &RouteMetricPolicy,
+    ) -> Result<(), NipartError> {
+        self.mgr
+            .exec(NipartDhcpCmd::StartIfaceDhcp(
+                Box::new(base_iface.clone()),
+                Box::new(policy.clone()),
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn stop_iface_dhcp(
+        &mut self,
+        iface_name: &str,
+        remove_lease: bool,
+    ) -> Result<(), NipartError> {
+        self.mgr
+            .exec(NipartDhcpCmd::StopIfaceDhcp(
+                iface_name.to_string(),
+                remove_lease,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn apply_dhcp_config(
+        &mut self,
+        mut conn: Option<&mut NipartIpcConnection>,
+        merged_state: &MergedNetworkState,
+    ) -> Result<(), NipartError> {
+        let route_metric_policy = merged_state.route_metric_policy.merged();
+        for merged_iface in merged_state
+            .ifaces
+            .iter()
+            .filter(|i| i.is_changed() && !i.merged.is_userspace())
+        {
+            let mut apply_iface = match merged_iface.for_apply.as_ref() {
+                Some(i) => i.clone(),
+                None => {
+                    continue;
+                }
+            };
+            // DUID might be generated from MAC address
+            if apply_iface.base_iface().mac_address.is_none() {
+                apply_iface.base_iface_mut().mac_address =
+                    merged_iface.merged.base_iface().mac_address.clone();
+            }
+            apply_iface.base_iface_mut().iface_index =
+                merged_iface.merged.base_iface().iface_index;
+            // Route metric policy is based on interface type
+            apply_iface.base_iface_mut().iface_type =
+                merged_iface.merged.iface_type().clone();
+            if apply_iface.is_up() {
+                if let Some(dhcp_enabled) =
+                    apply_iface.base_iface().ipv6.as_ref().map(|i| i.is_dhcp())
+                {
+                    if dhcp_enabled {
+                        // Use merged DHCPv6 mode, `auto-*` and DUID options in
+                        // case desired state only changing other properties.
+                        if let Some(ipv6_conf) =
+                            apply_iface.base_iface_mut().ipv6.as_mut()
+                            && let Some(merged_ipv6) =
+                                merged_iface.merged.base_iface().ipv6.as_ref()
+                        {
+                            if ipv6_conf.dhcp_mode.is_none() {
+                                ipv6_conf.dhcp_mode = merged_ipv6.dhcp_mode;
+                            }
+                            ipv6_conf.copy_auto_options(merged_ipv6);
+                            ipv6_conf.copy_dhcp_id_options(merged_ipv6);
+                        }
+                        log_debug(
+                            conn.as_deref_mut(),
+                            format!(
+                                "Starting DHCPv6 on interface {}({})",
+                                apply_iface.name(),
+                                apply_iface.iface_type()
+                            ),
+                        )
+                        .await;
+                        self.start_iface_dhcp(
+                            apply_iface.base_iface(),
+                            &route_metric_policy,
+                        )
+                        .await?;
+                    } else {
+                        log_debug(
+                            conn.as_deref_mut(),
+                            format!(
+                                "Stopping DHCPv6 on interface {}({})",
+                                apply_iface.name(),
+                                apply_iface.iface_type()
+                            ),
+                        )
+                        .await;
+                        self.stop_iface_dhcp(apply_iface.name(), true).await?;
+                    }
+                }
+            } else {
+                // Keep the lease for interface been set to down
+                self.stop_iface_dhcp(
+                    apply_iface.name(),
+                    apply_iface.is_absent(),
+                )
+                .await?;
+            }
+        }
+
+        // Interfaces not changed in this apply might still hold routes
+        // generated by previous route metric policy
+        if merged_state.route_metric_policy.is_changed() {
+            self.mgr
+                .exec(NipartDhcpCmd::ApplyRouteMetricPolicy(Box::new(
+                    route_metric_policy,
+                )))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,584 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures_channel::{
+    mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+    oneshot::Sender,
+};
+use futures_util::StreamExt;
+use mozim::{DhcpV6Client, DhcpV6Config, DhcpV6Lease, DhcpV6State};
+use nipart::{
+    BaseInterface, DhcpState, DhcpV6Mode, DnsResolverConfig, ErrorKind,
+    Interface, InterfaceIpAddr, InterfaceIpv6, NetworkState, NipartApplyOption,
+    NipartError, NipartInterface, NipartNoDaemon, NipartQueryOption,
+    RouteEntry, RouteMetricPolicy, RouteType, Routes,
+};
+
+use super::{NipartDhcpCmd, NipartDhcpReply, dhcp_worker::replace_dhcp_routes};
+use crate::TaskWorker;
+
+// Zero prefix length in IA_PD prefix option means no preference
+const PD_PREFIX_LEN_NO_HINT: u8 = 0;
+
+type FromManager =
+    (NipartDhcpCmd, Sender<Result<NipartDhcpReply, NipartError>>);
+
+#[derive(Debug)]
+pub(crate) struct NipartDhcpV6Worker {
+    threads: HashMap<String, NipartDhcpV6Thread>,
+    receiver: UnboundedReceiver<FromManager>,
+}
+
+impl TaskWorker for NipartDhcpV6Worker {
+    type Cmd = NipartDhcpCmd;
+    type Reply = NipartDhcpReply;
+
+    async fn new(
+        receiver: UnboundedReceiver<(
+            Self::Cmd,
+            Sender<Result<Self::Reply, NipartError>>,
+        )>,
+    ) -> Result<Self, NipartError> {
+        Ok(Self {
+            threads: HashMap::new(),
+            receiver,
+        })
+    }
+
+    fn receiver(&mut self) -> &mut UnboundedReceiver<FromManager> {
+        &mut self.receiver
+    }
+
+    async fn process_cmd(
+        &mut self,
+        cmd: NipartDhcpCmd,
+    ) -> Result<NipartDhcpReply, NipartError> {
+        match cmd {
+            NipartDhcpCmd::StartIfaceDhcp(base_iface, policy) => {
+                let iface_name = base_iface.name.clone();
+                let thread =
+                    NipartDhcpV6Thread::new(*base_iface, *policy).await?;
+                self.threads.insert(iface_name.clone(), thread);
+                log::debug!("DHCPv6 thread started on interface {iface_name}");
+                Ok(NipartDhcpReply::None)
+            }
+            // DHCPv6 lease is not stored
+            NipartDhcpCmd::StopIfaceDhcp(iface, _) => {
+                self.threads.remove(&iface);
+                Ok(NipartDhcpReply::None)
+            }
+            NipartDhcpCmd::Query => {
+                let mut ret = HashMap::new();
+                for (iface_name, thread) in self.threads.iter() {
+                    ret.insert(iface_name.to_string(), thread.get_state()?);
+                }
+
+                Ok(NipartDhcpReply::QueryV6Reply(ret))
+            }
+            NipartDhcpCmd::ApplyRouteMetricPolicy(policy) => {
+                for thread in self.threads.values() {
+                    thread.apply_route_metric_policy(&policy).await?;
+                }
+                Ok(NipartDhcpReply::None)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct NipartDhcpV6ShareData {
+    state: DhcpState,
+    // Only stored for prefix delegation which generates routes
+    pd_lease: Option<DhcpV6Lease>,
+    route_metric_policy: RouteMetricPolicy,
+}
+
+#[derive(Debug)]
+pub(crate) struct NipartDhcpV6Thread {
+    pub(crate) base_iface: BaseInterface,
+    pub(crate) mode: DhcpV6Mode,
+    // No need to send any data. Dropping this Sender will cause
+    // Receiver.recv() got None which trigger DHCP thread quit.
+    _quit_notifer: UnboundedSender<()>,
+    share_data: Arc<Mutex<NipartDhcpV6ShareData>>,
+}
+
+impl NipartDhcpV6Thread {
+    pub(crate) async fn new(
+        base_iface: BaseInterface,
+        route_metric_policy: RouteMetricPolicy,
+    ) -> Result<Self, NipartError> {
+        let (sender, receiver) = unbounded();
+        let mode = base_iface
+            .ipv6
+            .as_ref()
+            .and_then(|i| i.dhcp_mode)
+            .unwrap_or_default();
+        let ret = Self {
+            base_iface: base_iface.clone(),
+            mode,
+            _quit_notifer: sender,
+            share_data: Arc::new(Mutex::new(NipartDhcpV6ShareData {
+                route_metric_policy,
+                ..Default::default()
+            })),
+        };
+        let iface_index = match base_iface.iface_index {
+            Some(m) => m,
+            None => {
+                return Err(NipartError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Got no interface index for DHCPv6 on interface {}({})",
+                        base_iface.name, base_iface.iface_type
+                    ),
+                ));
+            }
+        };
+        let mozim_mode = match mode {
+            DhcpV6Mode::Stateful => mozim::DhcpV6Mode::NonTemporaryAddresses,
+            DhcpV6Mode::PrefixDelegation => {
+                mozim::DhcpV6Mode::PrefixDelegation(PD_PREFIX_LEN_NO_HINT)
+            }
+            DhcpV6Mode::InfoRequest => mozim::DhcpV6Mode::InformationRequest,
+            m => {
+                return Err(NipartError::new(
+                    ErrorKind::NoSupport,
+                    format!(
+                        "Unsupported DHCPv6 mode {m} on interface {}({})",
+                        base_iface.name, base_iface.iface_type
+                    ),
+                ));
+            }
+        };
+        let mut dhcp_config =
+            DhcpV6Config::new(base_iface.name.as_str(), mozim_mode);
+        dhcp_config.set_iface_index(iface_index);
+        let duid = NipartNoDaemon::get_dhcp_duid(
+            base_iface.name.as_str(),
+            base_iface.mac_address.as_deref(),
+            base_iface.ipv6.as_ref().and_then(|i| i.dhcp_duid.as_ref()),
+        )?;
+        dhcp_config.set_duid(mozim::DhcpV6Duid::Other(duid));
+        let dhcp_client =
+            DhcpV6Client::init(dhcp_config, None).await.map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Failed to start DHCPv6 client on iface {}/{}: {e}",
+                        base_iface.name, base_iface.iface_type,
+                    ),
+                )
+            })?;
+
+        let share_data = ret.share_data.clone();
+        tokio::spawn(async move {
+            if let Err(e) =
+                dhcp_thread(dhcp_client, base_iface, mode, receiver, share_data)
+                    .await
+            {
+                log::error!("{e}");
+            }
+        });
+        Ok(ret)
+    }
+
+    /// Return IPv6 configuration holding DHCPv6 mode, state and `auto-*`
+    /// options.
+    pub(crate) fn get_state(&self) -> Result<InterfaceIpv6, NipartError> {
+        match self.share_data.lock() {
+            Ok(data) => {
+                let mut ipv6_conf = InterfaceIpv6::default();
+                ipv6_conf.enabled = Some(true);
+                ipv6_conf.dhcp = Some(true);
+                ipv6_conf.dhcp_mode = Some(self.mode);
+                ipv6_conf.dhcp_state = Some(data.state.clone());
+                if let Some(cur_ipv6) = self.base_iface.ipv6.as_ref() {
+                    ipv6_conf.copy_auto_options(cur_ipv6);
+                    ipv6_conf.copy_dhcp_id_options(cur_ipv6);
+                }
+                Ok(ipv6_conf)
+            }
+            Err(e) => Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to lock share data of DHCPv6 thread for interface \
+                     {}: {e}",
+                    self.base_iface.name
+                ),
+            )),
+        }
+    }
+
+    /// Store new route metric policy for further lease and regenerate routes
+    /// of current delegated prefix if their metric changed.
+    pub(crate) async fn apply_route_metric_policy(
+        &self,
+        policy: &RouteMetricPolicy,
+    ) -> Result<(), NipartError> {
+        let (old_policy, lease) = match self.share_data.lock() {
+            Ok(mut data) => (
+                std::mem::replace(
+                    &mut data.route_metric_policy,
+                    policy.clone(),
+                ),
+                data.pd_lease.clone(),
+            ),
+            Err(e) => {
+                return Err(NipartError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Failed to lock share data of DHCPv6 thread for \
+                         interface {}: {e}",
+                        self.base_iface.name
+                    ),
+                ));
+            }
+        };
+        let iface_type = &self.base_iface.iface_type;
+        let Some(lease) = lease.filter(|_| {
+            self.base_iface
+                .ipv6
+                .as_ref()
+                .is_none_or(|i| i.auto_route_metric.is_none())
+                && old_policy.base_metric(iface_type)
+                    != policy.base_metric(iface_type)
+        }) else {
+            return Ok(());
+        };
+        log::info!(
+            "Regenerating DHCPv6 delegated prefix routes of {}({}) for route \
+             metric policy change",
+            self.base_iface.name,
+            iface_type
+        );
+        replace_dhcp_routes(
+            gen_pd_routes(&self.base_iface, &lease, &old_policy),
+            gen_pd_routes(&self.base_iface, &lease, policy),
+        )
+        .await
+    }
+}
+
+fn set_state(
+    base_iface: &BaseInterface,
+    share_data: &Arc<Mutex<NipartDhcpV6ShareData>>,
+    state: DhcpState,
+) -> Result<(), NipartError> {
+    match share_data.lock() {
+        Ok(mut share_data) => {
+            share_data.state = state;
+            Ok(())
+        }
+        Err(e) => Err(NipartError::new(
+            ErrorKind::Bug,
+            format!(
+                "Failed to lock DHCPv6 {}({}) share data: {e}",
+                base_iface.name, base_iface.iface_type,
+            ),
+        )),
+    }
+}
+
+async fn dhcp_thread(
+    mut dhcp_client: DhcpV6Client,
+    base_iface: BaseInterface,
+    mode: DhcpV6Mode,
+    mut quit_indicator: UnboundedReceiver<()>,
+    share_data: Arc<Mutex<NipartDhcpV6ShareData>>,
+) -> Result<(), NipartError> {
+    log::debug!(
+        "Waiting link carrier up for interface {}/{} before start DHCPv6",
+        base_iface.name,
+        base_iface.iface_type
+    );
+    NipartNoDaemon::wait_link_carrier_up(base_iface.name.as_str()).await?;
+    log::debug!(
+        "Interface {}/{} link carrier is up, starting DHCPv6 process",
+        base_iface.name,
+        base_iface.iface_type
+    );
+    set_state(&base_iface, &share_data, DhcpState::Running)?;
+    let result = loop {
+        tokio::select! {
+            result = dhcp_client.run() => {
+                match result {
+                    Ok(DhcpV6State::Done(lease)) => {
+                        // Information request has no address leased
+                        if mode == DhcpV6Mode::InfoRequest {
+                            log::info!(
+                                "DHCPv6 {mode} on {}({}) got reply",
+                                base_iface.name,
+                                base_iface.iface_type,
+                            );
+                        } else {
+                            log::info!(
+                                "DHCPv6 {mode} on {}({}) got lease {}/{}",
+                                base_iface.name,
+                                base_iface.iface_type,
+                                lease.addr,
+                                lease.prefix_len,
+                            );
+                        }
+                        if let Err(e) = set_state(
+                            &base_iface,
+                            &share_data,
+                            DhcpState::Done,
+                        ) {
+                            break Err(e);
+                        }
+                        if let Err(e) = apply_lease(
+                            &base_iface,
+                            mode,
+                            &lease,
+                            &share_data,
+                        )
+                        .await
+                        {
+                            break Err(e);
+                        }
+                    }
+                    Ok(dhcp_state) => {
+                        log::info!(
+                            "DHCPv6 on {}({}) reach {} state",
+                            base_iface.name,
+                            base_iface.iface_type,
+                            dhcp_state
+                        );
+                    }
+                    Err(e) => {
+                        break Err(NipartError::new(
+                            ErrorKind::Bug,
+                            format!("Unhandled DHCPv6 error: {e}"),
+                        ));
+                    }
+                }
+            }
+            _ = quit_indicator.next() => {
+                log::info!(
+                    "Stopped DHCPv6 on {}({})",
+                    base_iface.name,
+                    base_iface.iface_type,
+                );
+                NipartNoDaemon::apply_dhcp_dns(
+                    base_iface.name.as_str(),
+                    true,
+                    None,
+                )
+                .await?;
+                NipartNoDaemon::apply_dhcp_addrs(
+                    base_iface.name.as_str(),
+                    true,
+                    &[],
+                )?;
+                return Ok(());
+            }
+        }
+    };
+
+    if let Err(e) = result {
+        set_state(&base_iface, &share_data, DhcpState::Error(e.to_string()))?;
+        // Remove DNS learned from this DHCPv6 session as nobody will
+        // refresh it anymore.
+        if let Err(e) =
+            NipartNoDaemon::apply_dhcp_dns(base_iface.name.as_str(), true, None)
+                .await
+        {
+            log::warn!(
+                "Failed to remove DHCPv6 DNS of {}({}): {e}",
+                base_iface.name,
+                base_iface.iface_type,
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn apply_lease(
+    base_iface: &BaseInterface,
+    mode: DhcpV6Mode,
+    lease: &DhcpV6Lease,
+    share_data: &Arc<Mutex<NipartDhcpV6ShareData>>,
+) -> Result<(), NipartError> {
+    let mut net_state = NetworkState::new();
+    match mode {
+        DhcpV6Mode::Stateful => {
+            log::debug!(
+                "Applying DHCPv6 lease {}/{} to interface {}({})",
+                lease.addr,
+                lease.prefix_len,
+                base_iface.name,
+                base_iface.iface_type
+            );
+            NipartNoDaemon::apply_dhcp_addrs(
+                base_iface.name.as_str(),
+                true,
+                &[lease.addr.into()],
+            )?;
+            let mut apply_base_iface = base_iface.clone_name_type_only();
+            apply_base_iface.ipv6 =
+                Some(gen_ipv6_conf(base_iface, mode, lease).await?);
+            let iface_state: Interface = apply_base_iface.into();
+            net_state.ifaces.push(iface_state);
+        }
+        DhcpV6Mode::PrefixDelegation => {
+            log::debug!(
+                "Installing unreachable route for DHCPv6 delegated prefix \
+                 {}/{} of interface {}({})",
+                lease.addr,
+                lease.prefix_len,
+                base_iface.name,
+                base_iface.iface_type
+            );
+            let policy = match share_data.lock() {
+                Ok(mut data) => {
+                    data.pd_lease = Some(lease.clone());
+                    data.route_metric_policy.clone()
+                }
+                Err(e) => {
+                    return Err(NipartError::new(
+                        ErrorKind::Bug,
+                        format!(
+                            "Failed to lock DHCPv6 {}({}) share data: {e}",
+                            base_iface.name, base_iface.iface_type,
+                        ),
+                    ));
+                }
+            };
+            net_state.routes = gen_pd_routes(base_iface, lease, &policy);
+        }
+        _ => (),
+    }
+
+    if !net_state.is_empty() {
+        let apply_opt = NipartApplyOption::new().memory_only().no_verify();
+        NipartNoDaemon::apply_network_state(net_state, apply_opt).await?;
+    }
+
+    if base_iface
+        .ipv6
+        .as_ref()
+        .is_none_or(|ipv6_conf| ipv6_conf.is_auto_dns())
+    {
+        NipartNoDaemon::apply_dhcp_dns(
+            base_iface.name.as_str(),
+            true,
+            lease_dns(base_iface, lease).as_ref(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+// IPv6 link-local DNS server is only reachable via the interface lease
+// learned from.
+fn lease_dns(
+    base_iface: &BaseInterface,
+    lease: &DhcpV6Lease,
+) -> Option<DnsResolverConfig> {
+    let servers: Vec<String> = lease
+        .dns_srvs
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|s| {
+            if s.is_unicast_link_local() {
+                format!("{s}%{}", base_iface.name)
+            } else {
+                s.to_string()
+            }
+        })
+        .collect();
+    let searches: Vec<String> =
+        lease.domain_search.as_deref().unwrap_or_default().to_vec();
+    if servers.is_empty() && searches.is_empty() {
+        None
+    } else {
+        Some(DnsResolverConfig::new(servers, searches))
+    }
+}
+
+// Append leased address to current addresses, so addresses assigned by
+// IPv6 autoconf will not be purged.
+async fn gen_ipv6_conf(
+    base_iface: &BaseInterface,
+    mode: DhcpV6Mode,
+    lease: &DhcpV6Lease,
+) -> Result<InterfaceIpv6, NipartError> {
+    let cur_state =
+        NipartNoDaemon::query_network_state(NipartQueryOption::running())
+            .await?;
+    let mut addrs: Vec<InterfaceIpAddr> = cur_state
+        .ifaces
+        .kernel_ifaces
+        .get(base_iface.name.as_str())
+        .and_then(|i| i.base_iface().ipv6.as_ref())
+        .and_then(|i| i.addresses.as_ref())
+        .map(|addrs| {
+            addrs
+                .iter()
+                .filter(|a| {
+                    a.ip != lease.addr
+                        && !matches!(
+                            a.ip,
+                            std::net::IpAddr::V6(ip)
+                                if ip.is_unicast_link_local()
+                        )
+                })
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut ip_addr = InterfaceIpAddr::new(lease.addr.into(), lease.prefix_len);
+    ip_addr.preferred_life_time = Some(format!("{}sec", lease.preferred_time));
+    ip_addr.valid_life_time = Some(format!("{}sec", lease.valid_time));
+    addrs.push(ip_addr);
+
+    let mut ipv6_conf = InterfaceIpv6::default();
+    ipv6_conf.enabled = Some(true);
+    ipv6_conf.dhcp = Some(true);
+    ipv6_conf.dhcp_mode = Some(mode);
+    ipv6_conf.addresses = Some(addrs);
+    // Preserve `auto-*` options, otherwise applying lease will reset the
+    // sysctl of IPv6 router announcement to default.
+    if let Some(cur_ipv6) = base_iface.ipv6.as_ref() {
+        ipv6_conf.copy_auto_options(cur_ipv6);
+    }
+    Ok(ipv6_conf)
+}
+
+// Install unreachable route for delegated prefix to prevent routing loop
+// between us and upstream router for addresses not assigned to downstream
+// networks, see RFC 7084 WPD-5.
+fn gen_pd_routes(
+    base_iface: &BaseInterface,
+    lease: &DhcpV6Lease,
+    policy: &RouteMetricPolicy,
+) -> Routes {
+    let ipv6_conf = base_iface.ipv6.clone().unwrap_or_default();
+    let mut routes = Routes::default();
+    if !ipv6_conf.is_auto_routes() {
+        return routes;
+    }
+    let mut route = RouteEntry::default();
+    route.destination = Some(format!("{}/{}", lease.addr, lease.prefix_len));
+    route.route_type = Some(RouteType::Unreachable);
+    route.table_id = Some(ipv6_conf.auto_table_id());
+    // Interface index offset prevents routes from different interfaces
+    // sharing the same metric
+    route.metric = Some(match ipv6_conf.auto_route_metric {
+        Some(m) => i64::from(m),
+        None => {
+            i64::from(policy.base_metric(&base_iface.iface_type))
+                + i64::from(base_iface.iface_index.unwrap_or_default())
+        }
+    });
+
+    routes.config = Some(vec![route]);
+    routes
+}
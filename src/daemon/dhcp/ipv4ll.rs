@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    net::Ipv4Addr,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    time::{Duration, Instant},
+};
+
+use nipart::{BaseInterface, ErrorKind, NipartError};
+use nix::libc;
+use tokio::io::unix::AsyncFd;
+
+// Timing constants defined in RFC 3927 section 9
+const PROBE_WAIT: Duration = Duration::from_secs(1);
+const PROBE_NUM: u32 = 3;
+const PROBE_MIN: Duration = Duration::from_secs(1);
+const PROBE_MAX: Duration = Duration::from_secs(2);
+const ANNOUNCE_WAIT: Duration = Duration::from_secs(2);
+const ANNOUNCE_NUM: u32 = 2;
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_CONFLICTS: u32 = 10;
+const RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(60);
+const DEFEND_INTERVAL: Duration = Duration::from_secs(10);
+
+// Defined in linux/if_ether.h and linux/if_arp.h
+const ETH_P_ARP: u16 = 0x0806;
+const ETH_P_IP: u16 = 0x0800;
+const ARPHRD_ETHER: u16 = 1;
+const ARPOP_REQUEST: u16 = 1;
+const ETH_ALEN: usize = 6;
+const ARP_PKT_LEN: usize = 28;
+const BROADCAST_MAC: [u8; ETH_ALEN] = [0xff; ETH_ALEN];
+
+// Usable range is 169.254.1.0 to 169.254.254.255, RFC 3927 section 2.1
+const LINK_LOCAL_FIRST_OFFSET: u32 = 0x0100;
+const LINK_LOCAL_OFFSET_COUNT: u32 = 0xfe00;
+
+/// IPv4 link-local address selection and defending defined in RFC 3927 via
+/// ARP probe and announcement.
+#[derive(Debug)]
+pub(crate) struct NipartIpv4LinkLocal {
+    iface_name: String,
+    mac: [u8; ETH_ALEN],
+    socket: ArpSocket,
+    rng: u64,
+}
+
+impl NipartIpv4LinkLocal {
+    pub(crate) fn new(base_iface: &BaseInterface) -> Result<Self, NipartError> {
+        let iface_name = base_iface.name.clone();
+        let Some(iface_index) = base_iface.iface_index else {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Got no interface index for IPv4 link-local on interface \
+                     {iface_name}"
+                ),
+            ));
+        };
+        let mac = base_iface
+            .mac_address
+            .as_deref()
+            .and_then(parse_mac)
+            .ok_or_else(|| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Got no valid MAC address for IPv4 link-local on \
+                         interface {iface_name}"
+                    ),
+                )
+            })?;
+        let socket = ArpSocket::new(iface_name.as_str(), iface_index, mac)?;
+        // RFC 3927 section 2.1: seed the pseudo-random generator with MAC
+        // address, so host is likely to select the same address after
+        // reboot.
+        let rng = mac.iter().fold(0xcbf29ce484222325u64, |hash, b| {
+            (hash ^ u64::from(*b)).wrapping_mul(0x100000001b3)
+        });
+        Ok(Self {
+            iface_name,
+            mac,
+            socket,
+            rng,
+        })
+    }
+
+    /// Select an unused IPv4 link-local address by probing candidates and
+    /// announce it once no conflict found.
+    pub(crate) async fn select(&mut self) -> Result<Ipv4Addr, NipartError> {
+        let mut conflicts: u32 = 0;
+        loop {
+            let candidate = self.gen_candidate();
+            if conflicts >= MAX_CONFLICTS {
+                log::info!(
+                    "Too many IPv4 link-local address conflicts on interface \
+                     {}, waiting {} seconds before next probe",
+                    self.iface_name,
+                    RATE_LIMIT_INTERVAL.as_secs()
+                );
+                tokio::time::sleep(RATE_LIMIT_INTERVAL).await;
+            } else {
+                tokio::time::sleep(
+                    self.random_duration(Duration::ZERO, PROBE_WAIT),
+                )
+                .await;
+            }
+            log::debug!(
+                "Probing IPv4 link-local address {candidate} on interface {}",
+                self.iface_name
+            );
+            if self.probe(candidate).await? {
+                log::info!(
+                    "IPv4 link-local address {candidate} is in use on the \
+                     link of interface {}",
+                    self.iface_name
+                );
+                conflicts += 1;
+                continue;
+            }
+            self.announce(candidate).await?;
+            return Ok(candidate);
+        }
+    }
+
+    /// Listen on ARP packets claiming the same address and defend it.
+    /// Return when address should be given up due to repeated conflict
+    /// within [DEFEND_INTERVAL].
+    pub(crate) async fn defend(
+        &mut self,
+        addr: Ipv4Addr,
+    ) -> Result<(), NipartError> {
+        let mut last_defend: Option<Instant> = None;
+        loop {
+            let pkt = self.socket.recv().await?;
+            if pkt.sender_mac == self.mac || pkt.sender_ip != addr {
+                continue;
+            }
+            if last_defend.is_some_and(|t| t.elapsed() < DEFEND_INTERVAL) {
+                log::info!(
+                    "IPv4 link-local address {addr} on interface {} conflicts \
+                     with host {}, giving up",
+                    self.iface_name,
+                    mac_to_string(&pkt.sender_mac)
+                );
+                return Ok(());
+            }
+            log::info!(
+                "Defending IPv4 link-local address {addr} on interface {} \
+                 against host {}",
+                self.iface_name,
+                mac_to_string(&pkt.sender_mac)
+            );
+            self.socket.send(addr, addr).await?;
+            last_defend = Some(Instant::now());
+        }
+    }
+
+    // Return true if address is used by other host
+    async fn probe(
+        &mut self,
+        candidate: Ipv4Addr,
+    ) -> Result<bool, NipartError> {
+        for i in 0..PROBE_NUM {
+            self.socket.send(Ipv4Addr::UNSPECIFIED, candidate).await?;
+            let wait = if i + 1 == PROBE_NUM {
+                ANNOUNCE_WAIT
+            } else {
+                self.random_duration(PROBE_MIN, PROBE_MAX)
+            };
+            if self.wait_conflict(candidate, wait).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn announce(&mut self, addr: Ipv4Addr) -> Result<(), NipartError> {
+        for i in 0..ANNOUNCE_NUM {
+            if i != 0 {
+                tokio::time::sleep(ANNOUNCE_INTERVAL).await;
+            }
+            self.socket.send(addr, addr).await?;
+        }
+        Ok(())
+    }
+
+    // RFC 3927 section 2.2.1: conflict if any ARP packet claiming the
+    // candidate as sender address, or other host is probing the same
+    // candidate.
+    async fn wait_conflict(
+        &mut self,
+        candidate: Ipv4Addr,
+        timeout: Duration,
+    ) -> Result<bool, NipartError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let pkt =
+                match tokio::time::timeout_at(deadline, self.socket.recv())
+                    .await
+                {
+                    Ok(pkt) => pkt?,
+                    Err(_) => return Ok(false),
+                };
+            if pkt.sender_mac == self.mac {
+                continue;
+            }
+            if pkt.sender_ip == candidate
+                || (pkt.sender_ip.is_unspecified()
+                    && pkt.target_ip == candidate)
+            {
+                return Ok(true);
+            }
+        }
+    }
+
+    fn gen_candidate(&mut self) -> Ipv4Addr {
+        let offset = LINK_LOCAL_FIRST_OFFSET
+            + (self.next_random() % u64::from(LINK_LOCAL_OFFSET_COUNT)) as u32;
+        Ipv4Addr::new(169, 254, (offset >> 8) as u8, (offset & 0xff) as u8)
+    }
+
+    fn random_duration(&mut self, min: Duration, max: Duration) -> Duration {
+        let range = (max - min).as_millis() as u64;
+        if range == 0 {
+            min
+        } else {
+            min + Duration::from_millis(self.next_random() % range)
+        }
+    }
+
+    // xorshift64*, no need to be cryptographically secure
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545f4914f6cdd1d)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ArpPacket {
+    sender_mac: [u8; ETH_ALEN],
+    sender_ip: Ipv4Addr,
+    target_ip: Ipv4Addr,
+}
+
+impl ArpPacket {
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < ARP_PKT_LEN
+            || u16::from_be_bytes([data[0], data[1]]) != ARPHRD_ETHER
+            || u16::from_be_bytes([data[2], data[3]]) != ETH_P_IP
+            || usize::from(data[4]) != ETH_ALEN
+            || data[5] != 4
+        {
+            return None;
+        }
+        let mut sender_mac = [0u8; ETH_ALEN];
+        sender_mac.copy_from_slice(&data[8..14]);
+        Some(Self {
+            sender_mac,
+            sender_ip: Ipv4Addr::new(data[14], data[15], data[16], data[17]),
+            target_ip: Ipv4Addr::new(data[24], data[25], data[26], data[27]),
+        })
+    }
+}
+
+// Packet socket of SOCK_DGRAM type bound to specified interface, kernel
+// will handle the ethernet header.
+#[derive(Debug)]
+struct ArpSocket {
+    fd: AsyncFd<OwnedFd>,
+    iface_name: String,
+    iface_index: u32,
+    mac: [u8; ETH_ALEN],
+}
+
+impl ArpSocket {
+    fn new(
+        iface_name: &str,
+        iface_index: u32,
+        mac: [u8; ETH_ALEN],
+    ) -> Result<Self, NipartError> {
+        let raw_fd = unsafe {
+            libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_DGRAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                i32::from(ETH_P_ARP.to_be()),
+            )
+        };
+        if raw_fd < 0 {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to create ARP socket for interface {iface_name}: \
+                     {}",
+                    std::io::Error::last_os_error()
+                ),
+            ));
+        }
+        // The fd is valid and owned by nobody else
+        let fd = unsafe { OwnedFd::from_raw_fd(raw_fd) };
+        let addr = sockaddr_ll(iface_index, None);
+        let rc = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        };
+        if rc != 0 {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to bind ARP socket to interface {iface_name}: {}",
+                    std::io::Error::last_os_error()
+                ),
+            ));
+        }
+        let fd = AsyncFd::new(fd).map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to register ARP socket of interface {iface_name} \
+                     to tokio: {e}"
+                ),
+            )
+        })?;
+        Ok(Self {
+            fd,
+            iface_name: iface_name.to_string(),
+            iface_index,
+            mac,
+        })
+    }
+
+    // Broadcast ARP request. ARP probe is using 0.0.0.0 as sender IP,
+    // ARP announcement is using the same sender and target IP.
+    async fn send(
+        &self,
+        sender_ip: Ipv4Addr,
+        target_ip: Ipv4Addr,
+    ) -> Result<(), NipartError> {
+        let mut pkt = Vec::with_capacity(ARP_PKT_LEN);
+        pkt.extend_from_slice(&ARPHRD_ETHER.to_be_bytes());
+        pkt.extend_from_slice(&ETH_P_IP.to_be_bytes());
+        pkt.push(ETH_ALEN as u8);
+        pkt.push(4);
+        pkt.extend_from_slice(&ARPOP_REQUEST.to_be_bytes());
+        pkt.extend_from_slice(&self.mac);
+        pkt.extend_from_slice(&sender_ip.octets());
+        pkt.extend_from_slice(&[0u8; ETH_ALEN]);
+        pkt.extend_from_slice(&target_ip.octets());
+
+        let addr = sockaddr_ll(self.iface_index, Some(BROADCAST_MAC));
+        loop {
+            let mut guard =
+                self.fd.writable().await.map_err(|e| {
+                    self.io_error("wait ARP socket writable", e)
+                })?;
+            match guard.try_io(|fd| {
+                let rc = unsafe {
+                    libc::sendto(
+                        fd.as_raw_fd(),
+                        pkt.as_ptr() as *const libc::c_void,
+                        pkt.len(),
+                        0,
+                        &addr as *const libc::sockaddr_ll
+                            as *const libc::sockaddr,
+                        std::mem::size_of::<libc::sockaddr_ll>()
+                            as libc::socklen_t,
+                    )
+                };
+                if rc < 0 {
+                    Err(std::io::Error::last_os_error())
+                } else {
+                    Ok(())
+                }
+            }) {
+                Ok(result) => {
+                    return result
+                        .map_err(|e| self.io_error("send ARP packet", e));
+                }
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    // Wait till got a valid ARP packet
+    async fn recv(&self) -> Result<ArpPacket, NipartError> {
+        let mut buffer = [0u8; 1500];
+        loop {
+            let mut guard =
+                self.fd.readable().await.map_err(|e| {
+                    self.io_error("wait ARP socket readable", e)
+                })?;
+            let len = match guard.try_io(|fd| {
+                let rc = unsafe {
+                    libc::recv(
+                        fd.as_raw_fd(),
+                        buffer.as_mut_ptr() as *mut libc::c_void,
+                        buffer.len(),
+                        0,
+                    )
+                };
+                if rc < 0 {
+                    Err(std::io::Error::last_os_error())
+                } else {
+                    Ok(rc as usize)
+                }
+            }) {
+                Ok(result) => result
+                    .map_err(|e| self.io_error("receive ARP packet", e))?,
+                Err(_would_block) => continue,
+            };
+            if let Some(pkt) = ArpPacket::parse(&buffer[..len]) {
+                return Ok(pkt);
+            }
+        }
+    }
+
+    fn io_error(&self, action: &str, e: std::io::Error) -> NipartError {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to {action} on interface {}: {e}", self.iface_name),
+        )
+    }
+}
+
+fn sockaddr_ll(
+    iface_index: u32,
+    dst_mac: Option<[u8; ETH_ALEN]>,
+) -> libc::sockaddr_ll {
+    // All zero is valid for `struct sockaddr_ll`
+    let mut addr: libc::sockaddr_ll = unsafe { std::mem::zeroed() };
+    addr.sll_family = libc::AF_PACKET as u16;
+    addr.sll_protocol = ETH_P_ARP.to_be();
+    addr.sll_ifindex = iface_index as i32;
+    if let Some(dst_mac) = dst_mac {
+        addr.sll_halen = ETH_ALEN as u8;
+        addr.sll_addr[..ETH_ALEN].copy_from_slice(&dst_mac);
+    }
+    addr
+}
+
+fn parse_mac(mac: &str) -> Option<[u8; ETH_ALEN]> {
+    let bytes = mac
+        .split(':')
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    bytes.try_into().ok()
+}
+
+fn mac_to_string(mac: &[u8]) -> String {
+    mac.iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<String>>()
+        .join(":")
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    os::unix::fs::PermissionsExt,
+    time::{Duration, SystemTime},
+};
+
+use mozim::DhcpV4Lease;
+use nipart::{ErrorKind, NipartError};
+use serde::{Deserialize, Serialize};
+use tokio::{fs::File, io::AsyncWriteExt};
+
+const LEASE_DIR: &str = "/var/lib/nipart/dhcp";
+
+/// DHCPv4 lease stored to disk with the time it was acquired, so we can
+/// tell whether it is expired after daemon restart or system reboot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NipartStoredDhcpV4Lease {
+    /// Seconds since UNIX epoch when lease acquired.
+    pub(crate) acquired: u64,
+    pub(crate) lease: DhcpV4Lease,
+}
+
+impl NipartStoredDhcpV4Lease {
+    pub(crate) fn new(lease: DhcpV4Lease) -> Self {
+        Self {
+            acquired: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            lease,
+        }
+    }
+
+    pub(crate) fn expiry(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH
+            + Duration::from_secs(
+                self.acquired + u64::from(self.lease.lease_time_sec),
+            )
+    }
+
+    /// Lease expiry time in RFC 3339 format.
+    pub(crate) fn expiry_str(&self) -> String {
+        chrono::DateTime::<chrono::Utc>::from(self.expiry())
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    }
+
+    pub(crate) fn is_expired(&self) -> bool {
+        self.expiry() <= SystemTime::now()
+    }
+
+    /// Load previously stored lease of specified interface and client ID.
+    /// Expired or corrupted lease file will be removed and `None` returned.
+    pub(crate) async fn load(
+        iface_name: &str,
+        client_id: &str,
+    ) -> Option<Self> {
+        let path = lease_path(iface_name, client_id);
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(c) => c,
+            Err(e) => {
+                log::debug!("No stored DHCPv4 lease loaded from {path}: {e}");
+                return None;
+            }
+        };
+        match serde_json::from_str::<Self>(&content) {
+            Ok(stored_lease) => {
+                if stored_lease.is_expired() {
+                    log::debug!("Removing expired DHCPv4 lease file {path}");
+                    tokio::fs::remove_file(&path).await.ok();
+                    None
+                } else {
+                    Some(stored_lease)
+                }
+            }
+            Err(e) => {
+                log::debug!("Removing corrupted DHCPv4 lease file {path}: {e}");
+                tokio::fs::remove_file(&path).await.ok();
+                None
+            }
+        }
+    }
+
+    pub(crate) async fn save(
+        &self,
+        iface_name: &str,
+        client_id: &str,
+    ) -> Result<(), NipartError> {
+        create_lease_dir()?;
+        let path = lease_path(iface_name, client_id);
+        let content = serde_json::to_string(self).map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to generate JSON for DHCPv4 lease {self:?}: {e}"
+                ),
+            )
+        })?;
+        log::debug!("Saving DHCPv4 lease of {iface_name} to {path}");
+        // Write to temporary file and rename, so crash during writing will
+        // not leave truncated lease file.
+        let tmp_path = format!("{path}.tmp");
+        let mut fd = File::create(&tmp_path).await?;
+        fd.set_permissions(PermissionsExt::from_mode(0o600)).await?;
+        fd.write_all(content.as_bytes()).await?;
+        fd.sync_all().await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    /// Remove all stored leases of specified interface.
+    pub(crate) async fn remove(iface_name: &str) {
+        let Ok(mut entries) = tokio::fs::read_dir(LEASE_DIR).await else {
+            return;
+        };
+        let prefix = format!("v4-{iface_name}-");
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            // Client ID is hex string, so lease of interface `eth1` will not
+            // match lease of interface `eth1-a`.
+            if file_name
+                .strip_prefix(prefix.as_str())
+                .and_then(|s| s.strip_suffix(".json"))
+                .is_some_and(|s| s.chars().all(|c| c.is_ascii_hexdigit()))
+            {
+                log::debug!("Removing DHCPv4 lease file {file_name}");
+                if let Err(e) = tokio::fs::remove_file(entry.path()).await {
+                    log::warn!(
+                        "Failed to remove DHCPv4 lease file {file_name}: {e}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn lease_path(iface_name: &str, client_id: &str) -> String {
+    format!("{LEASE_DIR}/v4-{iface_name}-{client_id}.json")
+}
+
+fn create_lease_dir() -> Result<(), NipartError> {
+    let dir_path = std::path::Path::new(LEASE_DIR);
+    if !dir_path.exists() {
+        log::debug!("Creating dir {}", dir_path.display());
+        std::fs::create_dir_all(dir_path).map_err(|e| {
+            NipartError::new(
+                ErrorKind::DaemonFailure,
+                format!("Failed to create dir {}: {e}", dir_path.display()),
+            )
+        })?;
+    }
+    Ok(())
+}
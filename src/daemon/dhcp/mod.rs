@@ -2,8 +2,14 @@
 
 mod dhcp_manager;
 mod dhcp_worker;
+mod dhcpv6_manager;
+mod dhcpv6_worker;
+mod ipv4ll;
+mod lease_store;
 
 pub(crate) use self::{
     dhcp_manager::NipartDhcpV4Manager,
     dhcp_worker::{NipartDhcpCmd, NipartDhcpReply, NipartDhcpV4Worker},
+    dhcpv6_manager::NipartDhcpV6Manager,
+    dhcpv6_worker::NipartDhcpV6Worker,
 };
@@ -568,6 +568,12 @@ fn parse_iface_type_from_nl_msg(link_msg: &LinkMessage) -> InterfaceType {
             InfoKind::IpVlan => InterfaceType::IpVlan,
             InfoKind::MacSec => InterfaceType::MacSec,
             InfoKind::Hsr => InterfaceType::Hsr,
+            InfoKind::GreTun => InterfaceType::Gre,
+            InfoKind::GreTap => InterfaceType::GreTap,
+            InfoKind::GreTun6 => InterfaceType::Ip6Gre,
+            InfoKind::IpTun => InterfaceType::Ipip,
+            InfoKind::SitTun => InterfaceType::Sit,
+            InfoKind::Ip6Tnl => InterfaceType::Ip6Tnl,
             InfoKind::Xfrm => InterfaceType::Xfrm,
             v => InterfaceType::Unknown(v.to_string().to_lowercase()),
         }
@@ -35,6 +35,13 @@ impl NipartCommander {
 
                 // Load user space from conf_manager
                 let mut saved_state = self.conf_manager.query_state().await?;
+
+                // Route metric policy is persisted in saved state
+                let mut policy =
+                    saved_state.route_metric_policy.take().unwrap_or_default();
+                policy.include_default();
+                net_state.route_metric_policy = Some(policy);
+
                 for (_, iface) in saved_state.ifaces.user_ifaces.drain() {
                     if iface.iface_type() == &InterfaceType::WifiCfg {
                         net_state.ifaces.push(iface);
@@ -52,6 +59,7 @@ impl NipartCommander {
                 }
 
                 self.dhcpv4_manager.fill_dhcp_states(&mut net_state).await?;
+                self.dhcpv6_manager.fill_dhcp_states(&mut net_state).await?;
 
                 if !opt.include_secrets {
                     net_state.hide_secrets();
@@ -1,6 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{inter_ifaces::apply_ifaces, route::apply_routes};
+use super::{
+    dns::apply_dns, hostname::apply_hostname, inter_ifaces::apply_ifaces,
+    nexthop::apply_nexthops, route::apply_routes,
+    route_metric_policy::apply_route_metric_policy,
+    route_rule::apply_route_rules,
+};
 use crate::{
     InterfaceType, MergedNetworkState, NetworkState, NipartApplyOption,
     NipartError, NipartInterface, NipartNoDaemon,
@@ -42,7 +47,11 @@ impl NipartNoDaemon {
 
         Self::apply_merged_state(&merged_state).await?;
         if option.dhcp_in_no_daemon {
-            Self::run_dhcp_once(&merged_state.ifaces).await?;
+            Self::run_dhcp_once(
+                &merged_state.ifaces,
+                &merged_state.route_metric_policy.merged(),
+            )
+            .await?;
         }
 
         let max_retry_count = get_max_retry_count(&merged_state);
@@ -59,7 +68,11 @@ impl NipartNoDaemon {
                     log::info!("Apply the desired state again");
                     Self::apply_merged_state(&merged_state).await?;
                     if option.dhcp_in_no_daemon {
-                        Self::run_dhcp_once(&merged_state.ifaces).await?;
+                        Self::run_dhcp_once(
+                            &merged_state.ifaces,
+                            &merged_state.route_metric_policy.merged(),
+                        )
+                        .await?;
                     }
                 }
                 result = merged_state.verify(&post_apply_current_state);
@@ -89,8 +102,17 @@ impl NipartNoDaemon {
     pub async fn apply_merged_state(
         merged_state: &MergedNetworkState,
     ) -> Result<(), NipartError> {
+        apply_route_metric_policy(
+            &merged_state.route_metric_policy,
+            merged_state.option.memory_only,
+        )?;
         apply_ifaces(&merged_state.ifaces).await?;
+        // Routes might refer to nexthop objects
+        apply_nexthops(&merged_state.nexthops).await?;
         apply_routes(&merged_state.routes).await?;
+        apply_route_rules(&merged_state.route_rules).await?;
+        apply_dns(&merged_state.dns).await?;
+        apply_hostname(&merged_state.hostname)?;
         Ok(())
     }
 }
@@ -2,7 +2,7 @@
 
 use super::{
     iface::nipart_iface_state_to_nispor,
-    ip::{np_ipv4_to_nipart, np_ipv6_to_nipart},
+    ip::{NipartIpAddrOrigins, np_ipv4_to_nipart, np_ipv6_to_nipart},
 };
 use crate::{
     BaseInterface, InterfaceLinkState, InterfaceState, InterfaceType,
@@ -27,6 +27,12 @@ fn np_iface_type_to_nipart(np_iface_type: &nispor::IfaceType) -> InterfaceType {
         nispor::IfaceType::Vxlan => InterfaceType::Vxlan,
         nispor::IfaceType::Ipoib => InterfaceType::InfiniBand,
         nispor::IfaceType::Tun => InterfaceType::Tun,
+        nispor::IfaceType::Gre => InterfaceType::Gre,
+        nispor::IfaceType::GreTap => InterfaceType::GreTap,
+        nispor::IfaceType::Ip6Gre => InterfaceType::Ip6Gre,
+        nispor::IfaceType::Ipip => InterfaceType::Ipip,
+        nispor::IfaceType::Sit => InterfaceType::Sit,
+        nispor::IfaceType::Ip6Tnl => InterfaceType::Ip6Tnl,
         nispor::IfaceType::Xfrm => InterfaceType::Xfrm,
         nispor::IfaceType::IpVlan => InterfaceType::IpVlan,
         nispor::IfaceType::Wifi => InterfaceType::WifiPhy,
@@ -76,6 +82,7 @@ impl From<&nispor::IfaceState> for InterfaceLinkState {
 
 pub(crate) fn np_iface_to_base_iface(
     np_iface: &nispor::Iface,
+    addr_origins: &NipartIpAddrOrigins,
 ) -> BaseInterface {
     let mut base_iface = BaseInterface {
         name: np_iface.name.to_string(),
@@ -114,8 +121,8 @@ pub(crate) fn np_iface_to_base_iface(
         );
         base_iface.state = InterfaceState::Ignore;
     }
-    base_iface.ipv4 = np_ipv4_to_nipart(np_iface);
-    base_iface.ipv6 = np_ipv6_to_nipart(np_iface);
+    base_iface.ipv4 = np_ipv4_to_nipart(np_iface, addr_origins);
+    base_iface.ipv6 = np_ipv6_to_nipart(np_iface, addr_origins);
 
     base_iface
 }
@@ -154,7 +161,12 @@ pub(crate) fn apply_base_iface_link_changes(
     //    interface which support MTU bigger than u32::MAX.
     np_iface.mtu = apply_iface.mtu.map(|mtu| mtu as u32);
 
-    if apply_iface.iface_type != InterfaceType::OvsInterface {
+    // OVS bridge ports are attached via OVSDB.
+    // HSR ports are attached when creating HSR interface.
+    if apply_iface.iface_type != InterfaceType::OvsInterface
+        && apply_iface.controller_type != Some(InterfaceType::OvsBridge)
+        && apply_iface.controller_type != Some(InterfaceType::Hsr)
+    {
         np_iface.controller = apply_iface.controller.clone();
     }
     Ok(())
@@ -5,46 +5,161 @@ use mozim::{DhcpV4Client, DhcpV4Config, DhcpV4Lease, DhcpV4State};
 
 use super::{ip::apply_iface_ip_changes, route::apply_routes};
 use crate::{
-    ErrorKind, InterfaceIpAddr, InterfaceIpv4, InterfaceType, MergedInterfaces,
-    MergedRoutes, NipartError, NipartInterface, NipartNoDaemon, RouteEntry,
-    Routes,
+    BaseInterface, DnsResolverConfig, ErrorKind, InterfaceIpAddr,
+    InterfaceIpv4, MergedInterfaces, MergedRoutes, NipartError,
+    NipartInterface, NipartNoDaemon, RouteEntry, RouteMetricPolicy, Routes,
 };
 
-const DEFAULT_ROUTE_TABLE_ID: u32 = 254;
-
 impl NipartNoDaemon {
+    /// Generate routes from DHCPv4 lease honoring `auto-routes`,
+    /// `auto-gateway`, `auto-route-table-id` and `auto-route-metric` of
+    /// IPv4 configuration in specified interface. Route metric is decided by
+    /// specified [RouteMetricPolicy] with interface index as offset if
+    /// `auto-route-metric` undefined.
+    /// When classless static routes option(RFC 3442) is provided by server,
+    /// router option will be ignored.
+    pub fn gen_dhcpv4_routes(
+        lease: &DhcpV4Lease,
+        base_iface: &BaseInterface,
+        policy: &RouteMetricPolicy,
+    ) -> Routes {
+        let mut conf_routes: Vec<RouteEntry> = Vec::new();
+        let ipv4_conf = base_iface.ipv4.clone().unwrap_or_default();
+        if ipv4_conf.is_auto_routes() {
+            let table_id = ipv4_conf.auto_table_id();
+            let metric = match ipv4_conf.auto_route_metric {
+                Some(m) => i64::from(m),
+                None => {
+                    i64::from(policy.base_metric(&base_iface.iface_type))
+                        + i64::from(base_iface.iface_index.unwrap_or_default())
+                }
+            };
+            if let Some(classless_routes) = lease
+                .classless_routes
+                .as_deref()
+                .filter(|rts| !rts.is_empty())
+            {
+                for classless_route in classless_routes {
+                    if classless_route.prefix_length == 0
+                        && !ipv4_conf.is_auto_gateway()
+                    {
+                        continue;
+                    }
+                    conf_routes.push(RouteEntry {
+                        destination: Some(format!(
+                            "{}/{}",
+                            classless_route.destination,
+                            classless_route.prefix_length
+                        )),
+                        next_hop_iface: Some(base_iface.name.to_string()),
+                        // Router 0.0.0.0 means destination is on-link
+                        next_hop_addr: if classless_route
+                            .router
+                            .is_unspecified()
+                        {
+                            None
+                        } else {
+                            Some(classless_route.router.to_string())
+                        },
+                        table_id: Some(table_id),
+                        metric: Some(metric),
+                        ..Default::default()
+                    });
+                }
+            } else if ipv4_conf.is_auto_gateway()
+                && let Some(gateways) = lease.gateways.as_ref()
+            {
+                for (index, gateway) in gateways.iter().enumerate() {
+                    conf_routes.push(RouteEntry {
+                        destination: Some("0.0.0.0/0".to_string()),
+                        next_hop_iface: Some(base_iface.name.to_string()),
+                        next_hop_addr: Some(gateway.to_string()),
+                        table_id: Some(table_id),
+                        metric: Some(metric + index as i64),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        Routes {
+            config: Some(conf_routes),
+            ..Default::default()
+        }
+    }
+
+    /// Generate DNS resolver configuration from DNS server option(6) and
+    /// domain name option(15) of DHCPv4 lease. Return None if neither
+    /// provided by server.
+    pub fn gen_dhcpv4_dns(lease: &DhcpV4Lease) -> Option<DnsResolverConfig> {
+        let servers: Vec<String> = lease
+            .dns_srv
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let searches: Vec<String> = lease.domain_name.iter().cloned().collect();
+        if servers.is_empty() && searches.is_empty() {
+            None
+        } else {
+            Some(DnsResolverConfig::new(servers, searches))
+        }
+    }
+
     pub(crate) async fn run_dhcp_once(
         merged_ifaces: &MergedInterfaces,
+        policy: &RouteMetricPolicy,
     ) -> Result<(), NipartError> {
         let mut get_lease_futures = FuturesUnordered::new();
 
-        for iface in merged_ifaces
-            .kernel_ifaces
-            .values()
-            .filter_map(|i| i.for_apply.as_ref())
-            .filter(|i| {
-                i.base_iface().ipv4.as_ref().and_then(|ip| ip.dhcp)
-                    == Some(true)
-            })
-        {
-            let get_lease_future = get_lease(iface.name(), iface.iface_type());
+        for merged_iface in merged_ifaces.kernel_ifaces.values().filter(|i| {
+            i.for_apply
+                .as_ref()
+                .and_then(|i| i.base_iface().ipv4.as_ref())
+                .and_then(|ip| ip.dhcp)
+                == Some(true)
+        }) {
+            // Use merged interface for full DHCP client identity options
+            // and MAC address
+            let get_lease_future = get_lease(merged_iface.merged.base_iface());
             get_lease_futures.push(get_lease_future);
         }
 
         while let Some(result) = get_lease_futures.next().await {
             // Should fail the whole apply action for any errors of DHCP.
             let (iface_name, lease) = result?;
-            apply_lease(merged_ifaces, iface_name, lease).await?;
+            apply_lease(merged_ifaces, iface_name, lease, policy).await?;
         }
         Ok(())
     }
 }
 
-async fn get_lease<'a>(
-    iface_name: &'a str,
-    iface_type: &InterfaceType,
-) -> Result<(&'a str, DhcpV4Lease), NipartError> {
-    let dhcp_config = DhcpV4Config::new(iface_name);
+async fn get_lease(
+    base_iface: &BaseInterface,
+) -> Result<(&str, DhcpV4Lease), NipartError> {
+    let iface_name = base_iface.name.as_str();
+    let iface_type = &base_iface.iface_type;
+    let mut dhcp_config = DhcpV4Config::new(iface_name);
+    // Same as daemon, undefined `dhcp-client-id` means MAC based client
+    // identifier
+    let ipv4 = base_iface.ipv4.clone().unwrap_or_default();
+    let (client_id_type, client_id) = NipartNoDaemon::get_dhcp_client_id(
+        iface_name,
+        base_iface.mac_address.as_deref(),
+        &ipv4,
+    )?;
+    dhcp_config.set_client_id(client_id_type, &client_id);
+    if let Some(hostname) =
+        NipartNoDaemon::get_static_hostname().filter(|h| h != "localhost")
+    {
+        dhcp_config.set_host_name(&hostname);
+        // Use FQDN option(81) instead of host name option(12) for fully
+        // qualified domain name
+        if hostname.contains('.') {
+            dhcp_config.use_host_name_as_fqdn();
+        }
+    }
     log::debug!(
         "Waiting link carrier up for interface {}/{} before start DHCP",
         iface_name,
@@ -93,6 +208,7 @@ async fn apply_lease(
     merged_ifaces: &MergedInterfaces,
     iface_name: &str,
     lease: DhcpV4Lease,
+    policy: &RouteMetricPolicy,
 ) -> Result<(), NipartError> {
     let Some(merged_iface) = merged_ifaces.kernel_ifaces.get(iface_name) else {
         return Err(NipartError::new(
@@ -116,6 +232,12 @@ async fn apply_lease(
     ip_addr.preferred_life_time = Some(format!("{}sec", lease.lease_time_sec));
     ip_addr.valid_life_time = Some(format!("{}sec", lease.lease_time_sec));
 
+    NipartNoDaemon::apply_dhcp_addrs(
+        iface_name,
+        false,
+        &[lease.yiaddr.into()],
+    )?;
+
     let ipv4_conf = InterfaceIpv4 {
         enabled: Some(true),
         dhcp: Some(true),
@@ -145,39 +267,35 @@ async fn apply_lease(
         }
     }
 
-    let mut conf_routes: Vec<RouteEntry> = Vec::new();
-    // TODO: Handle multiple addresses of router
-    if let Some(gateways) = lease.gateways.as_ref() {
-        for (index, gateway) in gateways.iter().enumerate() {
-            let route = RouteEntry {
-                destination: Some("0.0.0.0/0".to_string()),
-                next_hop_iface: Some(iface_name.to_string()),
-                next_hop_addr: Some(gateway.to_string()),
-                table_id: Some(DEFAULT_ROUTE_TABLE_ID),
-                // TODO: Be consistent on metric?
-                // TODO: Priority ethernet over wifi/VPN/etc ?
-                metric: merged_iface
-                    .current
-                    .as_ref()
-                    .and_then(|c| c.base_iface().iface_index)
-                    .map(|iface_index| {
-                        100i64 * iface_index as i64 + index as i64
-                    }),
-                ..Default::default()
-            };
-            conf_routes.push(route);
-        }
-    }
-
-    let des_routes = Routes {
-        config: Some(conf_routes),
-        ..Default::default()
-    };
+    let des_routes = NipartNoDaemon::gen_dhcpv4_routes(
+        &lease,
+        merged_iface.merged.base_iface(),
+        policy,
+    );
 
     let merged_routes =
         MergedRoutes::new(des_routes, Default::default(), merged_ifaces)?;
 
     apply_routes(&merged_routes).await?;
 
+    if merged_iface
+        .merged
+        .base_iface()
+        .ipv4
+        .as_ref()
+        .is_none_or(|i| i.is_auto_dns())
+    {
+        NipartNoDaemon::apply_dhcp_dns(
+            iface_name,
+            false,
+            NipartNoDaemon::gen_dhcpv4_dns(&lease).as_ref(),
+        )
+        .await?;
+    }
+    NipartNoDaemon::apply_dhcp_hostname(
+        iface_name,
+        lease.host_name.as_deref(),
+    )?;
+
     Ok(())
 }
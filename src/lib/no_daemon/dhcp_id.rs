@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    DhcpClientId, DhcpDuid, ErrorKind, InterfaceIpv4, NipartError,
+    NipartNoDaemon,
+};
+
+const DUID_CONF_DIR: &str = "/etc/nipart";
+const DUID_CONF_PATH: &str = "/etc/nipart/dhcp-duid.json";
+
+const DUID_TYPE_LLT: u16 = 1;
+const DUID_TYPE_LL: u16 = 3;
+const DUID_TYPE_UUID: u16 = 4;
+const HW_TYPE_ETHERNET: u8 = 1;
+const CLIENT_ID_TYPE_DUID: u8 = 255;
+// Seconds between 1970-01-01 and 2000-01-01 in UTC
+const DUID_TIME_EPOCH: u64 = 946684800;
+
+// Host wide DUIDs generated once and reused afterwards
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct NipartDuidConf {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    llt: Option<DhcpDuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<DhcpDuid>,
+}
+
+impl NipartDuidConf {
+    fn load() -> Self {
+        match std::fs::read_to_string(DUID_CONF_PATH) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(c) => c,
+                Err(e) => {
+                    log::warn!(
+                        "Ignoring corrupted DHCP DUID file {DUID_CONF_PATH}: \
+                         {e}"
+                    );
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) -> Result<(), NipartError> {
+        let content = serde_json::to_string(self).map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to generate JSON for DHCP DUID: {e}"),
+            )
+        })?;
+        std::fs::create_dir_all(DUID_CONF_DIR)
+            .and_then(|_| std::fs::write(DUID_CONF_PATH, content))
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to write {DUID_CONF_PATH}: {e}"),
+                )
+            })
+    }
+}
+
+impl NipartNoDaemon {
+    /// Bytes of DHCP Unique Identifier. Undefined `duid` means
+    /// [DhcpDuid::Llt]. The DUID-LLT and DUID-UUID are generated on first
+    /// use and stored in `/etc/nipart/dhcp-duid.json`, so they are stable
+    /// for this host even after NIC replaced.
+    pub fn get_dhcp_duid(
+        iface_name: &str,
+        mac_address: Option<&str>,
+        duid: Option<&DhcpDuid>,
+    ) -> Result<Vec<u8>, NipartError> {
+        match duid.unwrap_or(&DhcpDuid::Llt) {
+            DhcpDuid::Ll => {
+                let mac = parse_mac(iface_name, mac_address)?;
+                let mut ret = DUID_TYPE_LL.to_be_bytes().to_vec();
+                ret.extend_from_slice(
+                    &u16::from(HW_TYPE_ETHERNET).to_be_bytes(),
+                );
+                ret.extend_from_slice(&mac);
+                Ok(ret)
+            }
+            DhcpDuid::Llt => {
+                let mut conf = NipartDuidConf::load();
+                if let Some(bytes) =
+                    conf.llt.as_ref().and_then(|d| d.custom_bytes())
+                {
+                    return Ok(bytes);
+                }
+                let bytes = gen_duid_llt(&parse_mac(iface_name, mac_address)?);
+                log::info!("Generated host DHCP DUID-LLT using {iface_name}");
+                conf.llt = Some(DhcpDuid::new_custom(&bytes));
+                conf.save()?;
+                Ok(bytes)
+            }
+            DhcpDuid::Uuid => {
+                let mut conf = NipartDuidConf::load();
+                if let Some(bytes) =
+                    conf.uuid.as_ref().and_then(|d| d.custom_bytes())
+                {
+                    return Ok(bytes);
+                }
+                let bytes = gen_duid_uuid();
+                log::info!("Generated host DHCP DUID-UUID");
+                conf.uuid = Some(DhcpDuid::new_custom(&bytes));
+                conf.save()?;
+                Ok(bytes)
+            }
+            duid => duid.custom_bytes().ok_or_else(|| {
+                NipartError::new(
+                    ErrorKind::NoSupport,
+                    format!(
+                        "Unsupported DHCP DUID {}",
+                        String::from(duid.clone())
+                    ),
+                )
+            }),
+        }
+    }
+
+    /// DHCPv4 client identifier in the form of `(type, data)`.
+    /// Undefined `dhcp-client-id` means [DhcpClientId::Mac].
+    pub fn get_dhcp_client_id(
+        iface_name: &str,
+        mac_address: Option<&str>,
+        ipv4: &InterfaceIpv4,
+    ) -> Result<(u8, Vec<u8>), NipartError> {
+        match ipv4.dhcp_client_id.as_ref().unwrap_or(&DhcpClientId::Mac) {
+            DhcpClientId::Mac => {
+                Ok((HW_TYPE_ETHERNET, parse_mac(iface_name, mac_address)?))
+            }
+            DhcpClientId::Duid => Ok((
+                CLIENT_ID_TYPE_DUID,
+                Self::get_dhcp_duid(
+                    iface_name,
+                    mac_address,
+                    ipv4.dhcp_duid.as_ref(),
+                )?,
+            )),
+            DhcpClientId::IaidDuid => {
+                let mut data = gen_iaid(iface_name).to_be_bytes().to_vec();
+                data.extend_from_slice(&Self::get_dhcp_duid(
+                    iface_name,
+                    mac_address,
+                    ipv4.dhcp_duid.as_ref(),
+                )?);
+                Ok((CLIENT_ID_TYPE_DUID, data))
+            }
+            client_id => match client_id.custom_bytes().as_deref() {
+                Some([client_id_type, data @ ..]) if !data.is_empty() => {
+                    Ok((*client_id_type, data.to_vec()))
+                }
+                _ => Err(NipartError::new(
+                    ErrorKind::NoSupport,
+                    format!(
+                        "Unsupported DHCP client identifier {}",
+                        String::from(client_id.clone())
+                    ),
+                )),
+            },
+        }
+    }
+}
+
+fn parse_mac(
+    iface_name: &str,
+    mac_address: Option<&str>,
+) -> Result<Vec<u8>, NipartError> {
+    let Some(mac_address) = mac_address else {
+        return Err(NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Cannot generate DHCP client identity for interface \
+                 {iface_name} without MAC address"
+            ),
+        ));
+    };
+    mac_address
+        .split(':')
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect::<Option<Vec<u8>>>()
+        .filter(|m| !m.is_empty())
+        .ok_or_else(|| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Invalid MAC address {mac_address} of interface \
+                     {iface_name}"
+                ),
+            )
+        })
+}
+
+// RFC 8415 section 11.2: DUID type, hardware type, time in seconds since
+// 2000-01-01 UTC modulo 2^32, link-layer address
+fn gen_duid_llt(mac: &[u8]) -> Vec<u8> {
+    let time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+        .saturating_sub(DUID_TIME_EPOCH) as u32;
+    let mut ret = DUID_TYPE_LLT.to_be_bytes().to_vec();
+    ret.extend_from_slice(&u16::from(HW_TYPE_ETHERNET).to_be_bytes());
+    ret.extend_from_slice(&time.to_be_bytes());
+    ret.extend_from_slice(mac);
+    ret
+}
+
+// RFC 6355: DUID type followed by random version 4 UUID
+fn gen_duid_uuid() -> Vec<u8> {
+    let mut uuid: [u8; 16] = rand::random();
+    uuid[6] = (uuid[6] & 0x0f) | 0x40;
+    uuid[8] = (uuid[8] & 0x3f) | 0x80;
+    let mut ret = DUID_TYPE_UUID.to_be_bytes().to_vec();
+    ret.extend_from_slice(&uuid);
+    ret
+}
+
+// IAID should be stable for the same interface across reboot, use 32 bits
+// FNV-1a hash of interface name, hence not changed after NIC replaced.
+fn gen_iaid(iface_name: &str) -> u32 {
+    iface_name.bytes().fold(0x811c9dc5u32, |hash, b| {
+        (hash ^ u32::from(b)).wrapping_mul(0x01000193)
+    })
+}
@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::IpAddr, str::FromStr};
+
+use nix::libc;
+use zvariant::OwnedObjectPath;
+
+use crate::{
+    DnsResolver, DnsResolverConfig, ErrorKind, MergedDnsResolver, NipartError,
+    NipartNoDaemon,
+};
+
+const RESOLV_CONF_PATH: &str = "/etc/resolv.conf";
+const RESOLV_CONF_TMP_PATH: &str = "/etc/.resolv.conf.nipart";
+// Holding upstream DNS servers when DNS managed by systemd-resolved
+const RESOLVED_RESOLV_CONF_PATH: &str = "/run/systemd/resolve/resolv.conf";
+const RESOLVED_RUN_DIR: &str = "run/systemd/resolve/";
+const RESOLVED_DROP_IN_DIR: &str = "/run/systemd/resolved.conf.d";
+const RESOLVED_DROP_IN_PATH: &str =
+    "/run/systemd/resolved.conf.d/90-nipart.conf";
+const RESOLVED_UNIT_NAME: &str = "systemd-resolved.service";
+// Storing DNS learned from DHCP, so they could be merged regardless which
+// process learned them.
+const DNS_RUN_DIR: &str = "/run/nipart/dns";
+// Static DNS config should persist after reboot.
+const DNS_CONF_DIR: &str = "/etc/nipart/dns";
+const STATIC_CONF_NAME: &str = "static";
+const RESOLV_CONF_HEADER: &str = "# Generated by nipart";
+
+// These proxy() macros only generate private struct, hence it should be
+// sit with its consumer.
+#[zbus::proxy(
+    interface = "org.freedesktop.resolve1.Manager",
+    default_service = "org.freedesktop.resolve1",
+    default_path = "/org/freedesktop/resolve1"
+)]
+trait Resolve1Manager {
+    #[zbus(name = "SetLinkDNS")]
+    fn set_link_dns(
+        &self,
+        ifindex: i32,
+        addresses: &[(i32, Vec<u8>)],
+    ) -> zbus::Result<()>;
+
+    fn set_link_domains(
+        &self,
+        ifindex: i32,
+        domains: &[(&str, bool)],
+    ) -> zbus::Result<()>;
+
+    fn revert_link(&self, ifindex: i32) -> zbus::Result<()>;
+}
+
+#[zbus::proxy(
+    interface = "org.freedesktop.systemd1.Manager",
+    default_service = "org.freedesktop.systemd1",
+    default_path = "/org/freedesktop/systemd1"
+)]
+trait Systemd1Manager {
+    fn reload_unit(
+        &self,
+        name: &str,
+        mode: &str,
+    ) -> zbus::Result<OwnedObjectPath>;
+}
+
+impl NipartNoDaemon {
+    /// Store DNS learned from DHCP lease of specified interface and apply
+    /// it along with static DNS configuration. Setting `dns` to `None`
+    /// removes DNS learned previously, for example DHCP stopped.
+    pub async fn apply_dhcp_dns(
+        iface_name: &str,
+        is_ipv6: bool,
+        dns: Option<&DnsResolverConfig>,
+    ) -> Result<(), NipartError> {
+        let name = dhcp_conf_name(iface_name, is_ipv6);
+        match dns {
+            Some(dns) => {
+                let mut dns = dns.clone();
+                dns.sanitize()?;
+                if read_conf(&name) == Some(dns.clone()) {
+                    return Ok(());
+                }
+                log::debug!(
+                    "Applying DNS {dns} learned from DHCP{} on {iface_name}",
+                    if is_ipv6 { "v6" } else { "v4" }
+                );
+                save_conf(&name, &dns)?;
+            }
+            None => {
+                if read_conf(&name).is_none() {
+                    return Ok(());
+                }
+                remove_conf(&name);
+            }
+        }
+        if is_resolved_managed() {
+            apply_resolved_link_dns(iface_name).await
+        } else {
+            write_resolv_conf()
+        }
+    }
+}
+
+pub(crate) fn get_dns() -> DnsResolver {
+    let running_path = if is_resolved_managed() {
+        RESOLVED_RESOLV_CONF_PATH
+    } else {
+        RESOLV_CONF_PATH
+    };
+    let running = match std::fs::read_to_string(running_path) {
+        Ok(content) => Some(parse_resolv_conf(&content)),
+        Err(e) => {
+            log::debug!("Failed to read {running_path}: {e}");
+            None
+        }
+    };
+    DnsResolver {
+        running,
+        config: read_conf(STATIC_CONF_NAME),
+    }
+}
+
+pub(crate) async fn apply_dns(
+    merged_dns: &MergedDnsResolver,
+) -> Result<(), NipartError> {
+    let Some(apply_conf) = merged_dns.for_apply.as_ref() else {
+        return Ok(());
+    };
+    let resolved_managed = is_resolved_managed();
+    if resolved_managed
+        && !apply_conf.options.as_deref().unwrap_or_default().is_empty()
+    {
+        return Err(NipartError::new(
+            ErrorKind::NoSupport,
+            format!(
+                "DNS resolver options {:?} are not supported when DNS is \
+                 managed by systemd-resolved",
+                apply_conf.options.as_deref().unwrap_or_default()
+            ),
+        ));
+    }
+    if apply_conf.is_empty() {
+        remove_conf(STATIC_CONF_NAME);
+    } else {
+        save_conf(STATIC_CONF_NAME, apply_conf)?;
+    }
+    if resolved_managed {
+        apply_resolved_global_dns(apply_conf).await
+    } else {
+        write_resolv_conf()
+    }
+}
+
+// Treat DNS as managed by systemd-resolved when /etc/resolv.conf is
+// symbol link to its stub or upstream resolv.conf.
+fn is_resolved_managed() -> bool {
+    std::fs::read_link(RESOLV_CONF_PATH)
+        .map(|p| p.to_string_lossy().contains(RESOLVED_RUN_DIR))
+        .unwrap_or_default()
+}
+
+fn parse_resolv_conf(content: &str) -> DnsResolverConfig {
+    let mut servers: Vec<String> = Vec::new();
+    let mut searches: Vec<String> = Vec::new();
+    let mut options: Vec<String> = Vec::new();
+    for line in content.lines().map(|l| l.trim()) {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("nameserver") => {
+                if let Some(server) = words.next() {
+                    servers.push(server.to_string());
+                }
+            }
+            // The `domain` is obsolete name of `search`, and the last
+            // instance of them wins.
+            Some("search") | Some("domain") => {
+                searches = words.map(|w| w.to_string()).collect();
+            }
+            Some("options") => {
+                options.extend(words.map(|w| w.to_string()));
+            }
+            _ => (),
+        }
+    }
+    DnsResolverConfig {
+        server: Some(servers),
+        search: Some(searches),
+        options: if options.is_empty() {
+            None
+        } else {
+            Some(options)
+        },
+    }
+}
+
+// Static configuration first, then DHCP learned ones sorted by interface
+// name.
+fn merged_conf() -> DnsResolverConfig {
+    let mut conf = read_conf(STATIC_CONF_NAME).unwrap_or_default();
+    let mut dhcp_conf_names: Vec<String> = std::fs::read_dir(DNS_RUN_DIR)
+        .map(|dir| {
+            dir.filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    e.file_name()
+                        .to_str()
+                        .and_then(|n| n.strip_suffix(".json"))
+                        .map(|n| n.to_string())
+                })
+                .filter(|n| n != STATIC_CONF_NAME)
+                .collect()
+        })
+        .unwrap_or_default();
+    dhcp_conf_names.sort_unstable();
+    for name in dhcp_conf_names {
+        if let Some(dhcp_conf) = read_conf(&name) {
+            conf.append(&dhcp_conf);
+        }
+    }
+    conf
+}
+
+fn write_resolv_conf() -> Result<(), NipartError> {
+    let conf = merged_conf();
+    let mut content = format!("{RESOLV_CONF_HEADER}\n");
+    let searches = conf.search.as_deref().unwrap_or_default();
+    if !searches.is_empty() {
+        content.push_str(&format!("search {}\n", searches.join(" ")));
+    }
+    for server in conf.server.as_deref().unwrap_or_default() {
+        content.push_str(&format!("nameserver {server}\n"));
+    }
+    let options = conf.options.as_deref().unwrap_or_default();
+    if !options.is_empty() {
+        content.push_str(&format!("options {}\n", options.join(" ")));
+    }
+    log::debug!("Writing {RESOLV_CONF_PATH}: {content}");
+    // Write to temporary file and rename, so reader will never see
+    // incomplete file.
+    std::fs::write(RESOLV_CONF_TMP_PATH, content.as_bytes())
+        .and_then(|_| std::fs::rename(RESOLV_CONF_TMP_PATH, RESOLV_CONF_PATH))
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to write {RESOLV_CONF_PATH}: {e}"),
+            )
+        })
+}
+
+async fn dbus_connection() -> Result<zbus::Connection, NipartError> {
+    zbus::Connection::system().await.map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create system DBUS connection: {e}"),
+        )
+    })
+}
+
+// systemd-resolved has no DBUS API for global DNS, hence we use drop-in
+// configuration file and reload the service.
+async fn apply_resolved_global_dns(
+    conf: &DnsResolverConfig,
+) -> Result<(), NipartError> {
+    if conf.is_empty() {
+        std::fs::remove_file(RESOLVED_DROP_IN_PATH).ok();
+    } else {
+        let content = format!(
+            "{RESOLV_CONF_HEADER}\n[Resolve]\nDNS={}\nDomains={}\n",
+            conf.server.as_deref().unwrap_or_default().join(" "),
+            conf.search.as_deref().unwrap_or_default().join(" "),
+        );
+        std::fs::create_dir_all(RESOLVED_DROP_IN_DIR)
+            .and_then(|_| std::fs::write(RESOLVED_DROP_IN_PATH, content))
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to write {RESOLVED_DROP_IN_PATH}: {e}"),
+                )
+            })?;
+    }
+    let connection = dbus_connection().await?;
+    let proxy = Systemd1ManagerProxy::new(&connection)
+        .await
+        .map_err(map_zbus_err)?;
+    proxy
+        .reload_unit(RESOLVED_UNIT_NAME, "replace")
+        .await
+        .map_err(map_zbus_err)?;
+    Ok(())
+}
+
+async fn apply_resolved_link_dns(iface_name: &str) -> Result<(), NipartError> {
+    let Some(iface_index) = get_iface_index(iface_name) else {
+        log::debug!(
+            "Interface {iface_name} not found, skipping systemd-resolved DNS \
+             configuration"
+        );
+        return Ok(());
+    };
+    let mut conf = DnsResolverConfig::default();
+    for is_ipv6 in [false, true] {
+        if let Some(dhcp_conf) = read_conf(&dhcp_conf_name(iface_name, is_ipv6))
+        {
+            conf.append(&dhcp_conf);
+        }
+    }
+
+    let connection = dbus_connection().await?;
+    let proxy = Resolve1ManagerProxy::new(&connection)
+        .await
+        .map_err(map_zbus_err)?;
+    if conf.is_empty() {
+        return proxy.revert_link(iface_index).await.map_err(map_zbus_err);
+    }
+
+    let mut addresses: Vec<(i32, Vec<u8>)> = Vec::new();
+    for server in conf.server.as_deref().unwrap_or_default() {
+        // The link-local scope is implied by link
+        let ip_str = server.split_once('%').map(|s| s.0).unwrap_or(server);
+        match IpAddr::from_str(ip_str) {
+            Ok(IpAddr::V4(ip)) => {
+                addresses.push((libc::AF_INET, ip.octets().to_vec()))
+            }
+            Ok(IpAddr::V6(ip)) => {
+                addresses.push((libc::AF_INET6, ip.octets().to_vec()))
+            }
+            Err(e) => {
+                log::warn!("BUG: Got invalid DNS server {server}: {e}");
+            }
+        }
+    }
+    let domains: Vec<(&str, bool)> = conf
+        .search
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|s| (s.as_str(), false))
+        .collect();
+    proxy
+        .set_link_dns(iface_index, &addresses)
+        .await
+        .map_err(map_zbus_err)?;
+    proxy
+        .set_link_domains(iface_index, &domains)
+        .await
+        .map_err(map_zbus_err)?;
+    Ok(())
+}
+
+fn get_iface_index(iface_name: &str) -> Option<i32> {
+    std::fs::read_to_string(format!("/sys/class/net/{iface_name}/ifindex"))
+        .ok()
+        .and_then(|s| s.trim().parse::<i32>().ok())
+}
+
+fn dhcp_conf_name(iface_name: &str, is_ipv6: bool) -> String {
+    format!("{iface_name}.dhcp{}", if is_ipv6 { "v6" } else { "v4" })
+}
+
+fn conf_dir(name: &str) -> &'static str {
+    if name == STATIC_CONF_NAME {
+        DNS_CONF_DIR
+    } else {
+        DNS_RUN_DIR
+    }
+}
+
+fn conf_path(name: &str) -> String {
+    format!("{}/{name}.json", conf_dir(name))
+}
+
+fn read_conf(name: &str) -> Option<DnsResolverConfig> {
+    let path = conf_path(name);
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            log::debug!("Removing corrupted DNS config file {path}: {e}");
+            std::fs::remove_file(&path).ok();
+            None
+        }
+    }
+}
+
+fn save_conf(name: &str, conf: &DnsResolverConfig) -> Result<(), NipartError> {
+    let path = conf_path(name);
+    let content = serde_json::to_string(conf).map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to generate JSON for DNS config {conf}: {e}"),
+        )
+    })?;
+    std::fs::create_dir_all(conf_dir(name))
+        .and_then(|_| std::fs::write(&path, content))
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to write DNS config file {path}: {e}"),
+            )
+        })
+}
+
+fn remove_conf(name: &str) {
+    std::fs::remove_file(conf_path(name)).ok();
+}
+
+fn map_zbus_err(e: zbus::Error) -> NipartError {
+    NipartError::new(
+        ErrorKind::Bug,
+        format!("DBUS error when configuring DNS: {e}"),
+    )
+}
@@ -1,8 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::sriov::gen_np_sriov_vf_conf;
 use crate::{
     BaseInterface, EthernetConfig, EthernetDuplex, EthernetInterface,
-    Interface, NipartError, NipartInterface, VethConfig,
+    Interface, NipartError, NipartInterface, SrIovConfig, VethConfig,
 };
 
 pub(crate) fn apply_ethernet_conf(
@@ -30,6 +31,13 @@ pub(crate) fn apply_ethernet_conf(
         peer_np_iface.veth = Some(np_veth_conf);
         Ok(vec![np_iface, peer_np_iface])
     } else {
+        if let Some(sriov_conf) = apply_iface
+            .ethernet
+            .as_ref()
+            .and_then(|e| e.sr_iov.as_ref())
+        {
+            np_iface.sriov = gen_np_sriov_vf_conf(sriov_conf);
+        }
         Ok(vec![np_iface])
     }
 }
@@ -58,7 +66,7 @@ fn get_veth_conf(np_iface: &nispor::Iface) -> Option<VethConfig> {
 }
 
 fn get_eth_conf(np_iface: &nispor::Iface) -> Option<EthernetConfig> {
-    np_iface
+    let mut eth_conf = np_iface
         .ethtool
         .as_ref()
         .and_then(|ethtool_info| ethtool_info.link_mode.as_ref())
@@ -74,5 +82,11 @@ fn get_eth_conf(np_iface: &nispor::Iface) -> Option<EthernetConfig> {
                 }
                 _ => None,
             },
-        })
+            ..Default::default()
+        });
+    if let Some(np_sriov) = np_iface.sriov.as_ref() {
+        eth_conf.get_or_insert_with(EthernetConfig::default).sr_iov =
+            Some(SrIovConfig::from(np_sriov));
+    }
+    eth_conf
 }
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ErrorKind, HostNameState, MergedHostNameState, NipartError, NipartNoDaemon,
+};
+
+const STATIC_HOSTNAME_PATH: &str = "/etc/hostname";
+const HOSTNAME_CONF_DIR: &str = "/etc/nipart";
+const HOSTNAME_CONF_PATH: &str = "/etc/nipart/hostname.json";
+// Holding host name learned from DHCP per interface
+const DHCP_HOSTNAME_DIR: &str = "/run/nipart/hostname";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct NipartHostNameConf {
+    dhcp_override: bool,
+}
+
+impl NipartNoDaemon {
+    /// Static host name stored in `/etc/hostname`.
+    pub fn get_static_hostname() -> Option<String> {
+        let content = std::fs::read_to_string(STATIC_HOSTNAME_PATH).ok()?;
+        content
+            .lines()
+            .map(|l| l.trim())
+            .find(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(|l| l.to_string())
+    }
+
+    /// Store host name learned from DHCP lease of specified interface and
+    /// use it as running host name if `dhcp-override` is enabled. Setting
+    /// `hostname` to `None` removes host name learned previously, running
+    /// host name will be restored to static host name.
+    pub fn apply_dhcp_hostname(
+        iface_name: &str,
+        hostname: Option<&str>,
+    ) -> Result<(), NipartError> {
+        let path = dhcp_hostname_path(iface_name);
+        match hostname.filter(|h| !h.is_empty()) {
+            Some(hostname) => {
+                if let Err(e) = HostNameState::validate_host_name(hostname) {
+                    log::warn!(
+                        "Ignoring invalid host name learned from DHCP on \
+                         {iface_name}: {e}"
+                    );
+                    return Ok(());
+                }
+                if std::fs::read_to_string(&path).ok().as_deref()
+                    == Some(hostname)
+                {
+                    return Ok(());
+                }
+                std::fs::create_dir_all(DHCP_HOSTNAME_DIR)
+                    .and_then(|_| std::fs::write(&path, hostname))
+                    .map_err(|e| {
+                        NipartError::new(
+                            ErrorKind::Bug,
+                            format!("Failed to write {path}: {e}"),
+                        )
+                    })?;
+            }
+            None => {
+                if std::fs::remove_file(&path).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        refresh_running_hostname()
+    }
+}
+
+pub(crate) fn get_hostname() -> Option<HostNameState> {
+    let running = match nix::unistd::gethostname() {
+        Ok(n) => Some(n.to_string_lossy().to_string()),
+        Err(e) => {
+            log::debug!("Failed to get host name: {e}");
+            None
+        }
+    };
+    Some(HostNameState {
+        running,
+        config: NipartNoDaemon::get_static_hostname(),
+        dhcp_override: Some(read_conf().dhcp_override),
+    })
+}
+
+pub(crate) fn apply_hostname(
+    merged_hostname: &MergedHostNameState,
+) -> Result<(), NipartError> {
+    let Some(apply) = merged_hostname.for_apply.as_ref() else {
+        return Ok(());
+    };
+    if let Some(dhcp_override) = apply.dhcp_override {
+        save_conf(&NipartHostNameConf { dhcp_override })?;
+    }
+    match apply.config.as_deref() {
+        Some("") => {
+            log::debug!("Removing static host name");
+            std::fs::remove_file(STATIC_HOSTNAME_PATH).ok();
+        }
+        Some(hostname) => {
+            log::debug!("Setting static host name to {hostname}");
+            std::fs::write(STATIC_HOSTNAME_PATH, format!("{hostname}\n"))
+                .map_err(|e| {
+                    NipartError::new(
+                        ErrorKind::Bug,
+                        format!("Failed to write {STATIC_HOSTNAME_PATH}: {e}"),
+                    )
+                })?;
+        }
+        None => (),
+    }
+    refresh_running_hostname()
+}
+
+// Running host name is the first(sorted by interface name) host name
+// learned from DHCP if `dhcp-override` is enabled, otherwise the static host
+// name. Leave running host name untouched if none of them available.
+fn refresh_running_hostname() -> Result<(), NipartError> {
+    let mut hostname = None;
+    if read_conf().dhcp_override {
+        hostname = first_dhcp_hostname();
+    }
+    if hostname.is_none() {
+        hostname = NipartNoDaemon::get_static_hostname();
+    }
+    let Some(hostname) = hostname else {
+        return Ok(());
+    };
+    let cur_hostname = nix::unistd::gethostname()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if cur_hostname != hostname {
+        log::info!("Setting running host name to {hostname}");
+        nix::unistd::sethostname(&hostname).map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to set host name to {hostname}: {e}"),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn first_dhcp_hostname() -> Option<String> {
+    let mut iface_names: Vec<String> = std::fs::read_dir(DHCP_HOSTNAME_DIR)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str().map(|n| n.to_string()))
+        .collect();
+    iface_names.sort_unstable();
+    iface_names.iter().find_map(|iface_name| {
+        std::fs::read_to_string(dhcp_hostname_path(iface_name)).ok()
+    })
+}
+
+fn dhcp_hostname_path(iface_name: &str) -> String {
+    format!("{DHCP_HOSTNAME_DIR}/{iface_name}")
+}
+
+fn read_conf() -> NipartHostNameConf {
+    std::fs::read_to_string(HOSTNAME_CONF_PATH)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_conf(conf: &NipartHostNameConf) -> Result<(), NipartError> {
+    let content = serde_json::to_string(conf).map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to generate JSON for host name config: {e}"),
+        )
+    })?;
+    std::fs::create_dir_all(HOSTNAME_CONF_DIR)
+        .and_then(|_| std::fs::write(HOSTNAME_CONF_PATH, content))
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to write {HOSTNAME_CONF_PATH}: {e}"),
+            )
+        })
+}
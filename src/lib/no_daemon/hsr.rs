@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, ErrorKind, HsrConfig, HsrInterface, HsrProtocol, Interface,
+    InterfaceType, Interfaces, NipartError, NipartInterface,
+};
+
+impl From<nispor::HsrProtocol> for HsrProtocol {
+    fn from(v: nispor::HsrProtocol) -> Self {
+        match v {
+            nispor::HsrProtocol::Prp => Self::Prp,
+            nispor::HsrProtocol::Hsr => Self::Hsr,
+            p => {
+                log::debug!("Got unknown HSR protocol {p:?}, treating as HSR");
+                Self::Hsr
+            }
+        }
+    }
+}
+
+impl From<&nispor::HsrInfo> for HsrConfig {
+    fn from(np_info: &nispor::HsrInfo) -> Self {
+        let protocol: HsrProtocol = np_info.protocol.into();
+        Self {
+            port1: np_info.port1.clone(),
+            port2: np_info.port2.clone(),
+            supervision_address: Some(np_info.supervision_addr.to_uppercase()),
+            version: if protocol == HsrProtocol::Hsr {
+                Some(np_info.version)
+            } else {
+                None
+            },
+            protocol: Some(protocol),
+        }
+    }
+}
+
+pub(crate) fn apply_hsr_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &HsrInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(conf) = iface.hsr.as_ref() {
+        let (Some(port1), Some(port2)) =
+            (conf.port1.as_ref(), conf.port2.as_ref())
+        else {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!("apply_hsr_conf() got HSR without ports: {iface:?}"),
+            ));
+        };
+        let mut np_conf = nispor::HsrConf::default();
+        np_conf.port1 = port1.to_string();
+        np_conf.port2 = port2.to_string();
+        np_conf.multicast_spec = conf
+            .supervision_address
+            .as_deref()
+            .and_then(HsrConfig::multicast_spec_of);
+        np_conf.version = conf.version;
+        np_conf.protocol = conf.protocol.map(|p| match p {
+            HsrProtocol::Hsr => nispor::HsrProtocol::Hsr,
+            HsrProtocol::Prp => nispor::HsrProtocol::Prp,
+        });
+        np_iface.hsr = Some(np_conf);
+    }
+    Ok(vec![np_iface])
+}
+
+impl HsrInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            hsr: np_iface.hsr.as_ref().map(HsrConfig::from),
+        }
+    }
+}
+
+// Kernel does not set HSR interface as master of its ports, hence we set the
+// controller of HSR ports based on HSR configuration.
+pub(crate) fn set_hsr_ports_controller(ifaces: &mut Interfaces) {
+    let mut port_to_ctrl: Vec<(String, String)> = Vec::new();
+    for iface in ifaces.kernel_ifaces.values() {
+        if let Interface::Hsr(hsr_iface) = iface {
+            for port in hsr_iface.ports().unwrap_or_default() {
+                port_to_ctrl
+                    .push((port.to_string(), hsr_iface.name().to_string()));
+            }
+        }
+    }
+    for (port, ctrl) in port_to_ctrl {
+        if let Some(port_iface) = ifaces.kernel_ifaces.get_mut(&port) {
+            port_iface.base_iface_mut().controller = Some(ctrl);
+            port_iface.base_iface_mut().controller_type =
+                Some(InterfaceType::Hsr);
+        }
+    }
+}
@@ -1,9 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    base_iface::apply_base_iface_link_changes, bond::apply_bond_conf,
-    ethernet::apply_ethernet_conf, linux_bridge::apply_bridge_conf,
-    vlan::apply_vlan_conf, wireguard::apply_wg_conf,
+    base_iface::apply_base_iface_link_changes,
+    bond::apply_bond_conf,
+    ethernet::apply_ethernet_conf,
+    hsr::apply_hsr_conf,
+    infiniband::apply_infiniband_conf,
+    ip_tunnel::apply_ip_tunnel_conf,
+    ipvlan::apply_ipvlan_conf,
+    linux_bridge::apply_bridge_conf,
+    mac_vlan::{apply_mac_vlan_conf, apply_mac_vtap_conf},
+    macsec::apply_macsec_conf,
+    vlan::apply_vlan_conf,
+    vrf::apply_vrf_conf,
+    vxlan::apply_vxlan_conf,
+    wireguard::apply_wg_conf,
 };
 use crate::{
     BaseInterface, Interface, InterfaceState, InterfaceType, MergedInterfaces,
@@ -23,6 +34,22 @@ pub(crate) fn nipart_iface_type_to_nispor(
         InterfaceType::Bond => nispor::IfaceType::Bond,
         InterfaceType::LinuxBridge => nispor::IfaceType::Bridge,
         InterfaceType::Wireguard => nispor::IfaceType::Wireguard,
+        InterfaceType::OvsInterface => nispor::IfaceType::OpenvSwitch,
+        InterfaceType::Vrf => nispor::IfaceType::Vrf,
+        InterfaceType::Vxlan => nispor::IfaceType::Vxlan,
+        InterfaceType::MacVlan => nispor::IfaceType::MacVlan,
+        InterfaceType::IpVlan => nispor::IfaceType::IpVlan,
+        InterfaceType::MacVtap => nispor::IfaceType::MacVtap,
+        InterfaceType::MacSec => nispor::IfaceType::MacSec,
+        InterfaceType::Hsr => nispor::IfaceType::Hsr,
+        InterfaceType::InfiniBand => nispor::IfaceType::Ipoib,
+        InterfaceType::Tun => nispor::IfaceType::Tun,
+        InterfaceType::Gre => nispor::IfaceType::Gre,
+        InterfaceType::GreTap => nispor::IfaceType::GreTap,
+        InterfaceType::Ip6Gre => nispor::IfaceType::Ip6Gre,
+        InterfaceType::Ipip => nispor::IfaceType::Ipip,
+        InterfaceType::Sit => nispor::IfaceType::Sit,
+        InterfaceType::Ip6Tnl => nispor::IfaceType::Ip6Tnl,
         v => {
             log::warn!(
                 "BUG: Requesting unsupported interface type {iface_type}"
@@ -96,6 +123,24 @@ pub(crate) fn apply_iface_link_changes(
         )
     } else if let Interface::Wireguard(apply_iface) = apply_iface {
         apply_wg_conf(np_iface, apply_iface)
+    } else if let Interface::Vrf(apply_iface) = apply_iface {
+        apply_vrf_conf(np_iface, apply_iface)
+    } else if let Interface::Vxlan(apply_iface) = apply_iface {
+        apply_vxlan_conf(np_iface, apply_iface)
+    } else if let Interface::MacVlan(apply_iface) = apply_iface {
+        apply_mac_vlan_conf(np_iface, apply_iface)
+    } else if let Interface::IpVlan(apply_iface) = apply_iface {
+        apply_ipvlan_conf(np_iface, apply_iface)
+    } else if let Interface::MacVtap(apply_iface) = apply_iface {
+        apply_mac_vtap_conf(np_iface, apply_iface)
+    } else if let Interface::MacSec(apply_iface) = apply_iface {
+        apply_macsec_conf(np_iface, apply_iface)
+    } else if let Interface::Hsr(apply_iface) = apply_iface {
+        apply_hsr_conf(np_iface, apply_iface)
+    } else if let Interface::InfiniBand(apply_iface) = apply_iface {
+        apply_infiniband_conf(np_iface, apply_iface)
+    } else if let Interface::IpTunnel(apply_iface) = apply_iface {
+        apply_ip_tunnel_conf(np_iface, apply_iface)
     } else {
         Ok(vec![np_iface])
     }
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, InfiniBandConfig, InfiniBandInterface, InfiniBandMode,
+    NipartError,
+};
+
+impl From<nispor::IpoibMode> for InfiniBandMode {
+    fn from(v: nispor::IpoibMode) -> Self {
+        match v {
+            nispor::IpoibMode::Datagram => Self::Datagram,
+            nispor::IpoibMode::Connected => Self::Connected,
+            m => {
+                log::debug!("Got unknown IPoIB mode {m:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl From<&nispor::IpoibInfo> for InfiniBandConfig {
+    fn from(np_info: &nispor::IpoibInfo) -> Self {
+        Self {
+            mode: Some(np_info.mode.into()),
+            base_iface: np_info.base_iface.clone(),
+            pkey: Some(np_info.pkey),
+        }
+    }
+}
+
+pub(crate) fn apply_infiniband_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &InfiniBandInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(ib_conf) = iface.infiniband.as_ref() {
+        let mut np_ipoib = nispor::IpoibConf::default();
+        // Physical IPoIB interface cannot change its pkey
+        if ib_conf.base_iface.is_some() {
+            np_ipoib.base_iface = ib_conf.base_iface.clone();
+            np_ipoib.pkey = ib_conf.pkey;
+        }
+        np_ipoib.mode = match ib_conf.mode {
+            Some(InfiniBandMode::Datagram) => Some(nispor::IpoibMode::Datagram),
+            Some(InfiniBandMode::Connected) => {
+                Some(nispor::IpoibMode::Connected)
+            }
+            _ => None,
+        };
+        np_iface.ipoib = Some(np_ipoib);
+    }
+    Ok(vec![np_iface])
+}
+
+impl InfiniBandInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            infiniband: np_iface.ipoib.as_ref().map(InfiniBandConfig::from),
+        }
+    }
+}
@@ -2,12 +2,18 @@
 
 use super::{
     iface::{apply_iface_link_changes, nipart_iface_type_to_nispor},
-    ip::apply_iface_ip_changes,
+    ip::{
+        apply_iface_ip_changes, apply_ipv6_addr_gen_sysctl,
+        apply_ipv6_ra_sysctl, apply_ipv6_token,
+    },
+    ovs::NipartOvsDb,
+    sriov::{apply_sriov_total_vfs, remerge_new_vfs},
+    tun::apply_tun_ifaces,
     wifi::NipartWpaConn,
 };
 use crate::{
-    ErrorKind, Interface, InterfaceType, MergedInterface, MergedInterfaces,
-    NipartError, NipartInterface,
+    ErrorKind, Interface, InterfaceType, MacSecInterface, MergedInterface,
+    MergedInterfaces, NipartError, NipartInterface,
 };
 
 pub(crate) async fn apply_ifaces(
@@ -15,11 +21,24 @@ pub(crate) async fn apply_ifaces(
 ) -> Result<(), NipartError> {
     delete_ifaces_before_apply(merged_ifaces).await?;
 
+    apply_tun_ifaces(merged_ifaces)?;
+
+    // VF network devices should be ready before applying other changes
+    let remerged_ifaces = match apply_sriov_total_vfs(merged_ifaces).await? {
+        Some(cur_state) => {
+            Some(remerge_new_vfs(merged_ifaces, &cur_state.ifaces)?)
+        }
+        None => None,
+    };
+    let merged_ifaces = remerged_ifaces.as_ref().unwrap_or(merged_ifaces);
+
     // Some interface might been deleted when apply, hence it is OK to fail, we
     // trust verification stage to find the problem
     if let Err(e) = apply_ifaces_link_changes(merged_ifaces).await {
         log::info!("{e}");
     }
+    // MKA keys cannot be queried, hence verification cannot detect failure
+    apply_macsec_mka(merged_ifaces).await?;
     if let Err(e) = apply_ifaces_ip_changes(merged_ifaces).await {
         log::info!("{e}");
     }
@@ -90,6 +109,7 @@ async fn apply_ifaces_link_changes(
     });
 
     let mut changed_wifi_ifaces: Vec<&Interface> = Vec::new();
+    let mut changed_ovs_ifaces: Vec<&Interface> = Vec::new();
 
     for merged_iface in sorted_changed_mergd_ifaces.as_slice() {
         let apply_iface = if let Some(i) = merged_iface.for_apply.as_ref() {
@@ -114,6 +134,20 @@ async fn apply_ifaces_link_changes(
             changed_wifi_ifaces.push(apply_iface);
         }
 
+        if apply_iface.iface_type() == &InterfaceType::OvsBridge
+            && apply_iface.is_absent()
+        {
+            changed_ovs_ifaces.push(apply_iface);
+        }
+
+        // OVS internal interface is created and deleted by OVS daemon
+        if apply_iface.iface_type() == &InterfaceType::OvsInterface
+            && (apply_iface.is_absent() || merged_iface.current.is_none())
+        {
+            changed_ovs_ifaces.push(apply_iface);
+            continue;
+        }
+
         if !apply_iface.iface_type().is_userspace() {
             for np_iface in apply_iface_link_changes(
                 apply_iface,
@@ -160,7 +194,7 @@ async fn apply_ifaces_link_changes(
                 ));
             }
             Interface::OvsBridge(_) => {
-                // Place holder
+                changed_ovs_ifaces.push(apply_iface);
             }
             _ => (),
         }
@@ -182,6 +216,16 @@ async fn apply_ifaces_link_changes(
         }
     }
 
+    if !changed_ovs_ifaces.is_empty() {
+        NipartOvsDb::apply(changed_ovs_ifaces.as_slice(), merged_ifaces)
+            .await?;
+        apply_new_ovs_ifaces_link_changes(
+            changed_ovs_ifaces.as_slice(),
+            merged_ifaces,
+        )
+        .await?;
+    }
+
     if !changed_wifi_ifaces.is_empty() {
         NipartWpaConn::apply(changed_wifi_ifaces.as_slice(), merged_ifaces)
             .await?;
@@ -190,10 +234,81 @@ async fn apply_ifaces_link_changes(
     Ok(())
 }
 
+// Start MKA session for MACsec interface with keys defined and stop it for
+// removed MACsec interface.
+async fn apply_macsec_mka(
+    merged_ifaces: &MergedInterfaces,
+) -> Result<(), NipartError> {
+    let mut macsec_ifaces: Vec<(&MacSecInterface, &str)> = Vec::new();
+    for merged_iface in merged_ifaces.kernel_ifaces.values() {
+        let Some(Interface::MacSec(apply_iface)) =
+            merged_iface.for_apply.as_ref()
+        else {
+            continue;
+        };
+        let Some(base_iface) = merged_iface
+            .merged
+            .parent()
+            .or_else(|| merged_iface.current.as_ref().and_then(|c| c.parent()))
+        else {
+            continue;
+        };
+        let has_keys = apply_iface
+            .macsec
+            .as_ref()
+            .is_some_and(|c| c.mka_cak.is_some());
+        if (apply_iface.is_up() && has_keys)
+            || (!apply_iface.is_up() && merged_iface.current.is_some())
+        {
+            macsec_ifaces.push((apply_iface, base_iface));
+        }
+    }
+    if macsec_ifaces.is_empty() {
+        return Ok(());
+    }
+    NipartWpaConn::apply_macsec(macsec_ifaces.as_slice()).await
+}
+
+// Newly created OVS internal interfaces only exist in kernel after OVSDB
+// transaction, hence we apply their link layer changes afterwards.
+async fn apply_new_ovs_ifaces_link_changes(
+    changed_ovs_ifaces: &[&Interface],
+    merged_ifaces: &MergedInterfaces,
+) -> Result<(), NipartError> {
+    let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
+    for apply_iface in changed_ovs_ifaces.iter().filter(|i| {
+        i.iface_type() == &InterfaceType::OvsInterface && !i.is_absent()
+    }) {
+        np_ifaces.extend(apply_iface_link_changes(
+            apply_iface,
+            None,
+            merged_ifaces,
+        )?);
+    }
+    if !np_ifaces.is_empty() {
+        let mut net_conf = nispor::NetConf::default();
+        net_conf.ifaces = Some(np_ifaces);
+
+        log::trace!(
+            "Pending nispor changes {}",
+            serde_json::to_string(&net_conf).unwrap_or_default()
+        );
+        if let Err(e) = net_conf.apply_async().await {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to change link layer of OVS interfaces: {e}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
 async fn apply_ifaces_ip_changes(
     merged_ifaces: &MergedInterfaces,
 ) -> Result<(), NipartError> {
     let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
+    // Sysctl failure of one interface should not block other interfaces
+    let mut sysctl_errors: Vec<NipartError> = Vec::new();
 
     for merged_iface in merged_ifaces
         .kernel_ifaces
@@ -203,6 +318,22 @@ async fn apply_ifaces_ip_changes(
         // It is safe to unwrap here as it is checked by filter()
         let apply_iface = merged_iface.for_apply.as_ref().unwrap();
 
+        if !apply_iface.is_absent() {
+            if let Err(e) = apply_ipv6_ra_sysctl(
+                apply_iface.base_iface(),
+                merged_iface.current.as_ref().map(|c| c.base_iface()),
+            )
+            .and_then(|_| apply_ipv6_addr_gen_sysctl(apply_iface.base_iface()))
+            {
+                log::error!(
+                    "Failed to apply IPv6 sysctl of interface {}: {e}",
+                    apply_iface.name()
+                );
+                sysctl_errors.push(e);
+            }
+            apply_ipv6_token(apply_iface.base_iface()).await?;
+        }
+
         if let Some(np_iface) = apply_iface_ip_changes(
             apply_iface.base_iface(),
             merged_iface.current.as_ref().map(|c| c.base_iface()),
@@ -227,5 +358,16 @@ async fn apply_ifaces_ip_changes(
         }
     }
 
+    if let Some(first_error) = sysctl_errors.first() {
+        return Err(NipartError::new(
+            first_error.kind(),
+            sysctl_errors
+                .iter()
+                .map(|e| e.msg())
+                .collect::<Vec<&str>>()
+                .join("; "),
+        ));
+    }
+
     Ok(())
 }
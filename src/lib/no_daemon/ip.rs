@@ -1,14 +1,47 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use futures_util::stream::TryStreamExt;
+use rtnetlink::packet_route::{
+    address::AddressAttribute,
+    link::{AfSpecInet6, AfSpecUnspec, LinkAttribute, LinkMessage},
+};
 
 use super::iface::init_np_iface;
 use crate::{
-    BaseInterface, InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, NipartError,
+    BaseInterface, ErrorKind, InterfaceIpAddr, InterfaceIpAddrOrigin,
+    InterfaceIpv4, InterfaceIpv6, Interfaces, Ipv4LinkLocal, Ipv6AddrGenMode,
+    Ipv6Privacy, NipartError, NipartInterface, NipartNoDaemon,
 };
 
+const IPV6_SYSCTL_DIR: &str = "/proc/sys/net/ipv6/conf";
+// Sysctl values of newly created interfaces, used when restoring defaults
+const IPV6_SYSCTL_DEFAULT_CONF: &str = "default";
+// Kernel defaults used when `default` sysctl is not readable
+const DEFAULT_ACCEPT_RA_DEFRTR: &str = "1";
+const DEFAULT_ACCEPT_RA_RT_INFO_MAX_PLEN: &str = "0";
+// Host wide secret for generating IPv6 stable privacy addresses
+const IPV6_STABLE_SECRET_PATH: &str = "/etc/nipart/ipv6-stable-secret";
+// Values of `addr_gen_mode` defined in linux/if_link.h
+const IN6_ADDR_GEN_MODE_EUI64: &str = "0";
+const IN6_ADDR_GEN_MODE_STABLE_PRIVACY: &str = "2";
+const IN6_ADDR_GEN_MODE_RANDOM: &str = "3";
+// Holding IP addresses learned from DHCP per interface
+const DHCP_ADDR_DIR: &str = "/run/nipart/dhcp_addr";
+const FOREVER: &str = "forever";
+const IPV4_LINK_LOCAL_PREFIX_LEN: u8 = 16;
+// Address protocol defined in linux/if_addr.h
+const IFAPROT_KERNEL_RA: u8 = 2;
+const IFAPROT_KERNEL_LL: u8 = 3;
+
 pub(crate) fn np_ipv4_to_nipart(
     np_iface: &nispor::Iface,
+    addr_origins: &NipartIpAddrOrigins,
 ) -> Option<InterfaceIpv4> {
     if let Some(np_ip) = &np_iface.ipv4 {
         let mut ip = InterfaceIpv4 {
@@ -18,45 +51,26 @@ pub(crate) fn np_ipv4_to_nipart(
         if !ip.is_enabled() {
             return Some(ip);
         }
-        let mut addresses = Vec::new();
-        for np_addr in &np_ip.addresses {
-            if np_addr.valid_lft != "forever" {
-                ip.dhcp = Some(true);
-            }
-            match std::net::IpAddr::from_str(np_addr.address.as_str()) {
-                Ok(i) => {
-                    let addr = InterfaceIpAddr {
-                        ip: i,
-                        prefix_length: np_addr.prefix_len,
-                        valid_life_time: if np_addr.valid_lft != "forever" {
-                            Some(np_addr.valid_lft.clone())
-                        } else {
-                            None
-                        },
-                        preferred_life_time: if np_addr.preferred_lft
-                            != "forever"
-                        {
-                            Some(np_addr.preferred_lft.clone())
-                        } else {
-                            None
-                        },
-                        ..Default::default()
-                    };
-                    addresses.push(addr);
-                }
-                Err(e) => {
-                    log::warn!(
-                        "BUG: nispor got invalid IP address {}, error {}",
-                        np_addr.address.as_str(),
-                        e
-                    );
-                }
-            }
-        }
+        let addresses: Vec<InterfaceIpAddr> = np_ip
+            .addresses
+            .iter()
+            .filter_map(|np_addr| {
+                np_ip_addr_to_nipart(
+                    np_iface,
+                    np_addr.address.as_str(),
+                    np_addr.prefix_len,
+                    np_addr.valid_lft.as_str(),
+                    np_addr.preferred_lft.as_str(),
+                    addr_origins,
+                )
+            })
+            .collect();
+        ip.dhcp = Some(
+            addresses
+                .iter()
+                .any(|a| a.origin == Some(InterfaceIpAddrOrigin::Dhcp)),
+        );
         ip.addresses = Some(addresses);
-        if ip.dhcp.is_none() {
-            ip.dhcp = Some(false);
-        }
         Some(ip)
     } else {
         // IP might just disabled
@@ -66,6 +80,7 @@ pub(crate) fn np_ipv4_to_nipart(
 
 pub(crate) fn np_ipv6_to_nipart(
     np_iface: &nispor::Iface,
+    addr_origins: &NipartIpAddrOrigins,
 ) -> Option<InterfaceIpv6> {
     if let Some(np_ip) = &np_iface.ipv6 {
         let mut ip = InterfaceIpv6 {
@@ -76,50 +91,51 @@ pub(crate) fn np_ipv6_to_nipart(
         if !ip.is_enabled() {
             return Some(ip);
         }
-        let mut addresses = Vec::new();
-        for np_addr in &np_ip.addresses {
-            if np_addr.valid_lft != "forever" {
-                if np_addr.prefix_len == 128 {
-                    ip.dhcp = Some(true);
-                } else {
-                    ip.autoconf = Some(true);
-                }
-            }
-            match std::net::IpAddr::from_str(np_addr.address.as_str()) {
-                Ok(i) => {
-                    let addr = InterfaceIpAddr {
-                        ip: i,
-                        prefix_length: np_addr.prefix_len,
-                        valid_life_time: if np_addr.valid_lft != "forever" {
-                            Some(np_addr.valid_lft.clone())
-                        } else {
-                            None
-                        },
-                        preferred_life_time: if np_addr.preferred_lft
-                            != "forever"
-                        {
-                            Some(np_addr.preferred_lft.clone())
-                        } else {
-                            None
-                        },
-                        ..Default::default()
-                    };
-                    addresses.push(addr);
-                }
-                Err(e) => {
-                    log::warn!(
-                        "BUG: nispor got invalid IP address {}, error {}",
-                        np_addr.address.as_str(),
-                        e
-                    );
+        let addresses: Vec<InterfaceIpAddr> = np_ip
+            .addresses
+            .iter()
+            .filter_map(|np_addr| {
+                np_ip_addr_to_nipart(
+                    np_iface,
+                    np_addr.address.as_str(),
+                    np_addr.prefix_len,
+                    np_addr.valid_lft.as_str(),
+                    np_addr.preferred_lft.as_str(),
+                    addr_origins,
+                )
+            })
+            .collect();
+        ip.dhcp = Some(
+            addresses
+                .iter()
+                .any(|a| a.origin == Some(InterfaceIpAddrOrigin::Dhcp)),
+        );
+        ip.autoconf = Some(
+            addresses
+                .iter()
+                .any(|a| a.origin == Some(InterfaceIpAddrOrigin::Autoconf)),
+        );
+        ip.addr_gen_mode =
+            match read_ipv6_sysctl(np_iface.name.as_str(), "addr_gen_mode")
+                .as_deref()
+            {
+                Some(IN6_ADDR_GEN_MODE_EUI64) => Some(Ipv6AddrGenMode::Eui64),
+                Some(IN6_ADDR_GEN_MODE_STABLE_PRIVACY) => {
+                    Some(Ipv6AddrGenMode::StablePrivacy)
                 }
-            }
-        }
+                Some(IN6_ADDR_GEN_MODE_RANDOM) => Some(Ipv6AddrGenMode::Random),
+                _ => None,
+            };
+        // Negative `use_tempaddr` means disabled also
+        ip.privacy = read_ipv6_sysctl(np_iface.name.as_str(), "use_tempaddr")
+            .and_then(|v| v.parse::<i32>().ok())
+            .map(|v| match v {
+                1 => Ipv6Privacy::PreferPublic,
+                v if v >= 2 => Ipv6Privacy::PreferTemporary,
+                _ => Ipv6Privacy::Disabled,
+            });
+        np_ipv6_ra_sysctl_to_nipart(np_iface.name.as_str(), &mut ip);
         ip.addresses = Some(addresses);
-        if ip.autoconf.is_none() && ip.dhcp.is_none() {
-            ip.dhcp = Some(false);
-            ip.autoconf = Some(false);
-        }
         Some(ip)
     } else {
         // IP might just disabled
@@ -127,6 +143,260 @@ pub(crate) fn np_ipv6_to_nipart(
     }
 }
 
+// Default `accept_ra_rt_info_max_plen` is 0, hence `auto-routes` is unknown
+// when both `accept_ra_defrtr` and `accept_ra_rt_info_max_plen` are 0.
+fn np_ipv6_ra_sysctl_to_nipart(iface_name: &str, ip: &mut InterfaceIpv6) {
+    let accept_ra_defrtr = read_ipv6_sysctl(iface_name, "accept_ra_defrtr");
+    let max_plen = read_ipv6_sysctl(iface_name, "accept_ra_rt_info_max_plen");
+    ip.auto_gateway = accept_ra_defrtr.as_deref().map(|v| v != "0");
+    if ip.auto_gateway == Some(true)
+        || max_plen.as_deref().is_some_and(|v| v != "0")
+    {
+        ip.auto_routes = Some(true);
+    }
+    ip.auto_route_metric = read_ipv6_sysctl(iface_name, "ra_defrtr_metric")
+        .and_then(|v| v.parse::<u32>().ok());
+}
+
+fn np_ip_addr_to_nipart(
+    np_iface: &nispor::Iface,
+    address: &str,
+    prefix_length: u8,
+    valid_lft: &str,
+    preferred_lft: &str,
+    addr_origins: &NipartIpAddrOrigins,
+) -> Option<InterfaceIpAddr> {
+    match IpAddr::from_str(address) {
+        Ok(ip) => {
+            let mut addr = InterfaceIpAddr::new(ip, prefix_length);
+            if valid_lft != FOREVER {
+                addr.valid_life_time = Some(valid_lft.to_string());
+            }
+            if preferred_lft != FOREVER {
+                addr.preferred_life_time = Some(preferred_lft.to_string());
+            }
+            addr.origin = Some(addr_origins.get(np_iface, &addr));
+            Some(addr)
+        }
+        Err(e) => {
+            log::warn!(
+                "BUG: nispor got invalid IP address {address}, error {e}"
+            );
+            None
+        }
+    }
+}
+
+/// Origin of IP addresses based on address protocol reported by kernel and
+/// IP addresses owned by DHCP leases.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NipartIpAddrOrigins {
+    // Kernel support `IFA_PROTO` since 5.18
+    kernel_has_proto: bool,
+    // Indexed by interface index and IP address
+    kernel: HashMap<(u32, IpAddr), InterfaceIpAddrOrigin>,
+    // Whether DHCP address record storage exists
+    has_dhcp_record: bool,
+    // Indexed by interface name
+    dhcp: HashMap<String, Vec<IpAddr>>,
+}
+
+impl NipartIpAddrOrigins {
+    pub(crate) async fn retrieve() -> Result<Self, NipartError> {
+        let mut ret = Self {
+            has_dhcp_record: std::path::Path::new(DHCP_ADDR_DIR).is_dir(),
+            dhcp: read_dhcp_addrs(),
+            ..Default::default()
+        };
+        let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to create rtnetlink connection: {e}"),
+            )
+        })?;
+        tokio::spawn(conn);
+        let mut addr_msgs = handle.address().get().execute();
+        while let Some(addr_msg) = addr_msgs.try_next().await.map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to query IP addresses via rtnetlink: {e}"),
+            )
+        })? {
+            let mut ip = None;
+            let mut proto = None;
+            for attr in addr_msg.attributes {
+                match attr {
+                    AddressAttribute::Address(i) => ip = Some(i),
+                    AddressAttribute::Protocol(p) => proto = Some(u8::from(p)),
+                    _ => (),
+                }
+            }
+            let Some(proto) = proto else {
+                continue;
+            };
+            ret.kernel_has_proto = true;
+            let origin = match proto {
+                IFAPROT_KERNEL_RA => InterfaceIpAddrOrigin::Autoconf,
+                IFAPROT_KERNEL_LL => InterfaceIpAddrOrigin::LinkLocal,
+                _ => continue,
+            };
+            if let Some(ip) = ip {
+                ret.kernel.insert((addr_msg.header.index, ip), origin);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn get(
+        &self,
+        np_iface: &nispor::Iface,
+        addr: &InterfaceIpAddr,
+    ) -> InterfaceIpAddrOrigin {
+        if self
+            .dhcp
+            .get(np_iface.name.as_str())
+            .is_some_and(|ips| ips.contains(&addr.ip))
+        {
+            return InterfaceIpAddrOrigin::Dhcp;
+        }
+        if let Some(origin) = self.kernel.get(&(np_iface.index, addr.ip)) {
+            return *origin;
+        }
+        match addr.ip {
+            IpAddr::V4(ip) if ip.is_link_local() => {
+                InterfaceIpAddrOrigin::LinkLocal
+            }
+            IpAddr::V6(ip) if ip.is_unicast_link_local() => {
+                InterfaceIpAddrOrigin::LinkLocal
+            }
+            // Old kernel cannot tell address generated by IPv6 autoconf,
+            // treat dynamic address with prefix length other than 128 as
+            // autoconf.
+            IpAddr::V6(_)
+                if !self.kernel_has_proto
+                    && addr.valid_life_time.is_some()
+                    && addr.prefix_length != 128 =>
+            {
+                InterfaceIpAddrOrigin::Autoconf
+            }
+            // DHCP is not managed by nipart when DHCP address record
+            // storage does not exist, treat dynamic address as DHCP.
+            _ if !self.has_dhcp_record && addr.valid_life_time.is_some() => {
+                InterfaceIpAddrOrigin::Dhcp
+            }
+            _ => InterfaceIpAddrOrigin::Static,
+        }
+    }
+}
+
+impl NipartNoDaemon {
+    /// Add or remove IPv4 link-local address(169.254.0.0/16) without
+    /// touching other IP addresses of specified interface.
+    pub async fn apply_ipv4_link_local_addr(
+        iface_name: &str,
+        addr: Ipv4Addr,
+        remove: bool,
+    ) -> Result<(), NipartError> {
+        let mut np_ip_conf = nispor::IpConf::default();
+        np_ip_conf.addresses = vec![nipart_ip_addr_to_nispor(
+            &InterfaceIpAddr::new(addr.into(), IPV4_LINK_LOCAL_PREFIX_LEN),
+            remove,
+        )];
+        let mut np_iface = nispor::IfaceConf::default();
+        np_iface.name = iface_name.to_string();
+        np_iface.ipv4 = Some(np_ip_conf);
+        let mut net_conf = nispor::NetConf::default();
+        net_conf.ifaces = Some(vec![np_iface]);
+
+        net_conf.apply_async().await.map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Failed to {} IPv4 link-local address {addr} on interface \
+                     {iface_name}: {e}",
+                    if remove { "remove" } else { "add" }
+                ),
+            )
+        })
+    }
+
+    /// Create the storage of DHCP address records, indicating nipart is
+    /// managing DHCP on this host, hence dynamic IP addresses without DHCP
+    /// record are not reported as DHCP addresses.
+    pub fn init_dhcp_addrs() -> Result<(), NipartError> {
+        std::fs::create_dir_all(DHCP_ADDR_DIR).map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to create {DHCP_ADDR_DIR}: {e}"),
+            )
+        })
+    }
+
+    /// Store IP addresses learned from DHCP lease of specified interface, so
+    /// they could be reported with `origin: dhcp`. Setting `addrs` to empty
+    /// removes the record, for example DHCP stopped.
+    pub fn apply_dhcp_addrs(
+        iface_name: &str,
+        is_ipv6: bool,
+        addrs: &[IpAddr],
+    ) -> Result<(), NipartError> {
+        let path = format!(
+            "{DHCP_ADDR_DIR}/{iface_name}.{}.json",
+            if is_ipv6 { "dhcpv6" } else { "dhcpv4" }
+        );
+        if addrs.is_empty() {
+            std::fs::remove_file(&path).ok();
+            return Ok(());
+        }
+        let content = serde_json::to_string(addrs).map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to generate JSON for DHCP addresses: {e}"),
+            )
+        })?;
+        std::fs::create_dir_all(DHCP_ADDR_DIR)
+            .and_then(|_| std::fs::write(&path, content))
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to write {path}: {e}"),
+                )
+            })
+    }
+}
+
+// Indexed by interface name
+fn read_dhcp_addrs() -> HashMap<String, Vec<IpAddr>> {
+    let mut ret: HashMap<String, Vec<IpAddr>> = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(DHCP_ADDR_DIR) else {
+        return ret;
+    };
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(iface_name) = file_name
+            .strip_suffix(".dhcpv4.json")
+            .or_else(|| file_name.strip_suffix(".dhcpv6.json"))
+        else {
+            continue;
+        };
+        match std::fs::read_to_string(entry.path())
+            .ok()
+            .and_then(|c| serde_json::from_str::<Vec<IpAddr>>(&c).ok())
+        {
+            Some(ips) => {
+                ret.entry(iface_name.to_string()).or_default().extend(ips)
+            }
+            None => {
+                log::warn!(
+                    "Ignoring corrupted DHCP address file {}",
+                    entry.path().display()
+                );
+            }
+        }
+    }
+    ret
+}
+
 pub(crate) fn apply_iface_ip_changes(
     des_iface: &BaseInterface,
     cur_iface: Option<&BaseInterface>,
@@ -135,6 +405,29 @@ pub(crate) fn apply_iface_ip_changes(
         return Ok(None);
     }
 
+    // Addresses are not owned by DHCP anymore once DHCP changed from enabled
+    // to disabled. Desired state not mentioning `dhcp` keeps DHCP unchanged.
+    if des_iface
+        .ipv4
+        .as_ref()
+        .is_some_and(|i| !i.is_enabled() || i.dhcp == Some(false))
+        && cur_iface
+            .and_then(|c| c.ipv4.as_ref())
+            .is_some_and(|i| i.is_auto())
+    {
+        NipartNoDaemon::apply_dhcp_addrs(des_iface.name.as_str(), false, &[])?;
+    }
+    if des_iface
+        .ipv6
+        .as_ref()
+        .is_some_and(|i| !i.is_enabled() || i.dhcp == Some(false))
+        && cur_iface
+            .and_then(|c| c.ipv6.as_ref())
+            .is_some_and(|i| i.is_dhcp())
+    {
+        NipartNoDaemon::apply_dhcp_addrs(des_iface.name.as_str(), true, &[])?;
+    }
+
     let mut np_iface = init_np_iface(des_iface);
 
     let init_np_iface = np_iface.clone();
@@ -153,14 +446,26 @@ pub(crate) fn apply_iface_ip_changes(
             des_addrs = d;
         }
 
-        let mut cur_addrs: &[InterfaceIpAddr] = &[];
+        // IPv4 link-local addresses are managed by daemon, only purge them
+        // when `link-local: disabled` or IPv4 disabled.
+        let keep_link_local = des_ipv4.is_enabled()
+            && des_ipv4.link_local != Some(Ipv4LinkLocal::Disabled);
+        let mut cur_addrs: Vec<InterfaceIpAddr> = Vec::new();
         if let Some(cur_ipv4) = cur_iface.ipv4.as_ref()
             && cur_ipv4.is_enabled()
             && let Some(c) = cur_ipv4.addresses.as_ref()
         {
-            cur_addrs = c;
+            cur_addrs = c
+                .iter()
+                .filter(|a| {
+                    !(keep_link_local
+                        && a.is_link_local()
+                        && !des_addrs.iter().any(|d| d.ip == a.ip))
+                })
+                .map(without_origin)
+                .collect();
         }
-        let np_addrs = nipart_ip_addrs_to_nispor(des_addrs, cur_addrs);
+        let np_addrs = nipart_ip_addrs_to_nispor(des_addrs, &cur_addrs);
 
         if !np_addrs.is_empty() {
             let mut np_ip_conf = nispor::IpConf::default();
@@ -179,14 +484,20 @@ pub(crate) fn apply_iface_ip_changes(
             des_addrs = d;
         }
 
-        let mut cur_addrs: &[InterfaceIpAddr] = &[];
+        // IPv6 link-local addresses are managed by kernel and never included
+        // in desired state, hence should not be purged.
+        let mut cur_addrs: Vec<InterfaceIpAddr> = Vec::new();
         if let Some(cur_ipv6) = cur_iface.ipv6.as_ref()
             && cur_ipv6.is_enabled()
             && let Some(c) = cur_ipv6.addresses.as_ref()
         {
-            cur_addrs = c;
+            cur_addrs = c
+                .iter()
+                .filter(|a| !is_ipv6_link_local(a))
+                .map(without_origin)
+                .collect();
         }
-        let np_addrs = nipart_ip_addrs_to_nispor(des_addrs, cur_addrs);
+        let np_addrs = nipart_ip_addrs_to_nispor(des_addrs, &cur_addrs);
 
         if !np_addrs.is_empty() {
             let mut np_ip_conf = nispor::IpConf::default();
@@ -202,6 +513,280 @@ pub(crate) fn apply_iface_ip_changes(
     }
 }
 
+// Routes learned from IPv6 router announcement are managed by kernel, hence
+// `auto-routes`, `auto-gateway` and `auto-route-metric` are applied via
+// sysctl. Only options mentioned in desired are applied, enabled options
+// restore the defaults, so changing them back to default takes effect.
+pub(crate) fn apply_ipv6_ra_sysctl(
+    des_iface: &BaseInterface,
+    cur_iface: Option<&BaseInterface>,
+) -> Result<(), NipartError> {
+    let Some(des_ipv6) = des_iface.ipv6.as_ref().filter(|i| i.is_enabled())
+    else {
+        return Ok(());
+    };
+    let iface_name = des_iface.name.as_str();
+    let mut ipv6 = des_ipv6.clone();
+    // Current `auto-gateway` is false when `auto-routes` is false, hence only
+    // use it when desired does not mention `auto-routes`.
+    if let Some(cur_ipv6) = cur_iface
+        .and_then(|i| i.ipv6.as_ref())
+        .filter(|_| des_ipv6.auto_routes.is_none())
+    {
+        ipv6.auto_routes = cur_ipv6.auto_routes;
+        ipv6.auto_gateway = ipv6.auto_gateway.or(cur_ipv6.auto_gateway);
+    }
+
+    if des_ipv6.auto_routes.is_some() || des_ipv6.auto_gateway.is_some() {
+        let accept_ra_defrtr = if ipv6.is_auto_gateway() {
+            default_ipv6_sysctl("accept_ra_defrtr", DEFAULT_ACCEPT_RA_DEFRTR)
+        } else {
+            "0".to_string()
+        };
+        write_ipv6_sysctl_if_changed(
+            iface_name,
+            "accept_ra_defrtr",
+            &accept_ra_defrtr,
+        )?;
+    }
+
+    if des_ipv6.auto_routes.is_some() {
+        let max_plen = if ipv6.is_auto_routes() {
+            default_ipv6_sysctl(
+                "accept_ra_rt_info_max_plen",
+                DEFAULT_ACCEPT_RA_RT_INFO_MAX_PLEN,
+            )
+        } else {
+            "0".to_string()
+        };
+        write_ipv6_sysctl_if_changed(
+            iface_name,
+            "accept_ra_rt_info_max_plen",
+            &max_plen,
+        )?;
+    }
+
+    if let Some(metric) = des_ipv6.auto_route_metric {
+        write_ipv6_sysctl_if_changed(
+            iface_name,
+            "ra_defrtr_metric",
+            metric.to_string().as_str(),
+        )?;
+    }
+    Ok(())
+}
+
+// The `addr-gen-mode` and `privacy` are applied via sysctl, the `token` is
+// applied by `apply_ipv6_token()` via netlink.
+pub(crate) fn apply_ipv6_addr_gen_sysctl(
+    des_iface: &BaseInterface,
+) -> Result<(), NipartError> {
+    let Some(des_ipv6) = des_iface.ipv6.as_ref().filter(|i| i.is_enabled())
+    else {
+        return Ok(());
+    };
+    let iface_name = des_iface.name.as_str();
+    if let Some(mode) = des_ipv6.addr_gen_mode {
+        let value = match mode {
+            Ipv6AddrGenMode::Eui64 => IN6_ADDR_GEN_MODE_EUI64,
+            Ipv6AddrGenMode::StablePrivacy => {
+                // Kernel refuses stable privacy mode without stable secret
+                // defined, reading undefined secret returns EIO.
+                if read_ipv6_sysctl(iface_name, "stable_secret").is_none() {
+                    write_ipv6_sysctl(
+                        iface_name,
+                        "stable_secret",
+                        get_ipv6_stable_secret()?.to_string().as_str(),
+                    )?;
+                }
+                IN6_ADDR_GEN_MODE_STABLE_PRIVACY
+            }
+            Ipv6AddrGenMode::Random => IN6_ADDR_GEN_MODE_RANDOM,
+        };
+        write_ipv6_sysctl(iface_name, "addr_gen_mode", value)?;
+    }
+    if let Some(privacy) = des_ipv6.privacy {
+        write_ipv6_sysctl(
+            iface_name,
+            "use_tempaddr",
+            match privacy {
+                Ipv6Privacy::Disabled => "0",
+                Ipv6Privacy::PreferPublic => "1",
+                Ipv6Privacy::PreferTemporary => "2",
+            },
+        )?;
+    }
+    Ok(())
+}
+
+// Generated on first use and reused afterwards, so stable privacy addresses
+// are not changed after reboot.
+fn get_ipv6_stable_secret() -> Result<Ipv6Addr, NipartError> {
+    if let Some(secret) = std::fs::read_to_string(IPV6_STABLE_SECRET_PATH)
+        .ok()
+        .and_then(|s| Ipv6Addr::from_str(s.trim()).ok())
+    {
+        return Ok(secret);
+    }
+    let secret = Ipv6Addr::from(rand::random::<u128>());
+    log::info!("Generated host IPv6 stable secret {IPV6_STABLE_SECRET_PATH}");
+    if let Some(dir) = std::path::Path::new(IPV6_STABLE_SECRET_PATH).parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    std::fs::write(IPV6_STABLE_SECRET_PATH, secret.to_string()).map_err(
+        |e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to write {IPV6_STABLE_SECRET_PATH}: {e}"),
+            )
+        },
+    )?;
+    Ok(secret)
+}
+
+pub(crate) async fn apply_ipv6_token(
+    des_iface: &BaseInterface,
+) -> Result<(), NipartError> {
+    let Some(token) = des_iface
+        .ipv6
+        .as_ref()
+        .filter(|i| i.is_enabled())
+        .and_then(|i| i.token)
+    else {
+        return Ok(());
+    };
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+
+    let mut link_msg = LinkMessage::default();
+    link_msg
+        .attributes
+        .push(LinkAttribute::IfName(des_iface.name.clone()));
+    link_msg.attributes.push(LinkAttribute::AfSpecUnspec(vec![
+        AfSpecUnspec::Inet6(vec![AfSpecInet6::Token(token)]),
+    ]));
+    log::debug!("Setting IPv6 token of {} to {token}", des_iface.name);
+    handle.link().set(link_msg).execute().await.map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!(
+                "Failed to set IPv6 token of interface {} to {token}: {e}",
+                des_iface.name
+            ),
+        )
+    })
+}
+
+// Nispor does not report IPv6 token, query it via rtnetlink.
+pub(crate) async fn fill_ipv6_tokens(
+    ifaces: &mut Interfaces,
+) -> Result<(), NipartError> {
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+    let mut link_msgs = handle.link().get().execute();
+    while let Some(link_msg) = link_msgs.try_next().await.map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to query interfaces via rtnetlink: {e}"),
+        )
+    })? {
+        let mut iface_name = None;
+        let mut token = None;
+        for attr in link_msg.attributes {
+            match attr {
+                LinkAttribute::IfName(n) => iface_name = Some(n),
+                LinkAttribute::AfSpecUnspec(specs) => {
+                    for spec in specs {
+                        if let AfSpecUnspec::Inet6(inet6_specs) = spec {
+                            for inet6_spec in inet6_specs {
+                                if let AfSpecInet6::Token(t) = inet6_spec {
+                                    token = Some(t);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let (Some(iface_name), Some(token)) = (iface_name, token)
+            && !token.is_unspecified()
+            && let Some(ipv6) = ifaces
+                .kernel_ifaces
+                .get_mut(iface_name.as_str())
+                .and_then(|i| i.base_iface_mut().ipv6.as_mut())
+                .filter(|i| i.is_enabled())
+        {
+            ipv6.token = Some(token);
+        }
+    }
+    Ok(())
+}
+
+fn read_ipv6_sysctl(iface_name: &str, key: &str) -> Option<String> {
+    std::fs::read_to_string(format!("{IPV6_SYSCTL_DIR}/{iface_name}/{key}"))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+fn default_ipv6_sysctl(key: &str, fallback: &str) -> String {
+    read_ipv6_sysctl(IPV6_SYSCTL_DEFAULT_CONF, key)
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+fn write_ipv6_sysctl(
+    iface_name: &str,
+    key: &str,
+    value: &str,
+) -> Result<(), NipartError> {
+    let path = format!("{IPV6_SYSCTL_DIR}/{iface_name}/{key}");
+    log::debug!("Setting {path} to {value}");
+    std::fs::write(&path, value).map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to set {path} to {value}: {e}"),
+        )
+    })
+}
+
+fn write_ipv6_sysctl_if_changed(
+    iface_name: &str,
+    key: &str,
+    value: &str,
+) -> Result<(), NipartError> {
+    if read_ipv6_sysctl(iface_name, key).as_deref() == Some(value) {
+        Ok(())
+    } else {
+        write_ipv6_sysctl(iface_name, key, value)
+    }
+}
+
+// Origin is only for querying, should be ignored when comparing with
+// desired addresses.
+fn without_origin(ip_addr: &InterfaceIpAddr) -> InterfaceIpAddr {
+    let mut ret = ip_addr.clone();
+    ret.origin = None;
+    ret
+}
+
+fn is_ipv6_link_local(ip_addr: &InterfaceIpAddr) -> bool {
+    if let std::net::IpAddr::V6(ip) = ip_addr.ip {
+        ip.is_unicast_link_local()
+    } else {
+        false
+    }
+}
+
 fn nipart_ip_addr_to_nispor(
     ip_addr: &InterfaceIpAddr,
     remove: bool,
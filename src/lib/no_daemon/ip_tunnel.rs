@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, IpTunnelConfig, IpTunnelInterface,
+    IpTunnelMode, NipartError,
+};
+
+// Defined in linux/in.h
+const IPPROTO_IPIP: u8 = 4;
+const IPPROTO_IPV6: u8 = 41;
+
+impl From<&nispor::IpTunnelInfo> for IpTunnelConfig {
+    fn from(np_info: &nispor::IpTunnelInfo) -> Self {
+        Self {
+            base_iface: np_info.base_iface.clone(),
+            local: np_info.local,
+            remote: np_info.remote,
+            ttl: Some(np_info.ttl),
+            key: np_info.ikey,
+            encap_limit: np_info.encap_limit,
+            mode: None,
+        }
+    }
+}
+
+fn np_proto_to_ip6tnl_mode(proto: Option<u8>) -> IpTunnelMode {
+    match proto {
+        Some(0) | None => IpTunnelMode::Any,
+        Some(IPPROTO_IPV6) => IpTunnelMode::Ip6Ip6,
+        Some(IPPROTO_IPIP) => IpTunnelMode::IpIp6,
+        Some(p) => {
+            log::debug!("Got unknown ip6tnl protocol {p}");
+            IpTunnelMode::Unknown
+        }
+    }
+}
+
+pub(crate) fn apply_ip_tunnel_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &IpTunnelInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(tunnel_conf) = iface.ip_tunnel.as_ref() {
+        let mut np_tunnel = nispor::IpTunnelConf::default();
+        np_tunnel.base_iface = tunnel_conf.base_iface.clone();
+        np_tunnel.local = tunnel_conf.local;
+        np_tunnel.remote = tunnel_conf.remote;
+        np_tunnel.ttl = tunnel_conf.ttl;
+        // Nipart use the same GRE key for both directions
+        np_tunnel.ikey = tunnel_conf.key;
+        np_tunnel.okey = tunnel_conf.key;
+        np_tunnel.encap_limit = tunnel_conf.encap_limit;
+        np_tunnel.proto = match tunnel_conf.mode {
+            Some(IpTunnelMode::Any) => Some(0),
+            Some(IpTunnelMode::Ip6Ip6) => Some(IPPROTO_IPV6),
+            Some(IpTunnelMode::IpIp6) => Some(IPPROTO_IPIP),
+            Some(IpTunnelMode::Unknown) => {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Unknown mode for ip6tnl interface {}",
+                        iface.base.name
+                    ),
+                ));
+            }
+            None => None,
+        };
+        np_iface.ip_tunnel = Some(np_tunnel);
+    }
+    Ok(vec![np_iface])
+}
+
+impl IpTunnelInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        let mut ip_tunnel =
+            np_iface.ip_tunnel.as_ref().map(IpTunnelConfig::from);
+        if base_iface.iface_type == InterfaceType::Ip6Tnl
+            && let Some(conf) = ip_tunnel.as_mut()
+        {
+            conf.mode = Some(np_proto_to_ip6tnl_mode(
+                np_iface.ip_tunnel.as_ref().and_then(|i| i.proto),
+            ));
+        }
+        Self {
+            base: base_iface,
+            ip_tunnel,
+        }
+    }
+}
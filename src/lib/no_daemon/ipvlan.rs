@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, ErrorKind, IpVlanConfig, IpVlanInterface, IpVlanMode,
+    NipartError,
+};
+
+impl From<nispor::IpVlanMode> for IpVlanMode {
+    fn from(v: nispor::IpVlanMode) -> Self {
+        match v {
+            nispor::IpVlanMode::L2 => Self::L2,
+            nispor::IpVlanMode::L3 => Self::L3,
+            nispor::IpVlanMode::L3S => Self::L3S,
+            m => {
+                log::debug!("Got unknown IPVLAN mode {m:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl From<&nispor::IpVlanInfo> for IpVlanConfig {
+    fn from(np_info: &nispor::IpVlanInfo) -> Self {
+        Self {
+            base_iface: Some(np_info.base_iface.clone()),
+            mode: Some(np_info.mode.into()),
+            private: Some(np_info.flags.contains(&nispor::IpVlanFlag::Private)),
+            vepa: Some(np_info.flags.contains(&nispor::IpVlanFlag::Vepa)),
+        }
+    }
+}
+
+pub(crate) fn apply_ipvlan_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &IpVlanInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(conf) = iface.ipvlan.as_ref() {
+        let Some(base_iface) = conf.base_iface.as_ref() else {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "apply_ipvlan_conf() got IPVLAN without base-iface: \
+                     {iface:?}"
+                ),
+            ));
+        };
+        let mut np_conf = nispor::IpVlanConf::default();
+        np_conf.base_iface = base_iface.to_string();
+        np_conf.mode = match conf.mode {
+            Some(IpVlanMode::L2) => Some(nispor::IpVlanMode::L2),
+            Some(IpVlanMode::L3) => Some(nispor::IpVlanMode::L3),
+            Some(IpVlanMode::L3S) => Some(nispor::IpVlanMode::L3S),
+            Some(IpVlanMode::Unknown) => {
+                return Err(NipartError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "apply_ipvlan_conf() got IPVLAN with unknown mode: \
+                         {iface:?}"
+                    ),
+                ));
+            }
+            None => None,
+        };
+        if conf.private.is_some() || conf.vepa.is_some() {
+            let mut flags = Vec::new();
+            if conf.private == Some(true) {
+                flags.push(nispor::IpVlanFlag::Private);
+            }
+            if conf.vepa == Some(true) {
+                flags.push(nispor::IpVlanFlag::Vepa);
+            }
+            np_conf.flags = Some(flags);
+        }
+        np_iface.ipvlan = Some(np_conf);
+    }
+    Ok(vec![np_iface])
+}
+
+impl IpVlanInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            ipvlan: np_iface.ipvlan.as_ref().map(IpVlanConfig::from),
+        }
+    }
+}
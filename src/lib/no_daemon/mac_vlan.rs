@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, ErrorKind, MacVlanConfig, MacVlanInterface, MacVlanMode,
+    MacVtapInterface, NipartError,
+};
+
+// Kernel `MACVLAN_FLAG_NOPROMISC`
+const MACVLAN_FLAG_NOPROMISC: u16 = 1;
+
+impl From<nispor::MacVlanMode> for MacVlanMode {
+    fn from(v: nispor::MacVlanMode) -> Self {
+        match v {
+            nispor::MacVlanMode::Vepa => Self::Vepa,
+            nispor::MacVlanMode::Bridge => Self::Bridge,
+            nispor::MacVlanMode::Private => Self::Private,
+            nispor::MacVlanMode::PassThrough => Self::Passthru,
+            nispor::MacVlanMode::Source => Self::Source,
+            m => {
+                log::debug!("Got unknown MAC VLAN mode {m:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl From<nispor::MacVtapMode> for MacVlanMode {
+    fn from(v: nispor::MacVtapMode) -> Self {
+        match v {
+            nispor::MacVtapMode::Vepa => Self::Vepa,
+            nispor::MacVtapMode::Bridge => Self::Bridge,
+            nispor::MacVtapMode::Private => Self::Private,
+            nispor::MacVtapMode::PassThrough => Self::Passthru,
+            nispor::MacVtapMode::Source => Self::Source,
+            m => {
+                log::debug!("Got unknown MAC VTAP mode {m:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+fn nipart_mode_to_nispor(
+    mode: MacVlanMode,
+) -> Result<nispor::MacVlanMode, NipartError> {
+    match mode {
+        MacVlanMode::Vepa => Ok(nispor::MacVlanMode::Vepa),
+        MacVlanMode::Bridge => Ok(nispor::MacVlanMode::Bridge),
+        MacVlanMode::Private => Ok(nispor::MacVlanMode::Private),
+        MacVlanMode::Passthru => Ok(nispor::MacVlanMode::PassThrough),
+        MacVlanMode::Source => Ok(nispor::MacVlanMode::Source),
+        MacVlanMode::Unknown => Err(NipartError::new(
+            ErrorKind::Bug,
+            "nipart_mode_to_nispor() got unknown MAC VLAN mode".to_string(),
+        )),
+    }
+}
+
+fn np_flags_to_promiscuous(flags: u16) -> Option<bool> {
+    Some(flags & MACVLAN_FLAG_NOPROMISC == 0)
+}
+
+fn promiscuous_to_np_flags(promiscuous: Option<bool>) -> Option<u16> {
+    promiscuous.map(|p| if p { 0 } else { MACVLAN_FLAG_NOPROMISC })
+}
+
+impl From<&nispor::MacVlanInfo> for MacVlanConfig {
+    fn from(np_info: &nispor::MacVlanInfo) -> Self {
+        Self {
+            base_iface: Some(np_info.base_iface.clone()),
+            mode: Some(np_info.mode.into()),
+            promiscuous: np_flags_to_promiscuous(np_info.flags),
+        }
+    }
+}
+
+impl From<&nispor::MacVtapInfo> for MacVlanConfig {
+    fn from(np_info: &nispor::MacVtapInfo) -> Self {
+        Self {
+            base_iface: Some(np_info.base_iface.clone()),
+            mode: Some(np_info.mode.into()),
+            promiscuous: np_flags_to_promiscuous(np_info.flags),
+        }
+    }
+}
+
+fn check_mac_vlan_conf(
+    conf: &MacVlanConfig,
+    iface_name: &str,
+) -> Result<(String, MacVlanMode), NipartError> {
+    if let (Some(base_iface), Some(mode)) =
+        (conf.base_iface.as_ref(), conf.mode)
+    {
+        Ok((base_iface.to_string(), mode))
+    } else {
+        Err(NipartError::new(
+            ErrorKind::Bug,
+            format!(
+                "Got MAC VLAN/VTAP {iface_name} without base-iface or mode: \
+                 {conf:?}"
+            ),
+        ))
+    }
+}
+
+pub(crate) fn apply_mac_vlan_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &MacVlanInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(conf) = iface.mac_vlan.as_ref() {
+        let (base_iface, mode) = check_mac_vlan_conf(conf, &iface.base.name)?;
+        let mut np_conf = nispor::MacVlanConf::default();
+        np_conf.base_iface = base_iface;
+        np_conf.mode = nipart_mode_to_nispor(mode)?;
+        np_conf.flags = promiscuous_to_np_flags(conf.promiscuous);
+        np_iface.mac_vlan = Some(np_conf);
+    }
+    Ok(vec![np_iface])
+}
+
+pub(crate) fn apply_mac_vtap_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &MacVtapInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(conf) = iface.mac_vtap.as_ref() {
+        let (base_iface, mode) = check_mac_vlan_conf(conf, &iface.base.name)?;
+        let mut np_conf = nispor::MacVtapConf::default();
+        np_conf.base_iface = base_iface;
+        np_conf.mode = nipart_mode_to_nispor(mode)?;
+        np_conf.flags = promiscuous_to_np_flags(conf.promiscuous);
+        np_iface.mac_vtap = Some(np_conf);
+    }
+    Ok(vec![np_iface])
+}
+
+impl MacVlanInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            mac_vlan: np_iface.mac_vlan.as_ref().map(MacVlanConfig::from),
+        }
+    }
+}
+
+impl MacVtapInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            mac_vtap: np_iface.mac_vtap.as_ref().map(MacVlanConfig::from),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, ErrorKind, MacSecCipher, MacSecConfig, MacSecInterface,
+    MacSecValidate, NipartError,
+};
+
+impl From<nispor::MacSecCipherId> for MacSecCipher {
+    fn from(v: nispor::MacSecCipherId) -> Self {
+        match v {
+            nispor::MacSecCipherId::GcmAes128 => Self::GcmAes128,
+            nispor::MacSecCipherId::GcmAes256 => Self::GcmAes256,
+            nispor::MacSecCipherId::GcmAesXpn128 => Self::GcmAesXpn128,
+            nispor::MacSecCipherId::GcmAesXpn256 => Self::GcmAesXpn256,
+            c => {
+                log::debug!("Got unknown MACsec cipher {c:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl From<nispor::MacSecValidate> for MacSecValidate {
+    fn from(v: nispor::MacSecValidate) -> Self {
+        match v {
+            nispor::MacSecValidate::Disabled => Self::Disabled,
+            nispor::MacSecValidate::Check => Self::Check,
+            nispor::MacSecValidate::Strict => Self::Strict,
+            m => {
+                log::debug!("Got unknown MACsec validation mode {m:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl From<&nispor::MacSecInfo> for MacSecConfig {
+    fn from(np_info: &nispor::MacSecInfo) -> Self {
+        Self {
+            base_iface: np_info.base_iface.clone(),
+            sci: Some(np_info.sci),
+            port: Some(np_info.port),
+            cipher: Some(np_info.cipher.into()),
+            encrypt: Some(np_info.encrypt),
+            protect: Some(np_info.protect),
+            validation: Some(np_info.validate.into()),
+            // Kernel does not hold MKA keys
+            mka_cak: None,
+            mka_ckn: None,
+        }
+    }
+}
+
+pub(crate) fn apply_macsec_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &MacSecInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(conf) = iface.macsec.as_ref() {
+        let Some(base_iface) = conf.base_iface.as_ref() else {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "apply_macsec_conf() got MACsec without base-iface: \
+                     {iface:?}"
+                ),
+            ));
+        };
+        let mut np_conf = nispor::MacSecConf::default();
+        np_conf.base_iface = base_iface.to_string();
+        np_conf.sci = conf.sci;
+        np_conf.port = conf.port;
+        np_conf.cipher = match conf.cipher {
+            Some(MacSecCipher::GcmAes128) => {
+                Some(nispor::MacSecCipherId::GcmAes128)
+            }
+            Some(MacSecCipher::GcmAes256) => {
+                Some(nispor::MacSecCipherId::GcmAes256)
+            }
+            Some(MacSecCipher::GcmAesXpn128) => {
+                Some(nispor::MacSecCipherId::GcmAesXpn128)
+            }
+            Some(MacSecCipher::GcmAesXpn256) => {
+                Some(nispor::MacSecCipherId::GcmAesXpn256)
+            }
+            Some(MacSecCipher::Unknown) => {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Unknown cipher for MACsec interface {}",
+                        iface.base.name
+                    ),
+                ));
+            }
+            None => None,
+        };
+        np_conf.encrypt = conf.encrypt;
+        np_conf.protect = conf.protect;
+        np_conf.validate = match conf.validation {
+            Some(MacSecValidate::Disabled) => {
+                Some(nispor::MacSecValidate::Disabled)
+            }
+            Some(MacSecValidate::Check) => Some(nispor::MacSecValidate::Check),
+            Some(MacSecValidate::Strict) => {
+                Some(nispor::MacSecValidate::Strict)
+            }
+            Some(MacSecValidate::Unknown) => {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Unknown validation mode for MACsec interface {}",
+                        iface.base.name
+                    ),
+                ));
+            }
+            None => None,
+        };
+        np_iface.macsec = Some(np_conf);
+    }
+    Ok(vec![np_iface])
+}
+
+impl MacSecInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            macsec: np_iface.macsec.as_ref().map(MacSecConfig::from),
+        }
+    }
+}
@@ -4,16 +4,33 @@ mod apply;
 mod base_iface;
 mod bond;
 mod dhcp;
+mod dhcp_id;
+mod dns;
 mod ethernet;
+mod hostname;
+mod hsr;
 mod iface;
+mod infiniband;
 mod inter_ifaces;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
 mod linux_bridge;
 mod linux_bridge_vlan;
+mod mac_vlan;
+mod macsec;
+mod nexthop;
 mod ovs;
 mod query;
 mod route;
+mod route_metric_policy;
+mod route_rule;
+mod sriov;
+mod tun;
+mod udev;
 mod vlan;
+mod vrf;
+mod vxlan;
 mod watcher;
 mod wifi;
 mod wireguard;
@@ -0,0 +1,485 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, net::IpAddr};
+
+use futures_util::stream::TryStreamExt;
+use rtnetlink::{
+    IpVersion,
+    packet_route::{
+        AddressFamily,
+        nexthop::{
+            NexthopAttribute, NexthopGroup, NexthopGroupType, NexthopMessage,
+            NexthopResilientAttribute,
+        },
+        route::{RouteAddress, RouteAttribute, RouteProtocol},
+    },
+};
+
+use super::{
+    route::{get_iface_index, parse_route_addr},
+    route_rule::parse_ip_net,
+};
+use crate::{
+    ErrorKind, MergedNextHops, NextHopEntry, NextHopGroupMember,
+    NextHopResilient, NextHops, NipartError, NipartIpFamily, NipartRouteFilter,
+    NipartRouteProtocol, RouteEntry, Routes,
+};
+
+const SUPPORTED_NEXTHOP_PROTOCOL: [RouteProtocol; 3] = [
+    RouteProtocol::Unspec,
+    RouteProtocol::Boot,
+    RouteProtocol::Static,
+];
+
+// Should be identical to protocols of routes queried by `get_routes()`
+const SUPPORTED_ROUTE_PROTOCOL: [RouteProtocol; 7] = [
+    RouteProtocol::Boot,
+    RouteProtocol::Static,
+    RouteProtocol::Ra,
+    RouteProtocol::Dhcp,
+    RouteProtocol::Mrouted,
+    RouteProtocol::KeepAlived,
+    RouteProtocol::Babel,
+];
+
+const SUPPORTED_STATIC_ROUTE_PROTOCOL: [RouteProtocol; 2] =
+    [RouteProtocol::Boot, RouteProtocol::Static];
+
+// Kernel use clock_t for resilient nexthop group timers
+const USER_HZ: u32 = 100;
+const RT_TABLE_MAIN: u32 = 254;
+
+pub(crate) async fn get_nexthops(
+    np_ifaces: &HashMap<String, nispor::Iface>,
+) -> NextHops {
+    match query_nexthops(np_ifaces).await {
+        Ok(nexthops) => NextHops {
+            config: Some(nexthops),
+        },
+        Err(e) => {
+            log::warn!("Failed to retrieve nexthop objects via rtnetlink: {e}");
+            NextHops::default()
+        }
+    }
+}
+
+async fn query_nexthops(
+    np_ifaces: &HashMap<String, nispor::Iface>,
+) -> Result<Vec<NextHopEntry>, NipartError> {
+    let index_to_name: HashMap<u32, &str> = np_ifaces
+        .values()
+        .map(|i| (i.index, i.name.as_str()))
+        .collect();
+
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+
+    let mut ret = Vec::new();
+    let mut nh_msgs = handle.nexthop().get().execute();
+    while let Some(nh_msg) = nh_msgs.try_next().await.map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to query nexthop objects via rtnetlink: {e}"),
+        )
+    })? {
+        if !SUPPORTED_NEXTHOP_PROTOCOL.contains(&nh_msg.header.protocol) {
+            continue;
+        }
+        ret.push(nh_msg_to_nipart(&nh_msg, &index_to_name));
+    }
+    ret.sort_unstable_by_key(|nh| nh.id);
+    Ok(ret)
+}
+
+fn nh_msg_to_nipart(
+    nh_msg: &NexthopMessage,
+    index_to_name: &HashMap<u32, &str>,
+) -> NextHopEntry {
+    let mut ret = NextHopEntry::default();
+    let mut group_type = NexthopGroupType::Mpath;
+    let mut resilient = NextHopResilient::default();
+    for attr in nh_msg.attributes.iter() {
+        match attr {
+            NexthopAttribute::Id(id) => ret.id = Some(*id),
+            NexthopAttribute::Blackhole => ret.blackhole = Some(true),
+            NexthopAttribute::Oif(index) => {
+                ret.device = index_to_name.get(index).map(|n| n.to_string());
+            }
+            NexthopAttribute::Gateway(RouteAddress::Inet(ip)) => {
+                ret.gateway = Some(ip.to_string());
+            }
+            NexthopAttribute::Gateway(RouteAddress::Inet6(ip)) => {
+                ret.gateway = Some(ip.to_string());
+            }
+            NexthopAttribute::Group(members) => {
+                let mut members: Vec<NextHopGroupMember> = members
+                    .iter()
+                    .map(|m| NextHopGroupMember {
+                        id: m.id,
+                        weight: Some(u16::from(m.weight) + 1),
+                    })
+                    .collect();
+                members.sort_unstable();
+                ret.group = Some(members);
+            }
+            NexthopAttribute::GroupType(t) => group_type = *t,
+            NexthopAttribute::ResGroup(res_attrs) => {
+                for res_attr in res_attrs {
+                    match res_attr {
+                        NexthopResilientAttribute::Buckets(v) => {
+                            resilient.buckets = Some(*v);
+                        }
+                        NexthopResilientAttribute::IdleTimer(v) => {
+                            resilient.idle_timer = Some(*v / USER_HZ);
+                        }
+                        NexthopResilientAttribute::UnbalancedTimer(v) => {
+                            resilient.unbalanced_timer = Some(*v / USER_HZ);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    if group_type == NexthopGroupType::Resilient {
+        ret.resilient = Some(resilient);
+    }
+    if ret.group.is_none() {
+        ret.family = match nh_msg.header.address_family {
+            AddressFamily::Inet => Some(NipartIpFamily::Ipv4),
+            AddressFamily::Inet6 => Some(NipartIpFamily::Ipv6),
+            _ => None,
+        };
+    }
+    ret
+}
+
+pub(crate) async fn apply_nexthops(
+    merged_nexthops: &MergedNextHops,
+) -> Result<(), NipartError> {
+    if !merged_nexthops.is_changed() {
+        log::debug!("Nexthop object is not changed");
+        return Ok(());
+    }
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+
+    // Remove nexthop groups before their members
+    let mut absent_nhs: Vec<&NextHopEntry> = merged_nexthops
+        .changed_nexthops
+        .iter()
+        .filter(|nh| nh.is_absent())
+        .collect();
+    absent_nhs.sort_by_key(|nh| !nh.is_group());
+    for nh in absent_nhs {
+        let Some(id) = nh.id else {
+            continue;
+        };
+        log::debug!("Removing nexthop object {nh}");
+        let mut nh_msg = NexthopMessage::default();
+        nh_msg.attributes.push(NexthopAttribute::Id(id));
+        handle.nexthop().del(nh_msg).execute().await.map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to remove nexthop object {id}: {e}"),
+            )
+        })?;
+    }
+
+    // Create nexthop group after their members
+    let mut new_nhs: Vec<&NextHopEntry> = merged_nexthops
+        .changed_nexthops
+        .iter()
+        .filter(|nh| !nh.is_absent())
+        .collect();
+    new_nhs.sort_by_key(|nh| nh.is_group());
+    for nh in new_nhs {
+        log::debug!("Setting nexthop object {nh}");
+        let nh_msg = nipart_nh_to_nh_msg(&handle, nh).await?;
+        handle
+            .nexthop()
+            .add(nh_msg)
+            .replace()
+            .execute()
+            .await
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to set nexthop object {nh}: {e}"),
+                )
+            })?;
+    }
+    Ok(())
+}
+
+async fn nipart_nh_to_nh_msg(
+    handle: &rtnetlink::Handle,
+    nh: &NextHopEntry,
+) -> Result<NexthopMessage, NipartError> {
+    let mut nh_msg = NexthopMessage::default();
+    nh_msg.header.protocol = RouteProtocol::Static;
+    if let Some(id) = nh.id {
+        nh_msg.attributes.push(NexthopAttribute::Id(id));
+    }
+    if nh.is_blackhole() {
+        nh_msg.attributes.push(NexthopAttribute::Blackhole);
+    } else if let Some(members) = nh.group.as_deref() {
+        let mut nh_groups = Vec::new();
+        for member in members {
+            let mut nh_group = NexthopGroup::default();
+            nh_group.id = member.id;
+            nh_group.weight = member
+                .weight
+                .and_then(|w| u8::try_from(w.saturating_sub(1)).ok())
+                .unwrap_or_default();
+            nh_groups.push(nh_group);
+        }
+        nh_msg.attributes.push(NexthopAttribute::Group(nh_groups));
+        if let Some(resilient) = nh.resilient.as_ref() {
+            nh_msg
+                .attributes
+                .push(NexthopAttribute::GroupType(NexthopGroupType::Resilient));
+            let mut res_attrs = Vec::new();
+            if let Some(v) = resilient.buckets {
+                res_attrs.push(NexthopResilientAttribute::Buckets(v));
+            }
+            if let Some(v) = resilient.idle_timer {
+                res_attrs.push(NexthopResilientAttribute::IdleTimer(
+                    v.saturating_mul(USER_HZ),
+                ));
+            }
+            if let Some(v) = resilient.unbalanced_timer {
+                res_attrs.push(NexthopResilientAttribute::UnbalancedTimer(
+                    v.saturating_mul(USER_HZ),
+                ));
+            }
+            nh_msg
+                .attributes
+                .push(NexthopAttribute::ResGroup(res_attrs));
+        }
+    } else {
+        if let Some(iface_name) = nh.device.as_deref() {
+            nh_msg.attributes.push(NexthopAttribute::Oif(
+                get_iface_index(handle, iface_name).await?,
+            ));
+        }
+        if let Some(gateway) = nh.gateway.as_deref() {
+            nh_msg
+                .attributes
+                .push(NexthopAttribute::Gateway(parse_route_addr(gateway)?));
+        }
+    }
+    // Kernel requires IPv4 or IPv6 family for non-group nexthop object,
+    // sanitize has set family to the one of gateway or IPv4 if undefined.
+    if !nh.is_group() {
+        nh_msg.header.address_family = match nh.family {
+            Some(NipartIpFamily::Ipv6) => AddressFamily::Inet6,
+            _ => AddressFamily::Inet,
+        };
+    }
+    Ok(nh_msg)
+}
+
+// Kernel is expanding nexthop object into legacy next hop information of
+// route for backwards compatibility, so nispor is reporting these routes as
+// normal routes. Replace them with routes referring nexthop object.
+// The route filter is applied the same way as `get_routes()`, so routes
+// excluded by filter are not introduced back.
+pub(crate) async fn fill_nexthop_routes(
+    routes: &mut Routes,
+    np_ifaces: &HashMap<String, nispor::Iface>,
+    rt_filter: Option<&NipartRouteFilter>,
+) {
+    let nh_routes = match query_nexthop_routes(np_ifaces, rt_filter).await {
+        Ok(r) => r,
+        Err(e) => {
+            log::warn!(
+                "Failed to retrieve routes referring nexthop object via \
+                 rtnetlink: {e}"
+            );
+            return;
+        }
+    };
+    if nh_routes.is_empty() {
+        return;
+    }
+    let is_nh_route = |rt: &RouteEntry| {
+        rt.nexthop_id.is_none()
+            && nh_routes.iter().any(|nh_rt| nh_rt.is_expanded_from(rt))
+    };
+    if let Some(rts) = routes.running.as_mut() {
+        rts.retain(|rt| !is_nh_route(rt));
+        rts.extend(nh_routes.iter().map(|nh_rt| nh_rt.route.clone()));
+    }
+    if let Some(rts) = routes.config.as_mut() {
+        rts.retain(|rt| !is_nh_route(rt));
+        rts.extend(
+            nh_routes
+                .iter()
+                .filter(|nh_rt| nh_rt.is_static)
+                .map(|nh_rt| nh_rt.route.clone()),
+        );
+    }
+}
+
+// Route referring nexthop object along with the outgoing interfaces of
+// legacy next hop information expanded by kernel.
+#[derive(Debug)]
+struct NexthopRoute {
+    route: RouteEntry,
+    is_static: bool,
+    key: NexthopRouteKey,
+    oifs: Vec<String>,
+}
+
+// Normalized route table ID, destination and metric
+type NexthopRouteKey = (u32, Option<(IpAddr, u8)>, i64);
+
+fn nexthop_route_key(rt: &RouteEntry) -> NexthopRouteKey {
+    (
+        rt.table_id.filter(|t| *t != 0).unwrap_or(RT_TABLE_MAIN),
+        rt.destination.as_deref().and_then(|d| parse_ip_net(d).ok()),
+        rt.metric.unwrap_or_default(),
+    )
+}
+
+impl NexthopRoute {
+    // Whether specified route reported by nispor is the legacy next hop
+    // information expanded from this route.
+    fn is_expanded_from(&self, rt: &RouteEntry) -> bool {
+        self.key == nexthop_route_key(rt)
+            && rt
+                .next_hop_iface
+                .as_ref()
+                .is_some_and(|iface| self.oifs.contains(iface))
+    }
+}
+
+async fn query_nexthop_routes(
+    np_ifaces: &HashMap<String, nispor::Iface>,
+    rt_filter: Option<&NipartRouteFilter>,
+) -> Result<Vec<NexthopRoute>, NipartError> {
+    let index_to_name: HashMap<u32, &str> = np_ifaces
+        .values()
+        .map(|i| (i.index, i.name.as_str()))
+        .collect();
+    let mut protocols: Vec<RouteProtocol> =
+        match rt_filter.and_then(|f| f.protocols.as_deref()) {
+            Some(protocols) => protocols
+                .iter()
+                .map(|p| nipart_to_nl_route_protocol(*p))
+                .collect(),
+            None => SUPPORTED_ROUTE_PROTOCOL.to_vec(),
+        };
+    if rt_filter.is_some_and(|f| f.skip_running) {
+        protocols.retain(|p| SUPPORTED_STATIC_ROUTE_PROTOCOL.contains(p));
+    }
+    let tables = rt_filter.and_then(|f| f.tables.as_deref());
+    let families = rt_filter.and_then(|f| f.families.as_deref());
+
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+
+    let mut ret = Vec::new();
+    for (ip_version, family) in [
+        (IpVersion::V4, NipartIpFamily::Ipv4),
+        (IpVersion::V6, NipartIpFamily::Ipv6),
+    ] {
+        if families.is_some_and(|f| !f.contains(&family)) {
+            continue;
+        }
+        let mut rt_msgs = handle.route().get(ip_version).execute();
+        while let Some(rt_msg) = rt_msgs.try_next().await.map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to query routes via rtnetlink: {e}"),
+            )
+        })? {
+            if !protocols.contains(&rt_msg.header.protocol) {
+                continue;
+            }
+            let mut nexthop_id = None;
+            let mut dst = None;
+            let mut table_id = u32::from(rt_msg.header.table);
+            let mut metric = None;
+            let mut oif_indexes = Vec::new();
+            for attr in rt_msg.attributes.iter() {
+                match attr {
+                    RouteAttribute::NhId(id) => nexthop_id = Some(*id),
+                    RouteAttribute::Destination(RouteAddress::Inet(ip)) => {
+                        dst = Some(ip.to_string())
+                    }
+                    RouteAttribute::Destination(RouteAddress::Inet6(ip)) => {
+                        dst = Some(ip.to_string())
+                    }
+                    RouteAttribute::Table(t) => table_id = *t,
+                    RouteAttribute::Priority(p) => metric = Some(*p),
+                    RouteAttribute::Oif(i) => oif_indexes.push(*i),
+                    RouteAttribute::MultiPath(hops) => oif_indexes
+                        .extend(hops.iter().map(|h| h.interface_index)),
+                    _ => (),
+                }
+            }
+            let Some(nexthop_id) = nexthop_id else {
+                continue;
+            };
+            if tables.is_some_and(|t| !t.contains(&table_id)) {
+                continue;
+            }
+            let is_ipv6 = rt_msg.header.address_family == AddressFamily::Inet6;
+            let dst = dst.unwrap_or_else(|| {
+                if is_ipv6 { "::" } else { "0.0.0.0" }.to_string()
+            });
+            let route = RouteEntry {
+                destination: Some(format!(
+                    "{dst}/{}",
+                    rt_msg.header.destination_prefix_length
+                )),
+                table_id: Some(table_id),
+                metric: metric.map(i64::from),
+                nexthop_id: Some(nexthop_id),
+                ..Default::default()
+            };
+            ret.push(NexthopRoute {
+                key: nexthop_route_key(&route),
+                route,
+                is_static: SUPPORTED_STATIC_ROUTE_PROTOCOL
+                    .contains(&rt_msg.header.protocol),
+                oifs: oif_indexes
+                    .iter()
+                    .filter_map(|i| index_to_name.get(i))
+                    .map(|n| n.to_string())
+                    .collect(),
+            });
+        }
+    }
+    Ok(ret)
+}
+
+fn nipart_to_nl_route_protocol(protocol: NipartRouteProtocol) -> RouteProtocol {
+    match protocol {
+        NipartRouteProtocol::Boot => RouteProtocol::Boot,
+        NipartRouteProtocol::Static => RouteProtocol::Static,
+        NipartRouteProtocol::Ra => RouteProtocol::Ra,
+        NipartRouteProtocol::Dhcp => RouteProtocol::Dhcp,
+        NipartRouteProtocol::Mrouted => RouteProtocol::Mrouted,
+        NipartRouteProtocol::KeepAlived => RouteProtocol::KeepAlived,
+        NipartRouteProtocol::Babel => RouteProtocol::Babel,
+    }
+}
@@ -0,0 +1,369 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::{
+    NipartOvsDb,
+    db::{OVS_DB_NAME, OvsDbCondition, OvsDbConnection, OvsDbEntry},
+    method::OvsDbMethodTransact,
+    operation::{
+        OvsDbDelete, OvsDbInsert, OvsDbMutate, OvsDbMutation, OvsDbOperation,
+        OvsDbUpdate,
+    },
+    query::ovsdb_is_running,
+};
+use crate::{
+    ErrorKind, Interface, InterfaceType, MergedInterfaces, NipartError,
+    NipartInterface, OvsBridgeInterface,
+};
+
+const OVS_IFACE_CREATION_RETRY: usize = 50;
+const OVS_IFACE_CREATION_RETRY_INTERVAL_MS: u64 = 100;
+
+impl NipartOvsDb {
+    /// Apply changes of OVS bridges and OVS internal interfaces via OVSDB in
+    /// single transaction.
+    pub(crate) async fn apply(
+        ovs_ifaces: &[&Interface],
+        merged_ifaces: &MergedInterfaces,
+    ) -> Result<(), NipartError> {
+        if !ovsdb_is_running().await {
+            return Err(NipartError::new(
+                ErrorKind::PluginFailure,
+                "OVS daemon is not running, cannot apply OVS configuration"
+                    .to_string(),
+            ));
+        }
+        let mut cli = OvsDbConnection::new().await?;
+        let cur_brs = cli.get_ovs_bridges().await?;
+        let cur_ports = cli.get_ovs_ports().await?;
+        let cur_ifaces = cli.get_ovs_ifaces().await?;
+
+        let cur_state = OvsDbCurrent::new(&cur_brs, &cur_ports, &cur_ifaces);
+
+        // Bridges which get full port list overridden in this transaction
+        let changed_br_names: HashSet<&str> = ovs_ifaces
+            .iter()
+            .filter(|i| i.iface_type() == &InterfaceType::OvsBridge)
+            .map(|i| i.name())
+            .collect();
+
+        let mut operations: Vec<OvsDbOperation> = Vec::new();
+        let mut uuid_name_gen = OvsDbUuidNameGen::default();
+
+        for iface in ovs_ifaces {
+            match iface {
+                Interface::OvsBridge(br_iface) => {
+                    if br_iface.is_absent() {
+                        operations.extend(gen_bridge_delete_ops(
+                            br_iface.name(),
+                            &cur_state,
+                        ));
+                    } else {
+                        operations.extend(gen_bridge_ops(
+                            br_iface,
+                            &cur_state,
+                            &changed_br_names,
+                            merged_ifaces,
+                            &mut uuid_name_gen,
+                        ));
+                    }
+                }
+                Interface::OvsInterface(ovs_iface) => {
+                    // OVS internal interface attached to changed bridges are
+                    // handled by bridge port list already.
+                    if ovs_iface.is_absent()
+                        && let Some((br_name, port_uuid)) =
+                            cur_state.iface_to_br_port.get(ovs_iface.name())
+                        && !changed_br_names.contains(br_name.as_str())
+                    {
+                        operations.push(gen_port_detach_op(br_name, port_uuid));
+                    }
+                }
+                _ => {
+                    log::warn!(
+                        "BUG: NipartOvsDb::apply() got non-OVS interface {}/{}",
+                        iface.name(),
+                        iface.iface_type()
+                    );
+                }
+            }
+        }
+
+        if operations.is_empty() {
+            return Ok(());
+        }
+
+        let transact = OvsDbMethodTransact {
+            db_name: OVS_DB_NAME.to_string(),
+            operations,
+        };
+        log::trace!("Pending OVSDB transaction {transact:?}");
+        cli.transact(&transact).await?;
+
+        let new_internal_ifaces: Vec<&str> = ovs_ifaces
+            .iter()
+            .filter(|i| {
+                i.iface_type() == &InterfaceType::OvsInterface && i.is_up()
+            })
+            .filter(|i| {
+                merged_ifaces
+                    .kernel_ifaces
+                    .get(i.name())
+                    .map(|m| m.current.is_none())
+                    .unwrap_or_default()
+            })
+            .map(|i| i.name())
+            .collect();
+        if !new_internal_ifaces.is_empty() {
+            wait_ovs_ifaces_created(new_internal_ifaces.as_slice()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct OvsDbUuidNameGen {
+    index: u32,
+}
+
+impl OvsDbUuidNameGen {
+    // The OVSDB `uuid-name` should match `[a-zA-Z_][a-zA-Z0-9_]*`, so we
+    // cannot use interface name which might contain `-` or `.`.
+    fn next(&mut self, prefix: &str) -> String {
+        self.index += 1;
+        format!("{prefix}{}", self.index)
+    }
+}
+
+#[derive(Debug, Default)]
+struct OvsDbCurrent<'a> {
+    /// Bridge name to bridge entry
+    brs: HashMap<&'a str, &'a OvsDbEntry>,
+    /// Interface name to `(bridge name, port UUID)`
+    iface_to_br_port: HashMap<String, (String, String)>,
+}
+
+impl<'a> OvsDbCurrent<'a> {
+    fn new(
+        cur_brs: &'a HashMap<String, OvsDbEntry>,
+        cur_ports: &'a HashMap<String, OvsDbEntry>,
+        cur_ifaces: &'a HashMap<String, OvsDbEntry>,
+    ) -> Self {
+        let mut ret = Self::default();
+        for cur_br in cur_brs.values() {
+            ret.brs.insert(cur_br.name.as_str(), cur_br);
+            for port_uuid in cur_br.ports.as_slice() {
+                let Some(cur_port) = cur_ports.get(port_uuid) else {
+                    continue;
+                };
+                for iface_uuid in cur_port.ports.as_slice() {
+                    if let Some(cur_iface) = cur_ifaces.get(iface_uuid) {
+                        ret.iface_to_br_port.insert(
+                            cur_iface.name.to_string(),
+                            (cur_br.name.to_string(), port_uuid.to_string()),
+                        );
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+fn uuid_value(uuid: &str) -> Value {
+    Value::Array(vec![
+        Value::String("uuid".to_string()),
+        Value::String(uuid.to_string()),
+    ])
+}
+
+fn named_uuid_value(uuid_name: &str) -> Value {
+    Value::Array(vec![
+        Value::String("named-uuid".to_string()),
+        Value::String(uuid_name.to_string()),
+    ])
+}
+
+fn set_value(values: Vec<Value>) -> Value {
+    Value::Array(vec![Value::String("set".to_string()), Value::Array(values)])
+}
+
+fn name_condition(name: &str) -> OvsDbCondition {
+    OvsDbCondition::new("name", "==", Value::String(name.to_string()))
+}
+
+fn gen_bridge_delete_ops(
+    br_name: &str,
+    cur_state: &OvsDbCurrent,
+) -> Vec<OvsDbOperation> {
+    let Some(cur_br) = cur_state.brs.get(br_name) else {
+        log::debug!("OVS bridge {br_name} does not exist in OVSDB, no delete");
+        return Vec::new();
+    };
+    // Port and Interface rows are garbage collected by OVSDB once no longer
+    // referred.
+    vec![
+        OvsDbOperation::Mutate(OvsDbMutate {
+            table: "Open_vSwitch".to_string(),
+            conditions: Vec::new(),
+            mutations: vec![OvsDbMutation {
+                column: "bridges".to_string(),
+                mutator: "delete".to_string(),
+                value: uuid_value(&cur_br.uuid),
+            }],
+        }),
+        OvsDbOperation::Delete(OvsDbDelete {
+            table: "Bridge".to_string(),
+            conditions: vec![name_condition(br_name)],
+        }),
+    ]
+}
+
+fn gen_port_detach_op(br_name: &str, port_uuid: &str) -> OvsDbOperation {
+    OvsDbOperation::Mutate(OvsDbMutate {
+        table: "Bridge".to_string(),
+        conditions: vec![name_condition(br_name)],
+        mutations: vec![OvsDbMutation {
+            column: "ports".to_string(),
+            mutator: "delete".to_string(),
+            value: uuid_value(port_uuid),
+        }],
+    })
+}
+
+fn gen_bridge_ops(
+    br_iface: &OvsBridgeInterface,
+    cur_state: &OvsDbCurrent,
+    changed_br_names: &HashSet<&str>,
+    merged_ifaces: &MergedInterfaces,
+    uuid_name_gen: &mut OvsDbUuidNameGen,
+) -> Vec<OvsDbOperation> {
+    let mut ret = Vec::new();
+    let br_name = br_iface.name();
+    let cur_br = cur_state.brs.get(br_name);
+
+    let port_names = match (br_iface.ports(), cur_br) {
+        (Some(p), _) => p,
+        // No port changes
+        (None, Some(_)) => return ret,
+        (None, None) => Vec::new(),
+    };
+
+    let mut port_refs: Vec<Value> = Vec::new();
+    for port_name in port_names {
+        if let Some((cur_br_name, cur_port_uuid)) =
+            cur_state.iface_to_br_port.get(port_name)
+        {
+            if cur_br_name == br_name {
+                port_refs.push(uuid_value(cur_port_uuid));
+                continue;
+            } else if !changed_br_names.contains(cur_br_name.as_str()) {
+                // Moving port from other OVS bridge
+                ret.push(gen_port_detach_op(cur_br_name, cur_port_uuid));
+            }
+        }
+        let ovs_iface_type = if merged_ifaces
+            .kernel_ifaces
+            .get(port_name)
+            .map(|m| m.merged.iface_type() == &InterfaceType::OvsInterface)
+            .unwrap_or_default()
+        {
+            "internal"
+        } else {
+            ""
+        };
+
+        let iface_uuid_name = uuid_name_gen.next("iface");
+        let port_uuid_name = uuid_name_gen.next("port");
+
+        let mut iface_row: HashMap<String, Value> = HashMap::new();
+        iface_row
+            .insert("name".to_string(), Value::String(port_name.to_string()));
+        iface_row.insert(
+            "type".to_string(),
+            Value::String(ovs_iface_type.to_string()),
+        );
+        ret.push(OvsDbOperation::Insert(OvsDbInsert {
+            table: "Interface".to_string(),
+            row: iface_row,
+            uuid_name: Some(iface_uuid_name.clone()),
+        }));
+
+        let mut port_row: HashMap<String, Value> = HashMap::new();
+        port_row
+            .insert("name".to_string(), Value::String(port_name.to_string()));
+        port_row.insert(
+            "interfaces".to_string(),
+            named_uuid_value(&iface_uuid_name),
+        );
+        ret.push(OvsDbOperation::Insert(OvsDbInsert {
+            table: "Port".to_string(),
+            row: port_row,
+            uuid_name: Some(port_uuid_name.clone()),
+        }));
+        port_refs.push(named_uuid_value(&port_uuid_name));
+    }
+
+    let mut br_row: HashMap<String, Value> = HashMap::new();
+    br_row.insert("ports".to_string(), set_value(port_refs));
+
+    if cur_br.is_some() {
+        ret.push(OvsDbOperation::Update(OvsDbUpdate {
+            table: "Bridge".to_string(),
+            conditions: vec![name_condition(br_name)],
+            row: br_row,
+        }));
+    } else {
+        let br_uuid_name = uuid_name_gen.next("bridge");
+        br_row.insert("name".to_string(), Value::String(br_name.to_string()));
+        ret.push(OvsDbOperation::Insert(OvsDbInsert {
+            table: "Bridge".to_string(),
+            row: br_row,
+            uuid_name: Some(br_uuid_name.clone()),
+        }));
+        ret.push(OvsDbOperation::Mutate(OvsDbMutate {
+            table: "Open_vSwitch".to_string(),
+            conditions: Vec::new(),
+            mutations: vec![OvsDbMutation {
+                column: "bridges".to_string(),
+                mutator: "insert".to_string(),
+                value: set_value(vec![named_uuid_value(&br_uuid_name)]),
+            }],
+        }));
+    }
+    ret
+}
+
+// The ovs-vswitchd creates OVS internal interface asynchronously after OVSDB
+// transaction finished, wait them to show up in kernel before we apply link
+// and IP changes to them.
+async fn wait_ovs_ifaces_created(
+    iface_names: &[&str],
+) -> Result<(), NipartError> {
+    let mut filter = nispor::NetStateFilter::minimum();
+    filter.iface = Some(nispor::NetStateIfaceFilter::minimum());
+    for _ in 0..OVS_IFACE_CREATION_RETRY {
+        let np_state =
+            nispor::NetState::retrieve_with_filter_async(&filter).await?;
+        if iface_names
+            .iter()
+            .all(|iface_name| np_state.ifaces.contains_key(*iface_name))
+        {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(
+            OVS_IFACE_CREATION_RETRY_INTERVAL_MS,
+        ))
+        .await;
+    }
+    Err(NipartError::new(
+        ErrorKind::Timeout,
+        format!(
+            "Timeout on waiting OVS internal interfaces {} to be created",
+            iface_names.join(", ")
+        ),
+    ))
+}
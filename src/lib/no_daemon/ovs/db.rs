@@ -29,6 +29,14 @@ pub(crate) struct OvsDbCondition {
 }
 
 impl OvsDbCondition {
+    pub(crate) fn new(column: &str, function: &str, value: Value) -> Self {
+        Self {
+            column: column.to_string(),
+            function: function.to_string(),
+            value,
+        }
+    }
+
     pub(crate) fn to_value(&self) -> Value {
         Value::Array(vec![
             Value::String(self.column.to_string()),
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod apply;
 mod db;
 mod json_rpc;
 mod method;
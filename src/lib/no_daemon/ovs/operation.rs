@@ -15,16 +15,20 @@ use super::db::OvsDbCondition;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum OvsDbOperation {
     Select(OvsDbSelect),
-    _Update(OvsDbUpdate),
-    _Mutate(OvsDbMutate),
+    Insert(OvsDbInsert),
+    Update(OvsDbUpdate),
+    Mutate(OvsDbMutate),
+    Delete(OvsDbDelete),
 }
 
 impl OvsDbOperation {
     pub(crate) fn to_value(&self) -> Value {
         match self {
             Self::Select(s) => s.to_value(),
-            Self::_Update(s) => s.to_value(),
-            Self::_Mutate(s) => s.to_value(),
+            Self::Insert(s) => s.to_value(),
+            Self::Update(s) => s.to_value(),
+            Self::Mutate(s) => s.to_value(),
+            Self::Delete(s) => s.to_value(),
         }
     }
 }
@@ -60,6 +64,35 @@ impl OvsDbSelect {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbInsert {
+    pub(crate) table: String,
+    pub(crate) row: HashMap<String, Value>,
+    /// Name referring the UUID of inserted row in other operations of the
+    /// same transaction.
+    pub(crate) uuid_name: Option<String>,
+}
+
+impl OvsDbInsert {
+    pub(crate) fn to_value(&self) -> Value {
+        let mut ret = Map::new();
+        ret.insert("op".to_string(), Value::String("insert".to_string()));
+        ret.insert("table".to_string(), Value::String(self.table.clone()));
+        let mut row_map = Map::new();
+        for (k, v) in self.row.iter() {
+            row_map.insert(k.to_string(), v.clone());
+        }
+        ret.insert("row".to_string(), Value::Object(row_map));
+        if let Some(uuid_name) = self.uuid_name.as_ref() {
+            ret.insert(
+                "uuid-name".to_string(),
+                Value::String(uuid_name.to_string()),
+            );
+        }
+        Value::Object(ret)
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct OvsDbUpdate {
     pub(crate) table: String,
@@ -126,3 +159,21 @@ impl OvsDbMutate {
         Value::Object(ret)
     }
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbDelete {
+    pub(crate) table: String,
+    pub(crate) conditions: Vec<OvsDbCondition>,
+}
+
+impl OvsDbDelete {
+    pub(crate) fn to_value(&self) -> Value {
+        let mut ret = Map::new();
+        ret.insert("op".to_string(), Value::String("delete".to_string()));
+        ret.insert("table".to_string(), Value::String(self.table.clone()));
+        let condition_values: Vec<Value> =
+            self.conditions.iter().map(|c| c.to_value()).collect();
+        ret.insert("where".to_string(), Value::Array(condition_values));
+        Value::Object(ret)
+    }
+}
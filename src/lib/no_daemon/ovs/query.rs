@@ -67,7 +67,7 @@ impl NipartOvsDb {
     }
 }
 
-async fn ovsdb_is_running() -> bool {
+pub(crate) async fn ovsdb_is_running() -> bool {
     if let Ok(mut cli) = OvsDbConnection::new().await {
         cli.check_connection().await
     } else {
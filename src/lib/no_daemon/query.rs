@@ -1,14 +1,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    base_iface::np_iface_to_base_iface, ovs::NipartOvsDb, route::get_routes,
+    base_iface::np_iface_to_base_iface,
+    dns::get_dns,
+    hostname::get_hostname,
+    hsr::set_hsr_ports_controller,
+    ip::{NipartIpAddrOrigins, fill_ipv6_tokens},
+    nexthop::{fill_nexthop_routes, get_nexthops},
+    ovs::NipartOvsDb,
+    route::{filter_routes, get_routes, truncate_routes},
+    route_metric_policy::get_route_metric_policy,
+    route_rule::get_route_rules,
     wifi::NipartWpaConn,
 };
 use crate::{
-    BondInterface, DummyInterface, ErrorKind, EthernetInterface, Interface,
-    InterfaceType, LinuxBridgeInterface, LoopbackInterface, NetworkState,
-    NipartError, NipartInterface, NipartNoDaemon, NipartQueryOption,
-    UnknownInterface, VlanInterface, WifiPhyInterface, WireguardInterface,
+    BondInterface, DummyInterface, ErrorKind, EthernetInterface, HsrInterface,
+    InfiniBandInterface, Interface, InterfaceType, IpTunnelInterface,
+    IpVlanInterface, LinuxBridgeInterface, LoopbackInterface, MacSecInterface,
+    MacVlanInterface, MacVtapInterface, NetworkState, NipartError,
+    NipartInterface, NipartNoDaemon, NipartQueryOption, OvsInterface,
+    TunInterface, UnknownInterface, VlanInterface, VrfInterface,
+    VxlanInterface, WifiPhyInterface, WireguardInterface,
 };
 
 impl NipartNoDaemon {
@@ -33,6 +45,7 @@ impl NipartNoDaemon {
         filter.route = None;
         let np_state =
             nispor::NetState::retrieve_with_filter_async(&filter).await?;
+        let addr_origins = NipartIpAddrOrigins::retrieve().await?;
 
         let mut has_wifi_nic = false;
         let mut has_ovs_datapath_nic = false;
@@ -52,7 +65,7 @@ impl NipartNoDaemon {
                 continue;
             }
 
-            let base_iface = np_iface_to_base_iface(np_iface);
+            let base_iface = np_iface_to_base_iface(np_iface, &addr_origins);
             let iface = match &base_iface.iface_type {
                 InterfaceType::Ethernet | InterfaceType::Veth => {
                     Interface::Ethernet(Box::new(
@@ -110,6 +123,44 @@ impl NipartNoDaemon {
                     );
                     Interface::Wireguard(Box::new(wg_iface))
                 }
+                InterfaceType::OvsInterface => Interface::OvsInterface(
+                    Box::new(OvsInterface::new(base_iface)),
+                ),
+                InterfaceType::Vrf => Interface::Vrf(Box::new(
+                    VrfInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::Vxlan => Interface::Vxlan(Box::new(
+                    VxlanInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::MacVlan => Interface::MacVlan(Box::new(
+                    MacVlanInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::IpVlan => Interface::IpVlan(Box::new(
+                    IpVlanInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::MacVtap => Interface::MacVtap(Box::new(
+                    MacVtapInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::MacSec => Interface::MacSec(Box::new(
+                    MacSecInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::Hsr => Interface::Hsr(Box::new(
+                    HsrInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::InfiniBand => Interface::InfiniBand(Box::new(
+                    InfiniBandInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::Tun => Interface::Tun(Box::new(
+                    TunInterface::new_from_nispor(base_iface, np_iface),
+                )),
+                InterfaceType::Gre
+                | InterfaceType::GreTap
+                | InterfaceType::Ip6Gre
+                | InterfaceType::Ipip
+                | InterfaceType::Sit
+                | InterfaceType::Ip6Tnl => Interface::IpTunnel(Box::new(
+                    IpTunnelInterface::new_from_nispor(base_iface, np_iface),
+                )),
                 _ => {
                     log::trace!(
                         "Got unsupported interface {} type {:?}",
@@ -124,6 +175,9 @@ impl NipartNoDaemon {
             net_state.ifaces.push(iface);
         }
 
+        set_hsr_ports_controller(&mut net_state.ifaces);
+        fill_ipv6_tokens(&mut net_state.ifaces).await?;
+
         if has_wifi_nic {
             NipartWpaConn::fill_wifi_cfg(&mut net_state.ifaces).await?;
         }
@@ -132,11 +186,32 @@ impl NipartNoDaemon {
             NipartOvsDb::fill_ovs_cfg(&mut net_state).await?;
         }
 
-        net_state.routes = get_routes(&net_state.ifaces).await;
+        net_state.routes =
+            get_routes(&net_state.ifaces, option.route_filter.as_ref()).await;
+        if let Some(rt_filter) = option.route_filter.as_ref() {
+            filter_routes(&mut net_state.routes, rt_filter);
+        }
+        net_state.nexthops = get_nexthops(&np_state.ifaces).await;
+        if !net_state.nexthops.is_empty() {
+            fill_nexthop_routes(
+                &mut net_state.routes,
+                &np_state.ifaces,
+                option.route_filter.as_ref(),
+            )
+            .await;
+        }
+        if let Some(rt_filter) = option.route_filter.as_ref() {
+            truncate_routes(&mut net_state.routes, rt_filter);
+        }
 
         net_state
             .routes
             .mark_route_as_ignored_ifaces(&net_state.ifaces);
+        net_state.route_rules = get_route_rules(&np_state.rules);
+
+        net_state.dns = get_dns();
+        net_state.hostname = get_hostname();
+        net_state.route_metric_policy = Some(get_route_metric_policy());
         Ok(net_state)
     }
 }
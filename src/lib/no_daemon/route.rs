@@ -7,11 +7,24 @@
 //  * Íñigo Huguet <ihuguet@redhat.com>
 //  * Jan Vaclav <jvaclav@redhat.com>
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+};
+
+use futures_util::stream::TryStreamExt;
+use rtnetlink::packet_route::{
+    AddressFamily,
+    route::{
+        RouteAddress, RouteAttribute, RouteMessage, RouteMetric, RouteNextHop,
+        RouteProtocol, RouteScope,
+    },
+};
 
+use super::route_rule::parse_ip_net;
 use crate::{
-    ErrorKind, Interfaces, MergedRoutes, NipartError, RouteEntry, RouteType,
-    Routes,
+    ErrorKind, Interfaces, MergedRoutes, NipartError, NipartIpFamily,
+    NipartRouteFilter, NipartRouteProtocol, RouteEntry, RouteType, Routes,
 };
 
 const SUPPORTED_ROUTE_SCOPE: [nispor::RouteScope; 2] =
@@ -37,8 +50,17 @@ const IPV6_EMPTY_NEXT_HOP_ADDRESS: &str = "::";
 
 // kernel values
 const RTAX_CWND: u32 = 7;
+const RT_TABLE_MAIN: u32 = 254;
+const IPV6_DEFAULT_METRIC: u32 = 1024;
 
-pub(crate) async fn get_routes(_ifaces: &Interfaces) -> Routes {
+// Routes sharing the same destination, route table ID and metric are
+// grouped into single multipath route in kernel.
+type EcmpRouteKey<'a> = (&'a str, u32, u32);
+
+pub(crate) async fn get_routes(
+    ifaces: &Interfaces,
+    rt_filter: Option<&NipartRouteFilter>,
+) -> Routes {
     let mut ret = Routes::default();
     let mut np_routes: Vec<nispor::Route> = Vec::new();
     let route_type = [
@@ -47,22 +69,73 @@ pub(crate) async fn get_routes(_ifaces: &Interfaces) -> Routes {
         nispor::RouteType::Prohibit,
     ];
 
-    for protocol in &SUPPORTED_ROUTE_PROTOCOL {
-        let mut rt_filter = nispor::NetStateRouteFilter::default();
-        rt_filter.protocol = Some(*protocol);
-        let mut filter = nispor::NetStateFilter::minimum();
-        filter.route = Some(rt_filter);
-        match nispor::NetState::retrieve_with_filter_async(&filter).await {
-            Ok(np_state) => {
-                for np_rt in np_state.routes {
-                    np_routes.push(np_rt);
+    let mut protocols: Vec<nispor::RouteProtocol> =
+        match rt_filter.and_then(|f| f.protocols.as_deref()) {
+            Some(protocols) => protocols
+                .iter()
+                .map(|p| nipart_to_np_route_protocol(*p))
+                .collect(),
+            None => SUPPORTED_ROUTE_PROTOCOL.to_vec(),
+        };
+    // Only static routes are required for `Routes.config`
+    if rt_filter.is_some_and(|f| f.skip_running) {
+        protocols.retain(|p| SUPPORTED_STATIC_ROUTE_PROTOCOL.contains(p));
+    }
+    // Kernel can only filter route table ID fits in u8, other route tables
+    // are filtered by `filter_routes()` after query.
+    let tables: Vec<Option<u8>> = match rt_filter
+        .and_then(|f| f.tables.as_deref())
+        .and_then(|tables| {
+            tables
+                .iter()
+                .map(|t| u8::try_from(*t).ok())
+                .collect::<Option<Vec<u8>>>()
+        }) {
+        Some(tables) => tables.into_iter().map(Some).collect(),
+        None => vec![None],
+    };
+
+    // Dump routes of desired IP families only, instead of filtering them
+    // after dumped.
+    let families: Vec<Option<nispor::AddressFamily>> =
+        match rt_filter.and_then(|f| f.families.as_deref()) {
+            Some(families) => families
+                .iter()
+                .map(|f| {
+                    Some(match f {
+                        NipartIpFamily::Ipv4 => nispor::AddressFamily::Ipv4,
+                        NipartIpFamily::Ipv6 => nispor::AddressFamily::Ipv6,
+                    })
+                })
+                .collect(),
+            None => vec![None],
+        };
+
+    for protocol in &protocols {
+        for table in &tables {
+            for family in &families {
+                let mut np_rt_filter = nispor::NetStateRouteFilter::default();
+                np_rt_filter.protocol = Some(*protocol);
+                np_rt_filter.table = *table;
+                np_rt_filter.address_family = *family;
+                let mut filter = nispor::NetStateFilter::minimum();
+                filter.route = Some(np_rt_filter);
+                match nispor::NetState::retrieve_with_filter_async(&filter)
+                    .await
+                {
+                    Ok(np_state) => {
+                        for np_rt in np_state.routes {
+                            np_routes.push(np_rt);
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Failed to retrieve {protocol:?} route via \
+                             nispor: {e}"
+                        );
+                    }
                 }
             }
-            Err(e) => {
-                log::warn!(
-                    "Failed to retrieve {protocol:?} route via nispor: {e}"
-                );
-            }
         }
     }
 
@@ -99,9 +172,90 @@ pub(crate) async fn get_routes(_ifaces: &Interfaces) -> Routes {
         }
     }
     ret.config = Some(config_routes);
+
+    for rts in [ret.running.as_mut(), ret.config.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        for rt in rts {
+            if let Err(e) = rt
+                .resolve_vrf(|iface_name| ifaces.kernel_ifaces.get(iface_name))
+            {
+                log::debug!("Route {rt} is not in VRF route table: {e}");
+            }
+        }
+    }
     ret
 }
 
+fn nipart_to_np_route_protocol(
+    protocol: NipartRouteProtocol,
+) -> nispor::RouteProtocol {
+    match protocol {
+        NipartRouteProtocol::Boot => nispor::RouteProtocol::Boot,
+        NipartRouteProtocol::Static => nispor::RouteProtocol::Static,
+        NipartRouteProtocol::Ra => nispor::RouteProtocol::Ra,
+        NipartRouteProtocol::Dhcp => nispor::RouteProtocol::Dhcp,
+        NipartRouteProtocol::Mrouted => nispor::RouteProtocol::Mrouted,
+        NipartRouteProtocol::KeepAlived => nispor::RouteProtocol::KeepAlived,
+        NipartRouteProtocol::Babel => nispor::RouteProtocol::Babel,
+    }
+}
+
+// Apply the route filter which cannot be done by kernel during query.
+pub(crate) fn filter_routes(
+    routes: &mut Routes,
+    rt_filter: &NipartRouteFilter,
+) {
+    if rt_filter.skip_running {
+        routes.running = None;
+    }
+    for rts in [routes.running.as_mut(), routes.config.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(families) = rt_filter.families.as_deref() {
+            rts.retain(|rt| {
+                families.contains(&if rt.is_ipv6() {
+                    NipartIpFamily::Ipv6
+                } else {
+                    NipartIpFamily::Ipv4
+                })
+            });
+        }
+        if let Some(tables) = rt_filter.tables.as_deref() {
+            rts.retain(|rt| {
+                tables.contains(&rt.table_id.unwrap_or(RT_TABLE_MAIN))
+            });
+        }
+    }
+}
+
+// Should be invoked after all routes are queried, this only limit the size
+// of query result.
+pub(crate) fn truncate_routes(
+    routes: &mut Routes,
+    rt_filter: &NipartRouteFilter,
+) {
+    let Some(max_entries) = rt_filter.max_entries else {
+        return;
+    };
+    let max_entries = max_entries as usize;
+    for rts in [routes.running.as_mut(), routes.config.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        if rts.len() > max_entries {
+            log::info!(
+                "Truncating {} routes to {max_entries} entries as requested \
+                 by route filter",
+                rts.len()
+            );
+            rts.truncate(max_entries);
+        }
+    }
+}
+
 fn np_routetype_to_nipart(np_route: &nispor::Route) -> RouteEntry {
     let destination = match &np_route.dst {
         Some(dst) => Some(dst.to_string()),
@@ -132,6 +286,11 @@ fn np_routetype_to_nipart(np_route: &nispor::Route) -> RouteEntry {
         } else {
             None
         },
+        initcwnd: np_route.initcwnd,
+        initrwnd: np_route.initrwnd,
+        mtu: np_route.mtu,
+        quickack: np_route.quickack.map(|q| q > 0),
+        advmss: np_route.advmss,
         ..Default::default()
     };
     if np_route.address_family == nispor::AddressFamily::Ipv6 {
@@ -239,9 +398,7 @@ fn flat_multipath_route(np_route: &nispor::Route) -> Vec<RouteEntry> {
             new_np_route.via = Some(mp_route.via.to_string());
             new_np_route.oif = Some(mp_route.iface.to_string());
             let mut route = np_route_to_nipart(&new_np_route);
-            if np_route.address_family == nispor::AddressFamily::Ipv4 {
-                route.weight = Some(mp_route.weight);
-            }
+            route.weight = Some(mp_route.weight);
             ret.push(route);
         }
     }
@@ -273,26 +430,12 @@ fn nipart_to_nispor_route_conf(
             ret.table = Some(table_id as u8);
         }
     }
-    if nipart_rt.weight.is_some() {
-        return Err(NipartError::new(
-            ErrorKind::NoSupport,
-            "nispor apply does not support route weight yet".into(),
-        ));
-    }
-
     if nipart_rt.route_type.is_some() {
         return Err(NipartError::new(
             ErrorKind::NoSupport,
             "nispor apply does not support route type yet".into(),
         ));
     }
-
-    if nipart_rt.cwnd.is_some() {
-        return Err(NipartError::new(
-            ErrorKind::NoSupport,
-            "nispor apply does not support route congestion window yet".into(),
-        ));
-    }
     Ok(ret)
 }
 
@@ -304,9 +447,35 @@ pub(crate) async fn apply_routes(
         return Ok(());
     }
     validate_routes(merged_routes)?;
+
+    let merged_groups =
+        group_routes_by_ecmp_key(merged_routes.merged.values().flatten());
+    let cur_groups = group_routes_by_ecmp_key(
+        merged_routes.current.config.as_deref().unwrap_or_default(),
+    );
+
+    // Changes to ECMP route are applied as whole multipath route via
+    // rtnetlink, so does new route holding route metrics(RTAX_*) or
+    // referring nexthop object, nispor only handles single path route
+    // without route metrics.
+    let mut ecmp_keys: HashSet<EcmpRouteKey> = HashSet::new();
+    let mut rtnl_rts: Vec<(EcmpRouteKey, &RouteEntry)> = Vec::new();
     let mut np_route_confs = Vec::new();
     for nipart_rt in merged_routes.changed_routes.as_slice() {
-        np_route_confs.push(nipart_to_nispor_route_conf(nipart_rt)?)
+        if let Some(key) = ecmp_route_key(nipart_rt)
+            && [&merged_groups, &cur_groups]
+                .iter()
+                .any(|g| g.get(&key).is_some_and(|rts| is_ecmp(rts)))
+        {
+            ecmp_keys.insert(key);
+        } else if !nipart_rt.is_absent()
+            && (has_route_metrics(nipart_rt) || nipart_rt.nexthop_id.is_some())
+            && let Some(key) = route_key(nipart_rt)
+        {
+            rtnl_rts.push((key, nipart_rt));
+        } else {
+            np_route_confs.push(nipart_to_nispor_route_conf(nipart_rt)?)
+        }
     }
 
     if !np_route_confs.is_empty() {
@@ -326,50 +495,353 @@ pub(crate) async fn apply_routes(
         log::trace!("No route changes");
     }
 
+    if !ecmp_keys.is_empty() {
+        apply_ecmp_routes(&ecmp_keys, &merged_groups).await?;
+    }
+
+    if !rtnl_rts.is_empty() {
+        apply_single_path_routes(rtnl_rts.as_slice()).await?;
+    }
+
     Ok(())
 }
 
 fn validate_routes(merged_routes: &MergedRoutes) -> Result<(), NipartError> {
-    for iface in merged_routes.route_changed_ifaces.as_slice() {
-        let Some(iface_routes) = merged_routes.merged.get(iface) else {
+    let groups =
+        group_routes_by_ecmp_key(merged_routes.merged.values().flatten());
+    for ((dst, table_id, metric), rts) in groups.iter() {
+        if !rts.iter().any(|rt| {
+            rt.next_hop_iface.as_ref().is_some_and(|iface| {
+                merged_routes.route_changed_ifaces.contains(iface)
+            })
+        }) {
             continue;
-        };
+        }
+        // The `Routes::validate()` already confirmed non-absent routes
+        // always has destination.
+        // The `merged_routes.merged` does not have absent route.
+        let first_rt = rts[0];
+        if let Some(rt) = rts.iter().find(|rt| {
+            rt.next_hop_iface == first_rt.next_hop_iface
+                && rt.next_hop_addr == first_rt.next_hop_addr
+                && !std::ptr::eq(**rt, first_rt)
+        }) {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Multiple routes to {dst} are sharing the same metric \
+                     {metric}, route table {table_id} and next hop, please \
+                     use `state: absent` to remove others: {first_rt} {rt}"
+                ),
+            ));
+        }
+        if let Some(rt) = rts.iter().find(|rt| {
+            rt.source != first_rt.source
+                || rt.cwnd != first_rt.cwnd
+                || rt.initcwnd != first_rt.initcwnd
+                || rt.initrwnd != first_rt.initrwnd
+                || rt.mtu != first_rt.mtu
+                || rt.quickack != first_rt.quickack
+                || rt.advmss != first_rt.advmss
+        }) {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Routes to {dst} with metric {metric} in route table \
+                     {table_id} are grouped into ECMP route, but holding \
+                     different source or route metrics: {first_rt} {rt}"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
 
-        let mut hashed_rts: HashMap<(&str, Option<u32>), &RouteEntry> =
-            HashMap::new();
-        for rt in iface_routes {
-            if rt.weight.is_some() {
-                return Err(NipartError::new(
-                    ErrorKind::NoSupport,
-                    "Kernel mode does not support ECMP routes".to_string(),
-                ));
-            }
+fn ecmp_route_key(rt: &RouteEntry) -> Option<EcmpRouteKey<'_>> {
+    if rt.next_hop_iface.is_none() {
+        None
+    } else {
+        route_key(rt)
+    }
+}
 
-            // The `Routes::validate()` already confirmed non-absent routes
-            // always has destination.
-            // The `merged_routes.merged` does not have absent route.
-            let dst = if let Some(dst) = rt.destination.as_deref() {
-                dst
-            } else {
-                continue;
-            };
+fn route_key(rt: &RouteEntry) -> Option<EcmpRouteKey<'_>> {
+    if !rt.is_unicast() {
+        return None;
+    }
+    let dst = rt.destination.as_deref()?;
+    let table_id = rt
+        .table_id
+        .filter(|t| *t != RouteEntry::USE_DEFAULT_ROUTE_TABLE)
+        .unwrap_or(RT_TABLE_MAIN);
+    let metric = rt
+        .metric
+        .and_then(|m| u32::try_from(m).ok())
+        .unwrap_or(if rt.is_ipv6() { IPV6_DEFAULT_METRIC } else { 0 });
+    Some((dst, table_id, metric))
+}
+
+fn group_routes_by_ecmp_key<'a>(
+    rts: impl IntoIterator<Item = &'a RouteEntry>,
+) -> HashMap<EcmpRouteKey<'a>, Vec<&'a RouteEntry>> {
+    let mut ret: HashMap<EcmpRouteKey, Vec<&RouteEntry>> = HashMap::new();
+    for rt in rts.into_iter().filter(|rt| !rt.is_absent()) {
+        if let Some(key) = ecmp_route_key(rt) {
+            ret.entry(key).or_default().push(rt);
+        }
+    }
+    ret
+}
+
+fn is_ecmp(rts: &[&RouteEntry]) -> bool {
+    rts.len() > 1 || rts.iter().any(|rt| rt.weight.is_some())
+}
+
+// Replace the whole multipath route with merged next hops, or remove it when
+// no next hop left.
+async fn apply_ecmp_routes<'a>(
+    ecmp_keys: &HashSet<EcmpRouteKey<'a>>,
+    merged_groups: &HashMap<EcmpRouteKey<'a>, Vec<&'a RouteEntry>>,
+) -> Result<(), NipartError> {
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
 
-            if hashed_rts
-                .insert(
-                    (dst, rt.metric.and_then(|m| u32::try_from(m).ok())),
-                    rt,
+    for key in ecmp_keys {
+        let (dst, table_id, metric) = *key;
+        let mut msg = new_route_msg(dst, table_id, metric)?;
+        let Some(rts) = merged_groups.get(key).filter(|rts| !rts.is_empty())
+        else {
+            log::debug!(
+                "Removing ECMP route to {dst} with metric {metric} in route \
+                 table {table_id}"
+            );
+            handle.route().del(msg).execute().await.map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to remove ECMP route to {dst}: {e}"),
                 )
-                .is_some()
-            {
-                return Err(NipartError::new(
-                    ErrorKind::InvalidArgument,
-                    format!(
-                        "Multiple routes to {dst} are sharing the same \
-                         metric, please use `state: absent` to remove others."
-                    ),
+            })?;
+            continue;
+        };
+        // The `validate_routes()` already confirmed all routes in ECMP group
+        // are sharing the same source and route metrics.
+        set_route_common_attrs(&mut msg, rts[0])?;
+        let mut next_hops = Vec::new();
+        for rt in rts {
+            let mut next_hop = RouteNextHop::default();
+            if let Some(iface_name) = rt.next_hop_iface.as_deref() {
+                next_hop.interface_index =
+                    get_iface_index(&handle, iface_name).await?;
+            }
+            next_hop.hops = rt
+                .weight
+                .and_then(|w| u8::try_from(w.saturating_sub(1)).ok())
+                .unwrap_or_default();
+            if let Some(gateway) = get_gateway(rt)? {
+                next_hop.attributes.push(RouteAttribute::Gateway(gateway));
+            }
+            next_hops.push(next_hop);
+        }
+        msg.attributes.push(RouteAttribute::MultiPath(next_hops));
+        log::debug!(
+            "Setting ECMP route to {dst} with metric {metric} in route table \
+             {table_id}: {}",
+            rts.iter()
+                .map(|rt| rt.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        handle
+            .route()
+            .add(msg)
+            .replace()
+            .execute()
+            .await
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to set ECMP route to {dst}: {e}"),
+                )
+            })?;
+    }
+    Ok(())
+}
+
+async fn apply_single_path_routes(
+    rts: &[(EcmpRouteKey<'_>, &RouteEntry)],
+) -> Result<(), NipartError> {
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+
+    for ((dst, table_id, metric), rt) in rts {
+        let mut msg = new_route_msg(dst, *table_id, *metric)?;
+        set_route_common_attrs(&mut msg, rt)?;
+        if let Some(nexthop_id) = rt.nexthop_id {
+            msg.attributes.push(RouteAttribute::NhId(nexthop_id));
+        } else {
+            if let Some(iface_name) = rt.next_hop_iface.as_deref() {
+                msg.attributes.push(RouteAttribute::Oif(
+                    get_iface_index(&handle, iface_name).await?,
                 ));
             }
+            if let Some(gateway) = get_gateway(rt)? {
+                msg.attributes.push(RouteAttribute::Gateway(gateway));
+            } else {
+                msg.header.scope = RouteScope::Link;
+            }
         }
+        log::debug!("Setting route {rt}");
+        handle
+            .route()
+            .add(msg)
+            .replace()
+            .execute()
+            .await
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to set route {rt}: {e}"),
+                )
+            })?;
+    }
+    Ok(())
+}
+
+fn new_route_msg(
+    dst: &str,
+    table_id: u32,
+    metric: u32,
+) -> Result<RouteMessage, NipartError> {
+    let (dst_ip, prefix_len) = parse_ip_net(dst)?;
+    let mut msg = RouteMessage::default();
+    msg.header.address_family = if dst_ip.is_ipv6() {
+        AddressFamily::Inet6
+    } else {
+        AddressFamily::Inet
+    };
+    msg.header.destination_prefix_length = prefix_len;
+    msg.header.scope = RouteScope::Universe;
+    msg.header.kind = rtnetlink::packet_route::route::RouteType::Unicast;
+    if prefix_len != 0 {
+        msg.attributes
+            .push(RouteAttribute::Destination(ip_to_route_addr(dst_ip)));
+    }
+    msg.attributes.push(RouteAttribute::Table(table_id));
+    msg.attributes.push(RouteAttribute::Priority(metric));
+    Ok(msg)
+}
+
+// Set protocol, preferred source and route metrics(RTAX_*)
+fn set_route_common_attrs(
+    msg: &mut RouteMessage,
+    rt: &RouteEntry,
+) -> Result<(), NipartError> {
+    msg.header.protocol = RouteProtocol::Static;
+    if let Some(src) = rt.source.as_deref() {
+        msg.attributes
+            .push(RouteAttribute::PrefSource(parse_route_addr(src)?));
+    }
+    let metrics = gen_route_metrics(rt);
+    if !metrics.is_empty() {
+        msg.attributes.push(RouteAttribute::Metrics(metrics));
     }
     Ok(())
 }
+
+fn has_route_metrics(rt: &RouteEntry) -> bool {
+    rt.cwnd.is_some()
+        || rt.initcwnd.is_some()
+        || rt.initrwnd.is_some()
+        || rt.mtu.is_some()
+        || rt.quickack.is_some()
+        || rt.advmss.is_some()
+}
+
+fn gen_route_metrics(rt: &RouteEntry) -> Vec<RouteMetric> {
+    let mut ret = Vec::new();
+    // According to `man ip-route`, cwnd is useless without the lock flag
+    if let Some(cwnd) = rt.cwnd {
+        ret.push(RouteMetric::Lock(1 << RTAX_CWND));
+        ret.push(RouteMetric::Cwnd(cwnd));
+    }
+    if let Some(initcwnd) = rt.initcwnd {
+        ret.push(RouteMetric::InitCwnd(initcwnd));
+    }
+    if let Some(initrwnd) = rt.initrwnd {
+        ret.push(RouteMetric::InitRwnd(initrwnd));
+    }
+    if let Some(mtu) = rt.mtu {
+        ret.push(RouteMetric::Mtu(mtu));
+    }
+    if let Some(quickack) = rt.quickack {
+        ret.push(RouteMetric::QuickAck(quickack.into()));
+    }
+    if let Some(advmss) = rt.advmss {
+        ret.push(RouteMetric::AdvMss(advmss));
+    }
+    ret
+}
+
+fn get_gateway(rt: &RouteEntry) -> Result<Option<RouteAddress>, NipartError> {
+    match rt.next_hop_addr.as_deref() {
+        Some(via)
+            if !via.is_empty()
+                && via != IPV4_EMPTY_NEXT_HOP_ADDRESS
+                && via != IPV6_EMPTY_NEXT_HOP_ADDRESS =>
+        {
+            Ok(Some(parse_route_addr(via)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn parse_route_addr(
+    addr: &str,
+) -> Result<RouteAddress, NipartError> {
+    let ip = addr.parse::<IpAddr>().map_err(|e| {
+        NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid IP address '{addr}': {e}"),
+        )
+    })?;
+    Ok(ip_to_route_addr(ip))
+}
+
+fn ip_to_route_addr(ip: IpAddr) -> RouteAddress {
+    match ip {
+        IpAddr::V4(i) => RouteAddress::Inet(i),
+        IpAddr::V6(i) => RouteAddress::Inet6(i),
+    }
+}
+
+pub(crate) async fn get_iface_index(
+    handle: &rtnetlink::Handle,
+    iface_name: &str,
+) -> Result<u32, NipartError> {
+    let mut link_msgs = handle
+        .link()
+        .get()
+        .match_name(iface_name.to_string())
+        .execute();
+    match link_msgs.try_next().await {
+        Ok(Some(link_msg)) => Ok(link_msg.header.index),
+        Ok(None) => Err(NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!("Next hop interface {iface_name} not found"),
+        )),
+        Err(e) => Err(NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to query interface {iface_name}: {e}"),
+        )),
+    }
+}
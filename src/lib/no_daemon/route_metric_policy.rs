@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, MergedRouteMetricPolicy, NipartError, NipartNoDaemon,
+    RouteMetricPolicy,
+};
+
+const POLICY_CONF_DIR: &str = "/etc/nipart";
+const POLICY_CONF_PATH: &str = "/etc/nipart/route-metric-policy.json";
+// Holding policy applied with `memory-only`, takes precedence over the
+// persistent one till reboot.
+const POLICY_RUN_DIR: &str = "/run/nipart";
+const POLICY_RUN_PATH: &str = "/run/nipart/route-metric-policy.json";
+
+impl NipartNoDaemon {
+    /// Stored route metric policy with undefined properties set to default.
+    pub fn get_route_metric_policy() -> RouteMetricPolicy {
+        get_route_metric_policy()
+    }
+}
+
+pub(crate) fn get_route_metric_policy() -> RouteMetricPolicy {
+    let mut policy = read_policy(POLICY_RUN_PATH)
+        .or_else(|| read_policy(POLICY_CONF_PATH))
+        .unwrap_or_default();
+    policy.include_default();
+    policy
+}
+
+fn read_policy(path: &str) -> Option<RouteMetricPolicy> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(p) => Some(p),
+        Err(e) => {
+            log::warn!(
+                "Ignoring corrupted route metric policy file {path}: {e}"
+            );
+            None
+        }
+    }
+}
+
+// Only store the policy when changed, so re-applying the same merged state
+// on verification retry does not touch the file again.
+pub(crate) fn apply_route_metric_policy(
+    merged_policy: &MergedRouteMetricPolicy,
+    memory_only: bool,
+) -> Result<(), NipartError> {
+    let Some(policy) = merged_policy.for_apply.as_ref() else {
+        return Ok(());
+    };
+    let (dir, path) = if memory_only {
+        (POLICY_RUN_DIR, POLICY_RUN_PATH)
+    } else {
+        // Persistent policy should not be shadowed by previous memory-only
+        // one
+        std::fs::remove_file(POLICY_RUN_PATH).ok();
+        (POLICY_CONF_DIR, POLICY_CONF_PATH)
+    };
+    if read_policy(path).as_ref() == Some(policy) {
+        return Ok(());
+    }
+    log::debug!("Storing route metric policy {policy} to {path}");
+    let content = serde_json::to_string(policy).map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to generate JSON for route metric policy: {e}"),
+        )
+    })?;
+    std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(path, content))
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to write {path}: {e}"),
+            )
+        })
+}
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::IpAddr, str::FromStr};
+
+use rtnetlink::packet_route::{
+    AddressFamily,
+    route::RouteProtocol,
+    rule::{RuleAction, RuleAttribute, RuleMessage},
+};
+
+use crate::{
+    ErrorKind, MergedRouteRules, NipartError, RouteRuleAction, RouteRuleEntry,
+    RouteRuleFamily, RouteRules,
+};
+
+// Rules created by kernel on old kernel without `FRA_PROTOCOL` support
+const KERNEL_DEFAULT_RULES: [(u32, u32); 3] = [
+    (0, 255),     // lookup local
+    (32766, 254), // lookup main
+    (32767, 253), // lookup default
+];
+
+pub(crate) fn get_route_rules(np_rules: &[nispor::RouteRule]) -> RouteRules {
+    let mut rules = Vec::new();
+    for np_rule in np_rules {
+        if np_rule.protocol == Some(nispor::RouteProtocol::Kernel)
+            || np_rule.l3mdev == Some(true)
+        {
+            continue;
+        }
+        let Some(rule) = np_rule_to_nipart(np_rule) else {
+            continue;
+        };
+        if np_rule.protocol.is_none() && is_kernel_default_rule(&rule) {
+            continue;
+        }
+        rules.push(rule);
+    }
+    rules.sort_unstable();
+    RouteRules {
+        config: Some(rules),
+    }
+}
+
+fn np_rule_to_nipart(np_rule: &nispor::RouteRule) -> Option<RouteRuleEntry> {
+    let mut rule = RouteRuleEntry {
+        family: match np_rule.address_family {
+            nispor::AddressFamily::Ipv4 => Some(RouteRuleFamily::Ipv4),
+            nispor::AddressFamily::Ipv6 => Some(RouteRuleFamily::Ipv6),
+            _ => {
+                log::debug!(
+                    "Ignoring route rule with unsupported address family \
+                     {np_rule:?}"
+                );
+                return None;
+            }
+        },
+        action: match np_rule.action {
+            nispor::RuleAction::Table => None,
+            nispor::RuleAction::Blackhole => Some(RouteRuleAction::Blackhole),
+            nispor::RuleAction::Unreachable => {
+                Some(RouteRuleAction::Unreachable)
+            }
+            nispor::RuleAction::Prohibit => Some(RouteRuleAction::Prohibit),
+            _ => {
+                log::debug!(
+                    "Ignoring route rule with unsupported action {np_rule:?}"
+                );
+                return None;
+            }
+        },
+        priority: np_rule.priority,
+        from: np_rule.src.clone(),
+        to: np_rule.dst.clone(),
+        iif: np_rule.iif.clone(),
+        oif: np_rule.oif.clone(),
+        fwmark: np_rule.fw_mark,
+        fwmask: np_rule.fw_mask,
+        table_id: np_rule.table,
+        suppress_prefix_length: np_rule
+            .suppress_prefix_len
+            .filter(|l| *l != u32::MAX),
+        ..Default::default()
+    };
+    if let Err(e) = rule.sanitize() {
+        log::debug!("Ignoring route rule {np_rule:?}: {e}");
+        return None;
+    }
+    Some(rule)
+}
+
+fn is_kernel_default_rule(rule: &RouteRuleEntry) -> bool {
+    rule.from.is_none()
+        && rule.to.is_none()
+        && rule.iif.is_none()
+        && rule.oif.is_none()
+        && rule.fwmark.is_none()
+        && rule.suppress_prefix_length.is_none()
+        && rule.action.is_none()
+        && KERNEL_DEFAULT_RULES.iter().any(|(priority, table_id)| {
+            rule.priority == Some(*priority) && rule.table_id == Some(*table_id)
+        })
+}
+
+pub(crate) async fn apply_route_rules(
+    merged_rules: &MergedRouteRules,
+) -> Result<(), NipartError> {
+    if !merged_rules.is_changed() {
+        log::debug!("Route rule is not changed");
+        return Ok(());
+    }
+    let (conn, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to create rtnetlink connection: {e}"),
+        )
+    })?;
+    tokio::spawn(conn);
+
+    // Remove rules first in case new rule is sharing the same priority
+    for rule in merged_rules.changed_rules.iter().filter(|r| r.is_absent()) {
+        log::debug!("Removing route rule {rule}");
+        handle
+            .rule()
+            .del(nipart_rule_to_rule_msg(rule)?)
+            .execute()
+            .await
+            .map_err(|e| {
+                NipartError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to remove route rule {rule}: {e}"),
+                )
+            })?;
+    }
+    for rule in merged_rules.changed_rules.iter().filter(|r| !r.is_absent()) {
+        log::debug!("Adding route rule {rule}");
+        let mut req = handle.rule().add();
+        *req.message_mut() = nipart_rule_to_rule_msg(rule)?;
+        req.message_mut()
+            .attributes
+            .push(RuleAttribute::Protocol(RouteProtocol::Static));
+        req.execute().await.map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to add route rule {rule}: {e}"),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn nipart_rule_to_rule_msg(
+    rule: &RouteRuleEntry,
+) -> Result<RuleMessage, NipartError> {
+    let mut msg = RuleMessage::default();
+    msg.header.family = if rule.is_ipv6() {
+        AddressFamily::Inet6
+    } else {
+        AddressFamily::Inet
+    };
+    msg.header.action = match rule.action.unwrap_or_default() {
+        RouteRuleAction::Table => RuleAction::ToTable,
+        RouteRuleAction::Blackhole => RuleAction::Blackhole,
+        RouteRuleAction::Unreachable => RuleAction::Unreachable,
+        RouteRuleAction::Prohibit => RuleAction::Prohibit,
+    };
+    if let Some(from) = rule.from.as_deref() {
+        let (ip, prefix_len) = parse_ip_net(from)?;
+        msg.header.src_len = prefix_len;
+        msg.attributes.push(RuleAttribute::Source(ip));
+    }
+    if let Some(to) = rule.to.as_deref() {
+        let (ip, prefix_len) = parse_ip_net(to)?;
+        msg.header.dst_len = prefix_len;
+        msg.attributes.push(RuleAttribute::Destination(ip));
+    }
+    if let Some(priority) = rule.priority {
+        msg.attributes.push(RuleAttribute::Priority(priority));
+    }
+    if let Some(iif) = rule.iif.as_ref() {
+        msg.attributes.push(RuleAttribute::Iifname(iif.to_string()));
+    }
+    if let Some(oif) = rule.oif.as_ref() {
+        msg.attributes.push(RuleAttribute::Oifname(oif.to_string()));
+    }
+    if let Some(fwmark) = rule.fwmark {
+        msg.attributes.push(RuleAttribute::FwMark(fwmark));
+    }
+    if let Some(fwmask) = rule.fwmask {
+        msg.attributes.push(RuleAttribute::FwMask(fwmask));
+    }
+    if let Some(table_id) = rule.table_id {
+        msg.attributes.push(RuleAttribute::Table(table_id));
+    }
+    if let Some(suppress_prefix_length) = rule.suppress_prefix_length {
+        msg.attributes
+            .push(RuleAttribute::SuppressPrefixLen(suppress_prefix_length));
+    }
+    Ok(msg)
+}
+
+// The `from` and `to` of route rule is already sanitized to `ip/prefix`
+pub(crate) fn parse_ip_net(ip_net: &str) -> Result<(IpAddr, u8), NipartError> {
+    let (ip, prefix_len) = ip_net.split_once('/').unwrap_or((ip_net, ""));
+    let ip = IpAddr::from_str(ip).map_err(|e| {
+        NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid IP network '{ip_net}': {e}"),
+        )
+    })?;
+    let prefix_len = if prefix_len.is_empty() {
+        if ip.is_ipv6() { 128 } else { 32 }
+    } else {
+        prefix_len.parse::<u8>().map_err(|e| {
+            NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid IP network prefix length '{ip_net}': {e}"),
+            )
+        })?
+    };
+    Ok((ip, prefix_len))
+}
@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::udev::{is_udev_running, udev_net_device_is_initialized};
+use crate::{
+    ErrorKind, Interface, Interfaces, MergedInterface, MergedInterfaces,
+    NetworkState, NipartError, NipartInterface, NipartNoDaemon,
+    NipartQueryOption, SrIovConfig, SrIovVfConfig,
+};
+
+// Some SR-IOV NIC drivers take a long time to create VFs
+const VF_WAIT_RETRY_COUNT: usize = 60;
+const VF_WAIT_RETRY_INTERVAL_MS: u64 = 500;
+
+impl From<&nispor::SriovInfo> for SrIovConfig {
+    fn from(np_sriov: &nispor::SriovInfo) -> Self {
+        Self {
+            total_vfs: Some(np_sriov.vfs.len() as u32),
+            vfs: Some(np_sriov.vfs.iter().map(SrIovVfConfig::from).collect()),
+        }
+    }
+}
+
+impl From<&nispor::VfInfo> for SrIovVfConfig {
+    fn from(np_vf: &nispor::VfInfo) -> Self {
+        Self {
+            id: np_vf.id,
+            iface_name: np_vf.iface_name.clone().unwrap_or_default(),
+            mac_address: Some(np_vf.mac.to_uppercase()),
+            spoof_check: Some(np_vf.spoof_check),
+            trust: Some(np_vf.trust),
+            min_tx_rate: Some(np_vf.min_tx_rate),
+            max_tx_rate: Some(np_vf.max_tx_rate),
+            vlan_id: Some(np_vf.vlan_id),
+            qos: Some(np_vf.qos),
+        }
+    }
+}
+
+/// Generate nispor VF configurations. The `total-vfs` is not included as
+/// it is applied by [apply_sriov_total_vfs()] beforehand.
+pub(crate) fn gen_np_sriov_vf_conf(
+    sriov_conf: &SrIovConfig,
+) -> Option<nispor::SriovConf> {
+    let vfs = sriov_conf.vfs.as_ref()?;
+    if vfs.is_empty() {
+        return None;
+    }
+    let mut np_vfs = Vec::new();
+    for vf in vfs {
+        let mut np_vf = nispor::VfConf::default();
+        np_vf.id = vf.id;
+        np_vf.mac = vf.mac_address.clone();
+        np_vf.spoof_check = vf.spoof_check;
+        np_vf.trust = vf.trust;
+        np_vf.min_tx_rate = vf.min_tx_rate;
+        np_vf.max_tx_rate = vf.max_tx_rate;
+        np_vf.vlan_id = vf.vlan_id;
+        np_vf.qos = vf.qos;
+        np_vfs.push(np_vf);
+    }
+    let mut np_sriov = nispor::SriovConf::default();
+    np_sriov.vfs = Some(np_vfs);
+    Some(np_sriov)
+}
+
+/// Change the VF count of SR-IOV PFs and wait udev to finish initializing
+/// the VF network devices, so that follow up VF configurations can be
+/// applied to them. On host without udev, only wait kernel network devices.
+/// Return the current network state after VFs initialized, or `None` if no
+/// VF count changed.
+pub(crate) async fn apply_sriov_total_vfs(
+    merged_ifaces: &MergedInterfaces,
+) -> Result<Option<NetworkState>, NipartError> {
+    // HashMap of PF name to desired VF count
+    let mut pending_changes: HashMap<&str, u32> = HashMap::new();
+    for merged_iface in merged_ifaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.merged.is_up() && i.current.is_some())
+    {
+        if let Some(Interface::Ethernet(apply_iface)) =
+            merged_iface.for_apply.as_ref()
+            && let Some(total_vfs) = apply_iface
+                .ethernet
+                .as_ref()
+                .and_then(|e| e.sr_iov.as_ref())
+                .and_then(|s| s.total_vfs)
+            && cur_total_vfs(merged_iface.current.as_ref()) != Some(total_vfs)
+        {
+            pending_changes.insert(apply_iface.name(), total_vfs);
+        }
+    }
+    if pending_changes.is_empty() {
+        return Ok(None);
+    }
+
+    let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
+    for (pf_name, total_vfs) in pending_changes.iter() {
+        log::info!("Changing SR-IOV VF count of {pf_name} to {total_vfs}");
+        let mut np_sriov = nispor::SriovConf::default();
+        np_sriov.total_vfs = Some(*total_vfs);
+        let mut np_iface = nispor::IfaceConf::default();
+        np_iface.name = pf_name.to_string();
+        np_iface.iface_type = Some(nispor::IfaceType::Ethernet);
+        np_iface.sriov = Some(np_sriov);
+        np_ifaces.push(np_iface);
+    }
+
+    let mut net_conf = nispor::NetConf::default();
+    net_conf.ifaces = Some(np_ifaces);
+
+    log::debug!(
+        "Pending nispor changes {}",
+        serde_json::to_string(&net_conf).unwrap_or_default()
+    );
+
+    if let Err(e) = net_conf.apply_async().await {
+        return Err(NipartError::new(
+            ErrorKind::Bug,
+            format!("Failed to change SR-IOV VF count: {e}"),
+        ));
+    }
+
+    Ok(Some(wait_vfs_initialized(&pending_changes).await?))
+}
+
+/// The merged interfaces were generated before VFs created, hence desired
+/// VF interfaces have no current state and would be ignored when applying.
+/// Merge them again with the current state after VFs initialized.
+pub(crate) fn remerge_new_vfs(
+    merged_ifaces: &MergedInterfaces,
+    cur_ifaces: &Interfaces,
+) -> Result<MergedInterfaces, NipartError> {
+    let mut ret = merged_ifaces.clone();
+    for merged_iface in ret.kernel_ifaces.values_mut().filter(|i| {
+        i.current.is_none()
+            && i.desired.as_ref().is_some_and(|d| !d.is_virtual())
+    }) {
+        let Some(cur_iface) =
+            cur_ifaces.kernel_ifaces.get(merged_iface.merged.name())
+        else {
+            continue;
+        };
+        log::debug!(
+            "Merging desired interface {} with newly created VF",
+            cur_iface.name()
+        );
+        let up_priority = merged_iface
+            .for_apply
+            .as_ref()
+            .map(|i| i.base_iface().up_priority);
+        let mut new_merged_iface = MergedInterface::new(
+            merged_iface.desired.clone(),
+            Some(cur_iface.clone()),
+        )?;
+        if let Some(for_apply) = new_merged_iface.for_apply.as_mut()
+            && let Some(up_priority) = up_priority
+        {
+            for_apply.base_iface_mut().up_priority = up_priority;
+        }
+        *merged_iface = new_merged_iface;
+    }
+    Ok(ret)
+}
+
+fn cur_total_vfs(cur_iface: Option<&Interface>) -> Option<u32> {
+    if let Some(Interface::Ethernet(cur_iface)) = cur_iface {
+        cur_iface
+            .ethernet
+            .as_ref()
+            .and_then(|e| e.sr_iov.as_ref())
+            .and_then(|s| s.total_vfs)
+    } else {
+        None
+    }
+}
+
+async fn wait_vfs_initialized(
+    pending_changes: &HashMap<&str, u32>,
+) -> Result<NetworkState, NipartError> {
+    for cur_retry_count in 1..(VF_WAIT_RETRY_COUNT + 1) {
+        let cur_state =
+            NipartNoDaemon::query_network_state(NipartQueryOption::running())
+                .await?;
+        match get_uninitialized_vf(&cur_state, pending_changes) {
+            Some(msg) => {
+                log::debug!(
+                    "Retrying({cur_retry_count}/{VF_WAIT_RETRY_COUNT}) on \
+                     waiting SR-IOV VF: {msg}"
+                );
+                tokio::time::sleep(std::time::Duration::from_millis(
+                    VF_WAIT_RETRY_INTERVAL_MS,
+                ))
+                .await;
+            }
+            None => return Ok(cur_state),
+        }
+    }
+    Err(NipartError::new(
+        ErrorKind::Timeout,
+        format!(
+            "Timeout on waiting SR-IOV VF network devices of {:?} to be \
+             initialized",
+            pending_changes.keys().collect::<Vec<_>>()
+        ),
+    ))
+}
+
+// Return the description of first VF not ready yet
+fn get_uninitialized_vf(
+    cur_state: &NetworkState,
+    pending_changes: &HashMap<&str, u32>,
+) -> Option<String> {
+    let udev_running = is_udev_running();
+    for (pf_name, total_vfs) in pending_changes.iter() {
+        let cur_iface = cur_state.ifaces.kernel_ifaces.get(*pf_name);
+        let Some(Interface::Ethernet(cur_iface)) = cur_iface else {
+            return Some(format!("PF {pf_name} not found"));
+        };
+        let cur_vfs = cur_iface
+            .ethernet
+            .as_ref()
+            .and_then(|e| e.sr_iov.as_ref())
+            .and_then(|s| s.vfs.as_deref())
+            .unwrap_or_default();
+        if cur_vfs.len() != *total_vfs as usize {
+            return Some(format!(
+                "PF {pf_name} has {} VFs, expecting {total_vfs}",
+                cur_vfs.len()
+            ));
+        }
+        // VF bound to userspace driver(e.g. vfio-pci) or emulated by
+        // netdevsim has no network device, hence only wait VFs with
+        // network device.
+        for vf in cur_vfs.iter().filter(|vf| !vf.iface_name.is_empty()) {
+            let Some(vf_iface) =
+                cur_state.ifaces.kernel_ifaces.get(vf.iface_name.as_str())
+            else {
+                return Some(format!(
+                    "VF {} of PF {pf_name} not found",
+                    vf.iface_name
+                ));
+            };
+            if udev_running
+                && !vf_iface
+                    .base_iface()
+                    .iface_index
+                    .map(udev_net_device_is_initialized)
+                    .unwrap_or_default()
+            {
+                return Some(format!(
+                    "VF {} of PF {pf_name} is not initialized by udev yet",
+                    vf.iface_name
+                ));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::os::fd::AsRawFd;
+
+use nix::libc;
+
+use crate::{
+    BaseInterface, ErrorKind, Interface, MergedInterfaces, NipartError,
+    NipartInterface, TunConfig, TunInterface, TunMode,
+};
+
+const TUN_DEV_PATH: &str = "/dev/net/tun";
+
+// Defined in linux/if_tun.h. The TUNSETIFF is defined as `_IOW('T', 202,
+// int)` but actually taking pointer of `struct ifreq`.
+nix::ioctl_write_ptr_bad!(
+    tun_set_iff,
+    nix::request_code_write!(b'T', 202, std::mem::size_of::<libc::c_int>()),
+    TunIfReq
+);
+nix::ioctl_write_int!(tun_set_persist, b'T', 203);
+nix::ioctl_write_int!(tun_set_owner, b'T', 204);
+nix::ioctl_write_int!(tun_set_group, b'T', 206);
+
+// The `struct ifreq` with only `ifr_flags` of union used
+#[repr(C)]
+struct TunIfReq {
+    name: [u8; libc::IFNAMSIZ],
+    flags: libc::c_short,
+    _pad: [u8; 22],
+}
+
+impl From<nispor::TunMode> for TunMode {
+    fn from(v: nispor::TunMode) -> Self {
+        match v {
+            nispor::TunMode::Tun => Self::Tun,
+            nispor::TunMode::Tap => Self::Tap,
+            m => {
+                log::debug!("Got unknown TUN mode {m:?}");
+                Self::Unknown
+            }
+        }
+    }
+}
+
+impl From<&nispor::TunInfo> for TunConfig {
+    fn from(np_info: &nispor::TunInfo) -> Self {
+        Self {
+            mode: Some(np_info.mode.into()),
+            owner: np_info.owner.filter(|i| *i != TunConfig::UNSET_ID),
+            group: np_info.group.filter(|i| *i != TunConfig::UNSET_ID),
+            multi_queue: Some(np_info.multi_queue),
+            persist: Some(np_info.persist),
+            vnet_hdr: Some(np_info.vnet_hdr),
+        }
+    }
+}
+
+impl TunInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            tun: np_iface.tun.as_ref().map(TunConfig::from),
+        }
+    }
+}
+
+/// Kernel has no netlink interface for creating TUN/TAP, hence we use
+/// ioctl on `/dev/net/tun` to create new or change existing persistent
+/// TUN/TAP interfaces before other link changes.
+pub(crate) fn apply_tun_ifaces(
+    merged_ifaces: &MergedInterfaces,
+) -> Result<(), NipartError> {
+    for merged_iface in merged_ifaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.merged.is_up())
+    {
+        if let Some(Interface::Tun(apply_iface)) =
+            merged_iface.for_apply.as_ref()
+            && let Interface::Tun(merged_tun_iface) = &merged_iface.merged
+            && let Some(tun_conf) = merged_tun_iface.tun.as_ref()
+        {
+            let cur_tun_conf = if let Some(Interface::Tun(cur_iface)) =
+                merged_iface.current.as_ref()
+            {
+                cur_iface.tun.as_ref()
+            } else {
+                None
+            };
+            // Deleted by `need_delete_before_change()` should be treated as
+            // new interface
+            let is_new = cur_tun_conf.is_none()
+                || merged_iface.current.as_ref().is_some_and(|cur_iface| {
+                    merged_iface.merged.need_delete_before_change(cur_iface)
+                });
+            // Only owner, group and persist can be changed by attaching to
+            // existing TUN/TAP interface
+            let need_change = is_new
+                || cur_tun_conf.is_some_and(|cur_tun_conf| {
+                    tun_conf.owner.filter(|i| *i != TunConfig::UNSET_ID)
+                        != cur_tun_conf.owner
+                        || tun_conf.group.filter(|i| *i != TunConfig::UNSET_ID)
+                            != cur_tun_conf.group
+                        || tun_conf.persist != cur_tun_conf.persist
+                });
+            if need_change {
+                log::debug!(
+                    "{} TUN/TAP interface {}",
+                    if is_new { "Creating" } else { "Changing" },
+                    apply_iface.name(),
+                );
+                set_tun(apply_iface.name(), tun_conf)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_tun(iface_name: &str, tun_conf: &TunConfig) -> Result<(), NipartError> {
+    let fd = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(TUN_DEV_PATH)
+        .map_err(|e| {
+            NipartError::new(
+                ErrorKind::Bug,
+                format!("Failed to open {TUN_DEV_PATH}: {e}"),
+            )
+        })?;
+
+    let mut ifr = TunIfReq {
+        name: [0u8; libc::IFNAMSIZ],
+        flags: 0,
+        _pad: [0u8; 22],
+    };
+    if iface_name.len() >= libc::IFNAMSIZ {
+        return Err(NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!("TUN/TAP interface name {iface_name} is too long"),
+        ));
+    }
+    ifr.name[..iface_name.len()].copy_from_slice(iface_name.as_bytes());
+
+    let mut flags = libc::IFF_NO_PI;
+    flags |= match tun_conf.mode {
+        Some(TunMode::Tap) => libc::IFF_TAP,
+        _ => libc::IFF_TUN,
+    };
+    if tun_conf.multi_queue == Some(true) {
+        flags |= libc::IFF_MULTI_QUEUE;
+    }
+    if tun_conf.vnet_hdr == Some(true) {
+        flags |= libc::IFF_VNET_HDR;
+    }
+    ifr.flags = flags as libc::c_short;
+
+    let raw_fd = fd.as_raw_fd();
+    // SAFETY: `ifr` is a valid `struct ifreq` and outlives the ioctl call,
+    // `raw_fd` is opened and valid till the end of this function.
+    unsafe {
+        tun_set_iff(raw_fd, &ifr)
+            .map_err(|e| tun_ioctl_error("TUNSETIFF", iface_name, e))?;
+        if let Some(owner) = tun_conf.owner {
+            tun_set_owner(raw_fd, owner as _)
+                .map_err(|e| tun_ioctl_error("TUNSETOWNER", iface_name, e))?;
+        }
+        if let Some(group) = tun_conf.group {
+            tun_set_group(raw_fd, group as _)
+                .map_err(|e| tun_ioctl_error("TUNSETGROUP", iface_name, e))?;
+        }
+        // Sanitize already forbid changing persistent TUN/TAP to
+        // non-persistent, and we should not touch non-persistent TUN/TAP
+        // created by other application.
+        if tun_conf.persist == Some(true) {
+            tun_set_persist(raw_fd, 1)
+                .map_err(|e| tun_ioctl_error("TUNSETPERSIST", iface_name, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn tun_ioctl_error(
+    action: &str,
+    iface_name: &str,
+    e: nix::errno::Errno,
+) -> NipartError {
+    NipartError::new(
+        ErrorKind::Bug,
+        format!("Failed to {action} on TUN/TAP interface {iface_name}: {e}"),
+    )
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseInterface, ErrorKind, NipartError, VrfConfig, VrfInterface};
+
+impl From<&nispor::VrfInfo> for VrfConfig {
+    fn from(np_vrf: &nispor::VrfInfo) -> Self {
+        let mut ports = np_vrf.subordinates.clone();
+        ports.sort_unstable();
+        Self {
+            ports: Some(ports),
+            table_id: Some(np_vrf.table_id),
+        }
+    }
+}
+
+pub(crate) fn apply_vrf_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &VrfInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(vrf_conf) = iface.vrf.as_ref() {
+        if let Some(table_id) = vrf_conf.table_id {
+            let mut np_vrf = nispor::VrfConf::default();
+            np_vrf.table_id = table_id;
+            np_iface.vrf = Some(np_vrf);
+        } else {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!(
+                    "apply_vrf_conf() got VRF without route table ID: \
+                     {iface:?}"
+                ),
+            ));
+        }
+    }
+    Ok(vec![np_iface])
+}
+
+impl VrfInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            vrf: np_iface.vrf.as_ref().map(VrfConfig::from),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, ErrorKind, NipartError, VxlanConfig, VxlanInterface,
+};
+
+impl From<&nispor::VxlanInfo> for VxlanConfig {
+    fn from(np_vxlan: &nispor::VxlanInfo) -> Self {
+        Self {
+            id: Some(np_vxlan.vxlan_id),
+            base_iface: if np_vxlan.base_iface.is_empty() {
+                None
+            } else {
+                Some(np_vxlan.base_iface.clone())
+            },
+            local: np_vxlan.local.parse().ok(),
+            remote: np_vxlan.remote.parse().ok(),
+            dst_port: Some(np_vxlan.dst_port),
+            learning: Some(np_vxlan.learning),
+        }
+    }
+}
+
+pub(crate) fn apply_vxlan_conf(
+    mut np_iface: nispor::IfaceConf,
+    iface: &VxlanInterface,
+) -> Result<Vec<nispor::IfaceConf>, NipartError> {
+    if let Some(vxlan_conf) = iface.vxlan.as_ref() {
+        if let Some(id) = vxlan_conf.id {
+            let mut np_vxlan = nispor::VxlanConf::default();
+            np_vxlan.vxlan_id = id;
+            np_vxlan.base_iface = vxlan_conf.base_iface.clone();
+            np_vxlan.local = vxlan_conf.local;
+            np_vxlan.remote = vxlan_conf.remote;
+            np_vxlan.dst_port = vxlan_conf.dst_port;
+            np_vxlan.learning = vxlan_conf.learning;
+            np_iface.vxlan = Some(np_vxlan);
+        } else {
+            return Err(NipartError::new(
+                ErrorKind::Bug,
+                format!("apply_vxlan_conf() got VXLAN without ID: {iface:?}"),
+            ));
+        }
+    }
+    Ok(vec![np_iface])
+}
+
+impl VxlanInterface {
+    pub(crate) fn new_from_nispor(
+        base_iface: BaseInterface,
+        np_iface: &nispor::Iface,
+    ) -> Self {
+        Self {
+            base: base_iface,
+            vxlan: np_iface.vxlan.as_ref().map(VxlanConfig::from),
+        }
+    }
+}
@@ -6,7 +6,8 @@ use futures_util::StreamExt;
 use zvariant::{ObjectPath, OwnedObjectPath};
 
 use super::{
-    bss::WpaSupBss, interface::WpaSupInterface, network::WpaSupNetwork,
+    bss::WpaSupBss, interface::WpaSupInterface, macsec::WpaSupMacSecNetwork,
+    network::WpaSupNetwork,
 };
 use crate::{ErrorKind, NipartError};
 
@@ -249,6 +250,36 @@ impl NipartWpaSupDbus<'_> {
             .map_err(map_zbus_err)
     }
 
+    /// Create WPA interface using `macsec_linux` driver on parent of MACsec
+    /// interface. Wired interface should not scan for access point.
+    pub(crate) async fn add_macsec_iface(
+        &self,
+        iface_name: &str,
+    ) -> Result<String, NipartError> {
+        log::trace!("Enabled WPA MACsec interface {iface_name}");
+        let mut wpa_iface = WpaSupInterface::new(iface_name.to_string());
+        wpa_iface.driver = Some(WpaSupInterface::DRIVER_MACSEC.to_string());
+        let iface_obj_path = self
+            .proxy
+            .create_interface(wpa_iface.to_value())
+            .await
+            .map(obj_path_to_string)
+            .map_err(map_zbus_err)?;
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            WPA_SUP_DBUS_IFACE_ROOT,
+            iface_obj_path.as_str(),
+            WPA_SUP_DBUS_IFACE_IFACE,
+        )
+        .await
+        .map_err(map_zbus_err)?;
+        proxy
+            .set_property::<u32>("ApScan", 0)
+            .await
+            .map_err(map_zbus_fdo_err)?;
+        Ok(iface_obj_path)
+    }
+
     pub(crate) async fn del_iface(
         &self,
         iface_name: &str,
@@ -274,6 +305,25 @@ impl NipartWpaSupDbus<'_> {
         network: &WpaSupNetwork,
     ) -> Result<String, NipartError> {
         log::trace!("Adding WPA network {iface_obj_path} {}", network.ssid);
+        self.add_network_value(iface_obj_path, network.to_value())
+            .await
+    }
+
+    pub(crate) async fn add_macsec_network(
+        &self,
+        iface_obj_path: &str,
+        network: &WpaSupMacSecNetwork,
+    ) -> Result<String, NipartError> {
+        log::trace!("Adding WPA MACsec network {iface_obj_path}");
+        self.add_network_value(iface_obj_path, network.to_value())
+            .await
+    }
+
+    async fn add_network_value(
+        &self,
+        iface_obj_path: &str,
+        value: HashMap<&str, zvariant::Value<'_>>,
+    ) -> Result<String, NipartError> {
         let proxy = zbus::Proxy::new(
             &self.connection,
             WPA_SUP_DBUS_IFACE_ROOT,
@@ -285,7 +335,7 @@ impl NipartWpaSupDbus<'_> {
         proxy
             .call::<&str, HashMap<&str, zvariant::Value<'_>>, OwnedObjectPath>(
                 "AddNetwork",
-                &network.to_value(),
+                &value,
             )
             .await
             .map(obj_path_to_string)
@@ -60,15 +60,19 @@ impl From<WpaSupInterfaceState> for WifiState {
 pub(crate) struct WpaSupInterface {
     pub(crate) obj_path: OwnedObjectPath,
     pub(crate) iface_name: String,
+    pub(crate) driver: Option<String>,
     pub(crate) state: WpaSupInterfaceState,
     pub(crate) cur_auth_mode: Option<String>,
 }
 
 impl WpaSupInterface {
+    pub(crate) const DRIVER_MACSEC: &str = "macsec_linux";
+
     pub(crate) fn new(iface_name: String) -> Self {
         Self {
             iface_name,
             obj_path: OwnedObjectPath::default(),
+            driver: None,
             state: WpaSupInterfaceState::Unknown,
             cur_auth_mode: None,
         }
@@ -77,6 +81,9 @@ impl WpaSupInterface {
     pub(crate) fn to_value(&self) -> HashMap<&str, zvariant::Value<'_>> {
         let mut ret = HashMap::new();
         ret.insert("Ifname", zvariant::Value::new(self.iface_name.clone()));
+        if let Some(v) = self.driver.as_ref() {
+            ret.insert("Driver", zvariant::Value::new(v.clone()));
+        }
         ret
     }
 
@@ -96,6 +103,7 @@ impl WpaSupInterface {
                         ),
                     )
                 })?,
+            driver: _from_map!(map, "Driver", String::try_from)?,
             state: _from_map!(map, "State", String::try_from)?
                 .map(WpaSupInterfaceState::from)
                 .unwrap_or_default(),
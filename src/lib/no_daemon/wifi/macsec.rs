@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::{
+    NipartWpaConn, dbus::NipartWpaSupDbus, interface::WpaSupInterface,
+};
+use crate::{
+    ErrorKind, MacSecCipher, MacSecConfig, MacSecInterface, NipartError,
+    NipartInterface,
+};
+
+// Use static CAK/CKN for MKA
+const MACSEC_POLICY_SHOULD_SECURE: i32 = 1;
+const MACSEC_CSINDEX_GCM_AES_256: i32 = 1;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WpaSupMacSecNetwork {
+    pub(crate) mka_cak: Vec<u8>,
+    pub(crate) mka_ckn: Vec<u8>,
+    pub(crate) port: Option<u16>,
+    pub(crate) integ_only: bool,
+    pub(crate) csindex: Option<i32>,
+}
+
+impl WpaSupMacSecNetwork {
+    fn new(
+        iface_name: &str,
+        conf: &MacSecConfig,
+    ) -> Result<Option<Self>, NipartError> {
+        let (Some(cak), Some(ckn)) =
+            (conf.mka_cak.as_deref(), conf.mka_ckn.as_deref())
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            mka_cak: hex_to_bytes(iface_name, "mka-cak", cak)?,
+            mka_ckn: hex_to_bytes(iface_name, "mka-ckn", ckn)?,
+            port: conf.port,
+            integ_only: conf.encrypt == Some(false),
+            csindex: (conf.cipher == Some(MacSecCipher::GcmAes256))
+                .then_some(MACSEC_CSINDEX_GCM_AES_256),
+        }))
+    }
+
+    // wpa_supplicant converts byte array to hex string without quoting
+    pub(crate) fn to_value(&self) -> HashMap<&str, zvariant::Value<'_>> {
+        let mut ret = HashMap::new();
+        ret.insert("key_mgmt", zvariant::Value::new("NONE".to_string()));
+        ret.insert("eapol_flags", zvariant::Value::new(0i32));
+        ret.insert(
+            "macsec_policy",
+            zvariant::Value::new(MACSEC_POLICY_SHOULD_SECURE),
+        );
+        ret.insert(
+            "macsec_integ_only",
+            zvariant::Value::new(i32::from(self.integ_only)),
+        );
+        ret.insert("mka_cak", zvariant::Value::new(self.mka_cak.clone()));
+        ret.insert("mka_ckn", zvariant::Value::new(self.mka_ckn.clone()));
+        if let Some(v) = self.port {
+            ret.insert("macsec_port", zvariant::Value::new(i32::from(v)));
+        }
+        if let Some(v) = self.csindex {
+            ret.insert("macsec_csindex", zvariant::Value::new(v));
+        }
+        ret
+    }
+}
+
+impl NipartWpaConn {
+    /// Start or stop MKA session of MACsec interfaces. The wpa_supplicant
+    /// `macsec_linux` driver reuses existing MACsec interface holding the
+    /// same SCI on the parent interface.
+    /// Take list of MACsec interfaces for apply along with their parent.
+    pub(crate) async fn apply_macsec(
+        ifaces: &[(&MacSecInterface, &str)],
+    ) -> Result<(), NipartError> {
+        let dbus = NipartWpaSupDbus::new().await?;
+
+        for (iface, base_iface) in ifaces {
+            let network = iface
+                .macsec
+                .as_ref()
+                .map(|conf| WpaSupMacSecNetwork::new(iface.name(), conf))
+                .transpose()?
+                .flatten();
+            // Keep existing MKA session if keys not mentioned
+            if iface.is_up() && network.is_none() {
+                continue;
+            }
+            del_macsec_iface(&dbus, base_iface).await?;
+            let Some(network) = network.filter(|_| iface.is_up()) else {
+                continue;
+            };
+            if dbus.get_iface_obj_path(base_iface).await?.is_some() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface {base_iface} is already managed by \
+                         wpa_supplicant, cannot start MKA session of MACsec \
+                         interface {}",
+                        iface.name()
+                    ),
+                ));
+            }
+            log::debug!(
+                "Starting MKA session of MACsec interface {} on {base_iface}",
+                iface.name()
+            );
+            let iface_obj_path = dbus.add_macsec_iface(base_iface).await?;
+            let network_obj_path = dbus
+                .add_macsec_network(iface_obj_path.as_str(), &network)
+                .await?;
+            dbus.enable_network(network_obj_path.as_str()).await?;
+        }
+        Ok(())
+    }
+}
+
+// Only remove WPA interface created for MACsec, the parent might be
+// managed by wpa_supplicant for other purpose.
+async fn del_macsec_iface(
+    dbus: &NipartWpaSupDbus<'_>,
+    base_iface: &str,
+) -> Result<(), NipartError> {
+    let Some(iface_obj_path) = dbus.get_iface_obj_path(base_iface).await?
+    else {
+        return Ok(());
+    };
+    let wpa_iface = dbus.get_iface(iface_obj_path.as_str()).await?;
+    if wpa_iface.driver.as_deref() == Some(WpaSupInterface::DRIVER_MACSEC) {
+        log::debug!("Stopping MKA session on {base_iface}");
+        dbus.del_iface(base_iface).await?;
+    }
+    Ok(())
+}
+
+fn hex_to_bytes(
+    iface_name: &str,
+    prop_name: &str,
+    hex: &str,
+) -> Result<Vec<u8>, NipartError> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|s| u8::from_str_radix(s, 16).ok())
+                .ok_or_else(|| {
+                    NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "`macsec.{prop_name}` of MACsec interface \
+                             {iface_name} is not valid hex string"
+                        ),
+                    )
+                })
+        })
+        .collect()
+}
@@ -7,6 +7,7 @@ mod apply;
 mod bss;
 mod dbus;
 mod interface;
+mod macsec;
 mod network;
 mod query;
 mod scan;
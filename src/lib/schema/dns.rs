@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::IpAddr, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, JsonDisplay, NipartError};
+
+/// DNS resolver state. The example yaml output of [crate::NetworkState] with
+/// static DNS resolver configuration would be:
+/// ```yml
+/// dns-resolver:
+///   running:
+///     server:
+///     - 2001:db8:f::1
+///     - 192.0.2.1
+///     - 192.0.2.254
+///     search:
+///     - example.org
+///     - example.net
+///   config:
+///     server:
+///     - 2001:db8:f::1
+///     - 192.0.2.1
+///     search:
+///     - example.org
+///     options:
+///     - rotate
+///     - ndots:5
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct DnsResolver {
+    /// Running DNS resolver configurations containing both static
+    /// configurations and DNS learned from DHCPv4/DHCPv6.
+    /// Ignored when applying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running: Option<DnsResolverConfig>,
+    /// Static DNS resolver configurations.
+    /// When applying, `None` means preserve current static configurations,
+    /// `Some(DnsResolverConfig::default())` means purge all static
+    /// configurations. The static DNS servers and search domains take
+    /// precedence over DNS learned from DHCP.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<DnsResolverConfig>,
+}
+
+impl DnsResolver {
+    pub fn is_empty(&self) -> bool {
+        self.config.is_none()
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct DnsResolverConfig {
+    /// Name servers in the order of preference. IPv6 link-local address
+    /// should be suffixed with `%<iface_name>`, for example
+    /// `fe80::1%eth1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<Vec<String>>,
+    /// Search domains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<Vec<String>>,
+    /// Resolver options defined in `resolv.conf(5)`, for example `rotate`,
+    /// `ndots:5`. Ignored when DNS is managed by systemd-resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<String>>,
+}
+
+impl DnsResolverConfig {
+    pub fn new(server: Vec<String>, search: Vec<String>) -> Self {
+        Self {
+            server: Some(server),
+            search: Some(search),
+            options: None,
+        }
+    }
+
+    /// * Validate and normalize name server IP address.
+    /// * Remove duplicate entries while preserving order.
+    /// * Remove trailing dot of search domain.
+    pub(crate) fn sanitize(&mut self) -> Result<(), NipartError> {
+        if let Some(servers) = self.server.as_mut() {
+            for server in servers.iter_mut() {
+                *server = sanitize_name_server(server)?;
+            }
+            dedup_preserve_order(servers);
+        }
+        if let Some(searches) = self.search.as_mut() {
+            for search in searches.iter_mut() {
+                if search.is_empty() || search.contains(char::is_whitespace) {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid DNS search domain '{search}'"),
+                    ));
+                }
+                if search.len() > 1 && search.ends_with('.') {
+                    search.pop();
+                }
+                search.make_ascii_lowercase();
+            }
+            dedup_preserve_order(searches);
+        }
+        if let Some(opts) = self.options.as_mut() {
+            if let Some(opt) = opts
+                .iter()
+                .find(|o| o.is_empty() || o.contains(char::is_whitespace))
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid DNS resolver option '{opt}'"),
+                ));
+            }
+            dedup_preserve_order(opts);
+        }
+        Ok(())
+    }
+
+    /// Append servers and search domains of `other` which not found in
+    /// self.
+    pub(crate) fn append(&mut self, other: &Self) {
+        for (mine, others) in [
+            (&mut self.server, other.server.as_ref()),
+            (&mut self.search, other.search.as_ref()),
+            (&mut self.options, other.options.as_ref()),
+        ] {
+            if let Some(others) = others {
+                let mine = mine.get_or_insert_with(Vec::new);
+                for item in others {
+                    if !mine.contains(item) {
+                        mine.push(item.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.server.as_deref().unwrap_or_default().is_empty()
+            && self.search.as_deref().unwrap_or_default().is_empty()
+            && self.options.as_deref().unwrap_or_default().is_empty()
+    }
+}
+
+fn sanitize_name_server(server: &str) -> Result<String, NipartError> {
+    let (ip_str, iface_name) = match server.split_once('%') {
+        Some((ip_str, iface_name)) => (ip_str, Some(iface_name)),
+        None => (server, None),
+    };
+    let ip = IpAddr::from_str(ip_str).map_err(|e| {
+        NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid DNS name server '{server}': {e}"),
+        )
+    })?;
+    match (ip, iface_name) {
+        (IpAddr::V6(ipv6), Some(iface_name))
+            if ipv6.is_unicast_link_local() && !iface_name.is_empty() =>
+        {
+            Ok(format!("{ipv6}%{iface_name}"))
+        }
+        (IpAddr::V6(ipv6), None) if ipv6.is_unicast_link_local() => {
+            Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "IPv6 link-local DNS name server '{server}' should be \
+                     suffixed with `%<iface_name>`"
+                ),
+            ))
+        }
+        (_, Some(_)) => Err(NipartError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Only IPv6 link-local DNS name server could have \
+                 `%<iface_name>` suffix, got '{server}'"
+            ),
+        )),
+        (ip, None) => Ok(ip.to_string()),
+    }
+}
+
+fn dedup_preserve_order(items: &mut Vec<String>) {
+    let mut seen: Vec<String> = Vec::new();
+    items.retain(|item| {
+        if seen.contains(item) {
+            false
+        } else {
+            seen.push(item.clone());
+            true
+        }
+    });
+}
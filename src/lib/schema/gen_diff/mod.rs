@@ -3,4 +3,6 @@
 mod base_iface;
 mod inter_iface;
 mod net_state;
+mod nexthop;
 mod route;
+mod route_rule;
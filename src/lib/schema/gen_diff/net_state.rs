@@ -36,6 +36,12 @@ impl NetworkState {
 
         ret.ifaces = merged_state.ifaces.gen_diff()?;
         ret.routes = merged_state.routes.gen_diff();
+        ret.route_rules = merged_state.route_rules.gen_diff();
+        ret.nexthops = merged_state.nexthops.gen_diff();
+        ret.dns = merged_state.dns.gen_state_for_apply();
+        ret.hostname = merged_state.hostname.gen_state_for_apply();
+        ret.route_metric_policy =
+            merged_state.route_metric_policy.gen_state_for_apply();
         Ok(ret)
     }
 }
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedNextHops, NextHops};
+
+impl MergedNextHops {
+    pub fn gen_diff(&self) -> NextHops {
+        NextHops {
+            config: if self.changed_nexthops.is_empty() {
+                None
+            } else {
+                Some(self.changed_nexthops.clone())
+            },
+        }
+    }
+}
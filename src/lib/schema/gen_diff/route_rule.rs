@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedRouteRules, RouteRules};
+
+impl MergedRouteRules {
+    pub fn gen_diff(&self) -> RouteRules {
+        RouteRules {
+            config: if self.changed_rules.is_empty() {
+                None
+            } else {
+                Some(self.changed_rules.clone())
+            },
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, JsonDisplay, NipartError};
+
+// Linux HOST_NAME_MAX
+const HOST_NAME_MAX: usize = 64;
+
+/// Host name state. The example yaml output of [crate::NetworkState] with
+/// host name learned from DHCP would be:
+/// ```yml
+/// hostname:
+///   running: host-a.example.org
+///   config: host-a
+///   dhcp-override: true
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct HostNameState {
+    /// Host name currently used by kernel, could be transient host name
+    /// learned from DHCP. Ignored when applying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running: Option<String>,
+    /// Static host name stored in `/etc/hostname`.
+    /// When applying, `None` means preserve current static host name,
+    /// empty string means remove static host name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+    /// Whether host name learned from DHCP may override running host name.
+    /// When DHCP stopped, running host name will be restored to static host
+    /// name. When applying, `None` means preserve current setting. Default
+    /// is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_override: Option<bool>,
+}
+
+impl HostNameState {
+    pub fn is_empty(&self) -> bool {
+        self.config.is_none() && self.dhcp_override.is_none()
+    }
+
+    pub(crate) fn sanitize(&mut self) -> Result<(), NipartError> {
+        if let Some(config) = self.config.as_mut() {
+            if config.len() > 1 && config.ends_with('.') {
+                config.pop();
+            }
+            Self::validate_host_name(config)?;
+        }
+        Ok(())
+    }
+
+    /// Empty string is allowed for purging static host name.
+    pub(crate) fn validate_host_name(name: &str) -> Result<(), NipartError> {
+        if name.len() > HOST_NAME_MAX {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Host name '{name}' is longer than {HOST_NAME_MAX} \
+                     characters"
+                ),
+            ));
+        }
+        if !name.is_empty()
+            && name.split('.').any(|label| {
+                label.is_empty()
+                    || label.starts_with('-')
+                    || label.ends_with('-')
+                    || !label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid host name '{name}', should only contain ASCII \
+                     letters, digits, hyphen and dot separated labels"
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
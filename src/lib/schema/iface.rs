@@ -14,10 +14,12 @@ use serde::{Deserialize, Deserializer, Serialize};
 use super::value::get_json_value_difference;
 use crate::{
     BaseInterface, BondInterface, DummyInterface, ErrorKind, EthernetInterface,
-    InterfaceState, InterfaceType, JsonDisplayHideSecrets,
-    LinuxBridgeInterface, LoopbackInterface, NipartError, NipartInterface,
-    OvsBridgeInterface, OvsInterface, UnknownInterface, VlanInterface,
-    WifiCfgInterface, WifiPhyInterface, WireguardInterface,
+    HsrInterface, InfiniBandInterface, InterfaceState, InterfaceType,
+    IpTunnelInterface, IpVlanInterface, JsonDisplayHideSecrets,
+    LinuxBridgeInterface, LoopbackInterface, MacSecInterface, MacVlanInterface,
+    MacVtapInterface, NipartError, NipartInterface, OvsBridgeInterface,
+    OvsInterface, TunInterface, UnknownInterface, VlanInterface, VrfInterface,
+    VxlanInterface, WifiCfgInterface, WifiPhyInterface, WireguardInterface,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonDisplayHideSecrets)]
@@ -47,6 +49,26 @@ pub enum Interface {
     LinuxBridge(Box<LinuxBridgeInterface>),
     /// Wireguard Interface
     Wireguard(Box<WireguardInterface>),
+    /// VRF Interface
+    Vrf(Box<VrfInterface>),
+    /// VXLAN Interface
+    Vxlan(Box<VxlanInterface>),
+    /// MAC VLAN Interface
+    MacVlan(Box<MacVlanInterface>),
+    /// MAC VTAP Interface
+    MacVtap(Box<MacVtapInterface>),
+    /// IPVLAN Interface
+    IpVlan(Box<IpVlanInterface>),
+    /// MACsec Interface
+    MacSec(Box<MacSecInterface>),
+    /// HSR Interface
+    Hsr(Box<HsrInterface>),
+    /// IP over InfiniBand Interface
+    InfiniBand(Box<InfiniBandInterface>),
+    /// TUN/TAP Interface
+    Tun(Box<TunInterface>),
+    /// IP tunnel Interface
+    IpTunnel(Box<IpTunnelInterface>),
     /// Unknown interface.
     Unknown(Box<UnknownInterface>),
 }
@@ -147,6 +169,61 @@ impl<'de> Deserialize<'de> for Interface {
                     .map_err(serde::de::Error::custom)?;
                 Ok(Interface::Wireguard(Box::new(inner)))
             }
+            Some(InterfaceType::Vrf) => {
+                let inner = VrfInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Vrf(Box::new(inner)))
+            }
+            Some(InterfaceType::Vxlan) => {
+                let inner = VxlanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Vxlan(Box::new(inner)))
+            }
+            Some(InterfaceType::MacVlan) => {
+                let inner = MacVlanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::MacVlan(Box::new(inner)))
+            }
+            Some(InterfaceType::MacVtap) => {
+                let inner = MacVtapInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::MacVtap(Box::new(inner)))
+            }
+            Some(InterfaceType::IpVlan) => {
+                let inner = IpVlanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpVlan(Box::new(inner)))
+            }
+            Some(InterfaceType::MacSec) => {
+                let inner = MacSecInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::MacSec(Box::new(inner)))
+            }
+            Some(InterfaceType::Hsr) => {
+                let inner = HsrInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Hsr(Box::new(inner)))
+            }
+            Some(InterfaceType::InfiniBand) => {
+                let inner = InfiniBandInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::InfiniBand(Box::new(inner)))
+            }
+            Some(InterfaceType::Tun) => {
+                let inner = TunInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Tun(Box::new(inner)))
+            }
+            Some(InterfaceType::Gre)
+            | Some(InterfaceType::GreTap)
+            | Some(InterfaceType::Ip6Gre)
+            | Some(InterfaceType::Ipip)
+            | Some(InterfaceType::Sit)
+            | Some(InterfaceType::Ip6Tnl) => {
+                let inner = IpTunnelInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpTunnel(Box::new(inner)))
+            }
             _ => {
                 let inner = UnknownInterface::deserialize(v)
                     .map_err(serde::de::Error::custom)?;
@@ -307,6 +384,16 @@ macro_rules! gen_iface_trait_impl {
                     Self::Bond,
                     Self::LinuxBridge,
                     Self::Wireguard,
+                    Self::Vrf,
+                    Self::Vxlan,
+                    Self::MacVlan,
+                    Self::MacVtap,
+                    Self::IpVlan,
+                    Self::MacSec,
+                    Self::Hsr,
+                    Self::InfiniBand,
+                    Self::Tun,
+                    Self::IpTunnel,
                     Self::Unknown,
                 )
             }
@@ -332,6 +419,16 @@ macro_rules! gen_iface_trait_impl_mut {
                     Self::Bond,
                     Self::LinuxBridge,
                     Self::Wireguard,
+                    Self::Vrf,
+                    Self::Vxlan,
+                    Self::MacVlan,
+                    Self::MacVtap,
+                    Self::IpVlan,
+                    Self::MacSec,
+                    Self::Hsr,
+                    Self::InfiniBand,
+                    Self::Tun,
+                    Self::IpTunnel,
                     Self::Unknown,
                 )
             }
@@ -371,6 +468,16 @@ impl NipartInterface for Interface {
             Interface::Bond,
             Interface::LinuxBridge,
             Interface::Wireguard,
+            Interface::Vrf,
+            Interface::Vxlan,
+            Interface::MacVlan,
+            Interface::MacVtap,
+            Interface::IpVlan,
+            Interface::MacSec,
+            Interface::Hsr,
+            Interface::InfiniBand,
+            Interface::Tun,
+            Interface::IpTunnel,
             Interface::Unknown,
         )
     }
@@ -390,6 +497,16 @@ impl NipartInterface for Interface {
             Interface::Bond,
             Interface::LinuxBridge,
             Interface::Wireguard,
+            Interface::Vrf,
+            Interface::Vxlan,
+            Interface::MacVlan,
+            Interface::MacVtap,
+            Interface::IpVlan,
+            Interface::MacSec,
+            Interface::Hsr,
+            Interface::InfiniBand,
+            Interface::Tun,
+            Interface::IpTunnel,
             Interface::Unknown,
         );
     }
@@ -414,6 +531,16 @@ impl NipartInterface for Interface {
             Interface::Bond,
             Interface::LinuxBridge,
             Interface::Wireguard,
+            Interface::Vrf,
+            Interface::Vxlan,
+            Interface::MacVlan,
+            Interface::MacVtap,
+            Interface::IpVlan,
+            Interface::MacSec,
+            Interface::Hsr,
+            Interface::InfiniBand,
+            Interface::Tun,
+            Interface::IpTunnel,
             Interface::Unknown,
         )
     }
@@ -438,6 +565,16 @@ impl NipartInterface for Interface {
             Interface::Bond,
             Interface::LinuxBridge,
             Interface::Wireguard,
+            Interface::Vrf,
+            Interface::Vxlan,
+            Interface::MacVlan,
+            Interface::MacVtap,
+            Interface::IpVlan,
+            Interface::MacSec,
+            Interface::Hsr,
+            Interface::InfiniBand,
+            Interface::Tun,
+            Interface::IpTunnel,
             Interface::Unknown,
         )
     }
@@ -462,6 +599,16 @@ impl NipartInterface for Interface {
             Interface::Bond,
             Interface::LinuxBridge,
             Interface::Wireguard,
+            Interface::Vrf,
+            Interface::Vxlan,
+            Interface::MacVlan,
+            Interface::MacVtap,
+            Interface::IpVlan,
+            Interface::MacSec,
+            Interface::Hsr,
+            Interface::InfiniBand,
+            Interface::Tun,
+            Interface::IpTunnel,
             Interface::Unknown,
         )
     }
@@ -481,6 +628,16 @@ impl NipartInterface for Interface {
             Interface::Bond,
             Interface::LinuxBridge,
             Interface::Wireguard,
+            Interface::Vrf,
+            Interface::Vxlan,
+            Interface::MacVlan,
+            Interface::MacVtap,
+            Interface::IpVlan,
+            Interface::MacSec,
+            Interface::Hsr,
+            Interface::InfiniBand,
+            Interface::Tun,
+            Interface::IpTunnel,
             Interface::Unknown,
         )
     }
@@ -490,15 +647,15 @@ impl From<BaseInterface> for Interface {
     fn from(base_iface: BaseInterface) -> Self {
         let mut iface = match &base_iface.iface_type {
             InterfaceType::Ethernet => Interface::Ethernet(Default::default()),
-            InterfaceType::Hsr => todo!(),
+            InterfaceType::Hsr => Interface::Hsr(Default::default()),
             InterfaceType::Bond => Interface::Bond(Default::default()),
             InterfaceType::LinuxBridge => {
                 Interface::LinuxBridge(Default::default())
             }
             InterfaceType::Dummy => Interface::Dummy(Default::default()),
             InterfaceType::Loopback => Interface::Loopback(Default::default()),
-            InterfaceType::MacVlan => todo!(),
-            InterfaceType::MacVtap => todo!(),
+            InterfaceType::MacVlan => Interface::MacVlan(Default::default()),
+            InterfaceType::MacVtap => Interface::MacVtap(Default::default()),
             InterfaceType::OvsBridge => {
                 Interface::OvsBridge(Default::default())
             }
@@ -507,14 +664,22 @@ impl From<BaseInterface> for Interface {
             }
             InterfaceType::Veth => todo!(),
             InterfaceType::Vlan => Interface::Vlan(Default::default()),
-            InterfaceType::Vrf => todo!(),
-            InterfaceType::Vxlan => todo!(),
-            InterfaceType::InfiniBand => todo!(),
-            InterfaceType::Tun => todo!(),
-            InterfaceType::MacSec => todo!(),
+            InterfaceType::Vrf => Interface::Vrf(Default::default()),
+            InterfaceType::Vxlan => Interface::Vxlan(Default::default()),
+            InterfaceType::InfiniBand => {
+                Interface::InfiniBand(Default::default())
+            }
+            InterfaceType::Gre
+            | InterfaceType::GreTap
+            | InterfaceType::Ip6Gre
+            | InterfaceType::Ipip
+            | InterfaceType::Sit
+            | InterfaceType::Ip6Tnl => Interface::IpTunnel(Default::default()),
+            InterfaceType::Tun => Interface::Tun(Default::default()),
+            InterfaceType::MacSec => Interface::MacSec(Default::default()),
             InterfaceType::Ipsec => todo!(),
             InterfaceType::Xfrm => todo!(),
-            InterfaceType::IpVlan => todo!(),
+            InterfaceType::IpVlan => Interface::IpVlan(Default::default()),
             InterfaceType::WifiPhy => Interface::WifiPhy(Default::default()),
             InterfaceType::WifiCfg => Interface::WifiCfg(Default::default()),
             InterfaceType::Wireguard => {
@@ -71,6 +71,27 @@ pub enum InterfaceType {
     /// VxVLAN interface.
     /// Deserialize and serialize from/to 'vxlan'.
     Vxlan,
+    /// GRE over IPv4 tunnel interface.
+    /// Deserialize and serialize from/to 'gre'.
+    Gre,
+    /// Ethernet over GRE over IPv4 tunnel interface.
+    /// Deserialize and serialize from/to 'gretap'.
+    #[serde(rename = "gretap")]
+    GreTap,
+    /// GRE over IPv6 tunnel interface.
+    /// Deserialize and serialize from/to 'ip6gre'.
+    #[serde(rename = "ip6gre")]
+    Ip6Gre,
+    /// IPv4 over IPv4 tunnel interface.
+    /// Deserialize and serialize from/to 'ipip'.
+    Ipip,
+    /// IPv6 over IPv4 tunnel interface.
+    /// Deserialize and serialize from/to 'sit'.
+    Sit,
+    /// IPv4 or IPv6 over IPv6 tunnel interface.
+    /// Deserialize and serialize from/to 'ip6tnl'.
+    #[serde(rename = "ip6tnl")]
+    Ip6Tnl,
     /// IP over InfiniBand interface
     /// Deserialize and serialize from/to 'infiniband'.
     #[serde(rename = "infiniband")]
@@ -146,6 +167,35 @@ impl InterfaceType {
                 | InterfaceType::WifiPhy
                 | InterfaceType::Bond
                 | InterfaceType::Wireguard
+                | InterfaceType::Vrf
+                | InterfaceType::Vxlan
+                | InterfaceType::MacVlan
+                | InterfaceType::MacVtap
+                | InterfaceType::IpVlan
+                | InterfaceType::MacSec
+                | InterfaceType::Hsr
+                | InterfaceType::InfiniBand
+                | InterfaceType::Tun
+                | InterfaceType::Gre
+                | InterfaceType::GreTap
+                | InterfaceType::Ip6Gre
+                | InterfaceType::Ipip
+                | InterfaceType::Sit
+                | InterfaceType::Ip6Tnl
+        )
+    }
+
+    /// Whether interface type is IP tunnel which is represented by
+    /// [crate::IpTunnelInterface].
+    pub fn is_ip_tunnel(&self) -> bool {
+        matches!(
+            self,
+            InterfaceType::Gre
+                | InterfaceType::GreTap
+                | InterfaceType::Ip6Gre
+                | InterfaceType::Ipip
+                | InterfaceType::Sit
+                | InterfaceType::Ip6Tnl
         )
     }
 
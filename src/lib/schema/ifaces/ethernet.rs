@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
-    NipartInterface,
+    NipartInterface, SrIovConfig,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
@@ -45,6 +45,15 @@ impl EthernetInterface {
             ..Default::default()
         }
     }
+
+    pub(crate) fn sriov_is_enabled(&self) -> bool {
+        self.ethernet
+            .as_ref()
+            .and_then(|e| e.sr_iov.as_ref())
+            .and_then(|s| s.total_vfs)
+            .map(|t| t > 0)
+            .unwrap_or_default()
+    }
 }
 
 impl Default for EthernetInterface {
@@ -89,29 +98,44 @@ impl NipartInterface for EthernetInterface {
                 ),
             ));
         }
+        if let Some(sriov_conf) =
+            self.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut())
+        {
+            sriov_conf.sanitize(
+                self.base.name.as_str(),
+                current
+                    .and_then(|c| c.ethernet.as_ref())
+                    .and_then(|e| e.sr_iov.as_ref()),
+            )?;
+        }
         Ok(())
     }
 
+    fn sanitize_before_verify_iface_specfic(&mut self, current: &mut Self) {
+        if let Some(sriov_conf) =
+            self.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut())
+            && let Some(cur_sriov_conf) =
+                current.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut())
+        {
+            sriov_conf.sanitize_before_verify(cur_sriov_conf);
+        }
+    }
+
+    /// Disable SR-IOV if it was enabled by desired state.
     fn include_revert_context_iface_specific(
         &mut self,
-        _desired: &Self,
-        _pre_apply: &Self,
+        desired: &Self,
+        pre_apply: &Self,
     ) {
-        /*
-        if let (Interface::Ethernet(desired), Interface::Ethernet(current)) =
-            (desired, current)
-        {
-            if desired.sriov_is_enabled() && !current.sriov_is_enabled() {
-                self.ethernet
-                    .get_or_insert(EthernetConfig::new())
-                    .sr_iov
-                    .get_or_insert(SrIovConfig {
-                        total_vfs: Some(0),
-                        ..Default::default()
-                    });
-            }
+        if desired.sriov_is_enabled() && !pre_apply.sriov_is_enabled() {
+            self.ethernet
+                .get_or_insert_with(EthernetConfig::default)
+                .sr_iov
+                .get_or_insert(SrIovConfig {
+                    total_vfs: Some(0),
+                    ..Default::default()
+                });
         }
-         */
     }
 
     /// Should be deleted when changing veth peer
@@ -150,6 +174,9 @@ pub struct EthernetConfig {
     pub speed: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duplex: Option<EthernetDuplex>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sr-iov")]
+    /// Deserialize and serialize from/to `sr-iov`.
+    pub sr_iov: Option<SrIovConfig>,
 }
 
 #[derive(
@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+//  * Fernando Fernandez Mancera <ffmancera@riseup.net>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel High-availability Seamless Redundancy(HSR) or Parallel
+/// Redundancy Protocol(PRP) interface.
+///
+/// The example yaml output of a [crate::NetworkState] with a HSR interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: hsr0
+///   type: hsr
+///   state: up
+///   hsr:
+///     port1: eth1
+///     port2: eth2
+///     supervision-address: 01:15:4E:00:01:00
+///     protocol: hsr
+///     version: 1
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct HsrInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hsr: Option<HsrConfig>,
+}
+
+impl HsrInterface {
+    pub fn new(name: String, hsr: HsrConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::Hsr,
+                ..Default::default()
+            },
+            hsr: Some(hsr),
+        }
+    }
+}
+
+impl Default for HsrInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Hsr,
+                ..Default::default()
+            },
+            hsr: None,
+        }
+    }
+}
+
+impl NipartInterface for HsrInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn ports(&self) -> Option<Vec<&str>> {
+        let hsr_conf = self.hsr.as_ref()?;
+        if hsr_conf.port1.is_none() && hsr_conf.port2.is_none() {
+            None
+        } else {
+            Some(
+                [hsr_conf.port1.as_deref(), hsr_conf.port2.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            )
+        }
+    }
+
+    /// * Copy ports, protocol and version from current if not defined.
+    /// * Both ports are mandatory for new HSR.
+    /// * Version is only valid for HSR protocol.
+    /// * Supervision address should be in the format of `01:15:4E:00:01:XX`.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let Some(hsr_conf) = self.hsr.as_mut() else {
+            if current.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Need hsr section for creating HSR interface {}",
+                        self.base.name
+                    ),
+                ));
+            }
+            return Ok(());
+        };
+        if let Some(cur_conf) = current.and_then(|c| c.hsr.as_ref()) {
+            if hsr_conf.port1.is_none() {
+                hsr_conf.port1 = cur_conf.port1.clone();
+            }
+            if hsr_conf.port2.is_none() {
+                hsr_conf.port2 = cur_conf.port2.clone();
+            }
+            if hsr_conf.protocol.is_none() {
+                hsr_conf.protocol = cur_conf.protocol;
+            }
+        }
+        if hsr_conf.port1.is_none() || hsr_conf.port2.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Both `hsr.port1` and `hsr.port2` are mandatory for HSR \
+                     interface {}",
+                    self.base.name
+                ),
+            ));
+        }
+        if hsr_conf.protocol == Some(HsrProtocol::Prp)
+            && hsr_conf.version.is_some()
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`hsr.version` is only valid for HSR protocol, but \
+                     interface {} is using PRP protocol",
+                    self.base.name
+                ),
+            ));
+        }
+        if let Some(version) = hsr_conf.version
+            && version > HsrConfig::MAX_VERSION
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`hsr.version` of HSR interface {} should be 0 or 1, but \
+                     got {version}",
+                    self.base.name
+                ),
+            ));
+        }
+        if let Some(addr) = hsr_conf.supervision_address.as_mut() {
+            addr.make_ascii_uppercase();
+            if HsrConfig::multicast_spec_of(addr).is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`hsr.supervision-address` of HSR interface {} should \
+                         be in the format of {}XX, but got {addr}",
+                        self.base.name,
+                        HsrConfig::SUPERVISION_ADDRESS_PREFIX
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Include both ports if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(diff_conf) = self.hsr.as_mut()
+            && let Some(cur_conf) = current.hsr.as_ref()
+        {
+            let des_conf = desired.hsr.as_ref();
+            if diff_conf.port1.is_none() {
+                diff_conf.port1 = des_conf
+                    .and_then(|c| c.port1.clone())
+                    .or_else(|| cur_conf.port1.clone());
+            }
+            if diff_conf.port2.is_none() {
+                diff_conf.port2 = des_conf
+                    .and_then(|c| c.port2.clone())
+                    .or_else(|| cur_conf.port2.clone());
+            }
+        }
+    }
+
+    /// Include both ports when reverting HSR config.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(conf) = self.hsr.as_mut()
+            && let Some(pre_conf) = pre_apply.hsr.as_ref()
+        {
+            if conf.port1.is_none() {
+                conf.port1 = pre_conf.port1.clone();
+            }
+            if conf.port2.is_none() {
+                conf.port2 = pre_conf.port2.clone();
+            }
+        }
+    }
+
+    /// Kernel does not support changing any HSR property of existing HSR
+    /// interface.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_conf) = self.hsr.as_ref()
+            && let Some(cur_conf) = current.hsr.as_ref()
+        {
+            (des_conf.port1.is_some() && des_conf.port1 != cur_conf.port1)
+                || (des_conf.port2.is_some()
+                    && des_conf.port2 != cur_conf.port2)
+                || (des_conf.supervision_address.is_some()
+                    && des_conf.supervision_address
+                        != cur_conf.supervision_address)
+                || (des_conf.protocol.is_some()
+                    && des_conf.protocol != cur_conf.protocol)
+                || (des_conf.version.is_some()
+                    && des_conf.version != cur_conf.version)
+        } else {
+            false
+        }
+    }
+
+    /// Kernel only report version for HSR protocol.
+    fn sanitize_before_verify_iface_specfic(&mut self, current: &mut Self) {
+        if let Some(cur_conf) = current.hsr.as_mut()
+            && cur_conf.protocol == Some(HsrProtocol::Prp)
+        {
+            cur_conf.version = None;
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct HsrConfig {
+    /// The first port interface. Mandatory for new HSR interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port1: Option<String>,
+    /// The second port interface. Mandatory for new HSR interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port2: Option<String>,
+    /// Destination MAC address of supervision frames in the format of
+    /// `01:15:4E:00:01:XX`. Default to `01:15:4E:00:01:00`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supervision_address: Option<String>,
+    /// Redundancy protocol. Default to `hsr`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<HsrProtocol>,
+    /// HSR protocol version, 0 for HSRv0(IEC 62439-3:2010) or 1 for
+    /// HSRv1(IEC 62439-3:2012). Default to 0. Not valid for PRP.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub version: Option<u8>,
+}
+
+impl HsrConfig {
+    pub(crate) const SUPERVISION_ADDRESS_PREFIX: &str = "01:15:4E:00:01:";
+    const MAX_VERSION: u8 = 1;
+
+    /// Return the last byte of supervision address which is used by kernel
+    /// as `multicast_spec`. None means invalid supervision address.
+    pub(crate) fn multicast_spec_of(addr: &str) -> Option<u8> {
+        addr.to_ascii_uppercase()
+            .strip_prefix(Self::SUPERVISION_ADDRESS_PREFIX)
+            .filter(|s| s.len() == 2)
+            .and_then(|s| u8::from_str_radix(s, 16).ok())
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum HsrProtocol {
+    /// High-availability Seamless Redundancy.
+    /// Deserialize and serialize from/to `hsr`.
+    Hsr,
+    /// Parallel Redundancy Protocol.
+    /// Deserialize and serialize from/to `prp`.
+    Prp,
+}
@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// IP over InfiniBand(IPoIB) interface.
+///
+/// Physical IPoIB interface has no `base-iface`, while the partition
+/// key(pkey) sub-interface is created on top of `base-iface`.
+///
+/// The example yaml output of a [crate::NetworkState] with a IPoIB pkey
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ib0.8001
+///   type: infiniband
+///   state: up
+///   infiniband:
+///     base-iface: ib0
+///     mode: datagram
+///     pkey: 32769
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct InfiniBandInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub infiniband: Option<InfiniBandConfig>,
+}
+
+impl InfiniBandInterface {
+    pub fn new(name: String, infiniband: InfiniBandConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::InfiniBand,
+                ..Default::default()
+            },
+            infiniband: Some(infiniband),
+        }
+    }
+
+    fn is_pkey_iface(&self) -> bool {
+        self.infiniband
+            .as_ref()
+            .map(|c| c.base_iface.is_some())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for InfiniBandInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::InfiniBand,
+                ..Default::default()
+            },
+            infiniband: None,
+        }
+    }
+}
+
+impl NipartInterface for InfiniBandInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    /// Only pkey sub-interface is virtual.
+    fn is_virtual(&self) -> bool {
+        self.is_pkey_iface()
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.infiniband
+            .as_ref()
+            .and_then(|c| c.base_iface.as_deref())
+    }
+
+    /// * Copy base-iface and pkey from current pkey sub-interface, so that
+    ///   `state: absent` without `infiniband` section can still delete it.
+    /// * Both base-iface and pkey are mandatory for new pkey sub-interface.
+    /// * Set the full membership bit of pkey as kernel does.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let cur_ib_conf = current.and_then(|c| c.infiniband.as_ref());
+        if self.infiniband.is_none()
+            && let Some(cur_ib_conf) = cur_ib_conf
+            && cur_ib_conf.base_iface.is_some()
+        {
+            self.infiniband = Some(InfiniBandConfig {
+                base_iface: cur_ib_conf.base_iface.clone(),
+                pkey: cur_ib_conf.pkey,
+                ..Default::default()
+            });
+        }
+        if let Some(ib_conf) = self.infiniband.as_mut() {
+            if let Some(cur_ib_conf) = cur_ib_conf {
+                if ib_conf.base_iface.is_none() {
+                    ib_conf.base_iface = cur_ib_conf.base_iface.clone();
+                }
+                if ib_conf.pkey.is_none() {
+                    ib_conf.pkey = cur_ib_conf.pkey;
+                }
+            } else {
+                match (ib_conf.base_iface.as_ref(), ib_conf.pkey) {
+                    (Some(_), None) => {
+                        return Err(NipartError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "`infiniband.pkey` is mandatory for creating \
+                                 new IPoIB pkey interface {}",
+                                self.base.name
+                            ),
+                        ));
+                    }
+                    (None, Some(pkey))
+                        if pkey != InfiniBandConfig::DEFAULT_PKEY =>
+                    {
+                        return Err(NipartError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "`infiniband.base-iface` is mandatory for \
+                                 creating new IPoIB pkey interface {}",
+                                self.base.name
+                            ),
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+            if ib_conf.base_iface.is_some()
+                && let Some(pkey) = ib_conf.pkey
+            {
+                if pkey & !InfiniBandConfig::FULL_MEMBERSHIP_BIT == 0
+                    || pkey | InfiniBandConfig::FULL_MEMBERSHIP_BIT
+                        == InfiniBandConfig::DEFAULT_PKEY
+                {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid `infiniband.pkey` {pkey:#06x} for IPoIB \
+                             pkey interface {}, 0x0000, 0x8000, 0x7fff and \
+                             0xffff are reserved",
+                            self.base.name
+                        ),
+                    ));
+                }
+                ib_conf.pkey =
+                    Some(pkey | InfiniBandConfig::FULL_MEMBERSHIP_BIT);
+            }
+        }
+        Ok(())
+    }
+
+    /// Include base-iface and pkey if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_ib_conf) = desired.infiniband.as_ref()
+            && let Some(cur_ib_conf) = current.infiniband.as_ref()
+            && des_ib_conf != cur_ib_conf
+        {
+            let mut diff_ib_conf = des_ib_conf.clone();
+            if diff_ib_conf.base_iface.is_none() {
+                diff_ib_conf.base_iface = cur_ib_conf.base_iface.clone();
+            }
+            if diff_ib_conf.pkey.is_none() {
+                diff_ib_conf.pkey = cur_ib_conf.pkey;
+            }
+            self.infiniband = Some(diff_ib_conf);
+        }
+    }
+
+    /// Include base-iface and pkey when reverting IPoIB config.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(ib_conf) = self.infiniband.as_mut()
+            && let Some(pre_ib_conf) = pre_apply.infiniband.as_ref()
+        {
+            if ib_conf.base_iface.is_none() {
+                ib_conf.base_iface = pre_ib_conf.base_iface.clone();
+            }
+            if ib_conf.pkey.is_none() {
+                ib_conf.pkey = pre_ib_conf.pkey;
+            }
+        }
+    }
+
+    /// Kernel does not support changing base-iface or pkey of existing pkey
+    /// sub-interface.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_ib_conf) = self.infiniband.as_ref()
+            && let Some(cur_ib_conf) = current.infiniband.as_ref()
+        {
+            (des_ib_conf.base_iface.is_some()
+                && des_ib_conf.base_iface != cur_ib_conf.base_iface)
+                || (des_ib_conf.pkey.is_some()
+                    && des_ib_conf.pkey != cur_ib_conf.pkey)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct InfiniBandConfig {
+    /// IPoIB transport mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<InfiniBandMode>,
+    /// Parent IPoIB interface of pkey sub-interface.
+    /// Should be undefined for physical IPoIB interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Partition key, hex string like `0x8001` is also supported.
+    /// The full membership bit(`0x8000`) will be set automatically.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub pkey: Option<u16>,
+}
+
+impl InfiniBandConfig {
+    /// Default pkey used by physical IPoIB interface.
+    pub(crate) const DEFAULT_PKEY: u16 = 0xffff;
+    pub(crate) const FULL_MEMBERSHIP_BIT: u16 = 0x8000;
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum InfiniBandMode {
+    /// Unreliable datagram mode.
+    /// Deserialize and serialize from/to `datagram`.
+    Datagram,
+    /// Reliable connected mode.
+    /// Deserialize and serialize from/to `connected`.
+    Connected,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
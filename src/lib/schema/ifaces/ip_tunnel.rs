@@ -0,0 +1,299 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel IP tunnel interface, used by these interface types:
+///  * `gre`: GRE over IPv4.
+///  * `gretap`: Ethernet over GRE over IPv4.
+///  * `ip6gre`: GRE over IPv6.
+///  * `ipip`: IPv4 over IPv4.
+///  * `sit`: IPv6 over IPv4.
+///  * `ip6tnl`: IPv4 or IPv6 over IPv6, see [IpTunnelConfig.mode].
+///
+/// The example yaml output of a [crate::NetworkState] with a GRE interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: gre1
+///   type: gre
+///   state: up
+///   ip-tunnel:
+///     base-iface: eth1
+///     local: 192.0.2.1
+///     remote: 198.51.100.1
+///     ttl: 64
+///     key: 100
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpTunnelInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_tunnel: Option<IpTunnelConfig>,
+}
+
+impl IpTunnelInterface {
+    /// Create IP tunnel interface of specified type, please make sure
+    /// [InterfaceType::is_ip_tunnel()] is true for `iface_type`.
+    pub fn new(
+        name: String,
+        iface_type: InterfaceType,
+        ip_tunnel: IpTunnelConfig,
+    ) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type,
+                ..Default::default()
+            },
+            ip_tunnel: Some(ip_tunnel),
+        }
+    }
+
+    fn is_ipv6_underlay(&self) -> bool {
+        matches!(
+            self.base.iface_type,
+            InterfaceType::Ip6Gre | InterfaceType::Ip6Tnl
+        )
+    }
+
+    fn is_gre(&self) -> bool {
+        matches!(
+            self.base.iface_type,
+            InterfaceType::Gre | InterfaceType::GreTap | InterfaceType::Ip6Gre
+        )
+    }
+}
+
+impl Default for IpTunnelInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gre,
+                ..Default::default()
+            },
+            ip_tunnel: None,
+        }
+    }
+}
+
+impl NipartInterface for IpTunnelInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.ip_tunnel
+            .as_ref()
+            .and_then(|c| c.base_iface.as_deref())
+    }
+
+    /// * `remote` is mandatory for new IP tunnel.
+    /// * `local` and `remote` should match the IP family of tunnel type.
+    /// * `key` is only valid for GRE tunnels.
+    /// * `encap-limit` is only valid for IPv6 tunnels.
+    /// * `mode` is only valid for `ip6tnl`.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let is_ipv6_underlay = self.is_ipv6_underlay();
+        let is_gre = self.is_gre();
+        let iface_type = self.base.iface_type.clone();
+        let Some(tunnel_conf) = self.ip_tunnel.as_mut() else {
+            if current.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`ip-tunnel` section with `remote` is mandatory for \
+                         creating new {iface_type} interface {}",
+                        self.base.name
+                    ),
+                ));
+            }
+            return Ok(());
+        };
+        if current.is_none() && tunnel_conf.remote.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ip-tunnel.remote` is mandatory for creating new \
+                     {iface_type} interface {}",
+                    self.base.name
+                ),
+            ));
+        }
+        for (prop_name, addr) in [
+            ("local", tunnel_conf.local.as_ref()),
+            ("remote", tunnel_conf.remote.as_ref()),
+        ] {
+            if let Some(addr) = addr
+                && addr.is_ipv6() != is_ipv6_underlay
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`ip-tunnel.{prop_name}` {addr} of {iface_type} \
+                         interface {} should be {} address",
+                        self.base.name,
+                        if is_ipv6_underlay { "IPv6" } else { "IPv4" }
+                    ),
+                ));
+            }
+        }
+        if !is_gre && tunnel_conf.key.is_some() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ip-tunnel.key` is only supported by GRE tunnels, but \
+                     {iface_type} interface {} has it defined",
+                    self.base.name
+                ),
+            ));
+        }
+        if !is_ipv6_underlay && tunnel_conf.encap_limit.is_some() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ip-tunnel.encap-limit` is only supported by IPv6 \
+                     tunnels, but {iface_type} interface {} has it defined",
+                    self.base.name
+                ),
+            ));
+        }
+        if iface_type != InterfaceType::Ip6Tnl && tunnel_conf.mode.is_some() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ip-tunnel.mode` is only supported by ip6tnl, but \
+                     {iface_type} interface {} has it defined",
+                    self.base.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Include both local and remote address if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_conf) = desired.ip_tunnel.as_ref()
+            && let Some(cur_conf) = current.ip_tunnel.as_ref()
+            && des_conf != cur_conf
+        {
+            let mut diff_conf = des_conf.clone();
+            if diff_conf.local.is_none() {
+                diff_conf.local = cur_conf.local;
+            }
+            if diff_conf.remote.is_none() {
+                diff_conf.remote = cur_conf.remote;
+            }
+            self.ip_tunnel = Some(diff_conf);
+        }
+    }
+
+    /// Include both local and remote address when reverting.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(conf) = self.ip_tunnel.as_mut()
+            && let Some(pre_conf) = pre_apply.ip_tunnel.as_ref()
+        {
+            if conf.local.is_none() {
+                conf.local = pre_conf.local;
+            }
+            if conf.remote.is_none() {
+                conf.remote = pre_conf.remote;
+            }
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpTunnelConfig {
+    /// Underlay interface used for sending and receiving tunnel packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Source address of outgoing tunnel packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<IpAddr>,
+    /// Address of remote tunnel endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<IpAddr>,
+    /// TTL or hop limit of outgoing tunnel packets, 0 means inherit from
+    /// inner packet.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub ttl: Option<u8>,
+    /// GRE key used for both incoming and outgoing packets.
+    /// Only valid for `gre`, `gretap` and `ip6gre`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub key: Option<u32>,
+    /// Maximum level of IPv6 tunnel encapsulation, 255 means no limit.
+    /// Only valid for `ip6gre` and `ip6tnl`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub encap_limit: Option<u8>,
+    /// Inner protocol of `ip6tnl`, undefined means kernel default `any` for
+    /// new interface.
+    /// Only valid for `ip6tnl`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<IpTunnelMode>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum IpTunnelMode {
+    /// IPv4 or IPv6 over IPv6.
+    /// Deserialize and serialize from/to `any`.
+    Any,
+    /// IPv6 over IPv6.
+    /// Deserialize and serialize from/to `ip6ip6`.
+    #[serde(rename = "ip6ip6")]
+    Ip6Ip6,
+    /// IPv4 over IPv6.
+    /// Deserialize and serialize from/to `ipip6`.
+    #[serde(rename = "ipip6")]
+    IpIp6,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
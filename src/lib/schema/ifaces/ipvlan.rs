@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel IPVLAN interface.
+///
+/// The example yaml output of a [crate::NetworkState] with a IPVLAN
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ipvlan0
+///   type: ipvlan
+///   state: up
+///   ipvlan:
+///     base-iface: eth1
+///     mode: l3
+///     private: false
+///     vepa: false
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpVlanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipvlan: Option<IpVlanConfig>,
+}
+
+impl IpVlanInterface {
+    pub fn new(name: String, ipvlan: IpVlanConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::IpVlan,
+                ..Default::default()
+            },
+            ipvlan: Some(ipvlan),
+        }
+    }
+}
+
+impl Default for IpVlanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::IpVlan,
+                ..Default::default()
+            },
+            ipvlan: None,
+        }
+    }
+}
+
+impl NipartInterface for IpVlanInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.ipvlan.as_ref().and_then(|v| v.base_iface.as_deref())
+    }
+
+    /// * Copy base-iface and mode from current if not defined.
+    /// * Base-iface is mandatory for new IPVLAN.
+    /// * Mode should be kernel supported mode.
+    /// * Cannot enable both `private` and `vepa`.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let Some(ipvlan_conf) = self.ipvlan.as_mut() else {
+            if current.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`ipvlan` section is mandatory for creating new \
+                         IPVLAN {}",
+                        self.base.name
+                    ),
+                ));
+            }
+            return Ok(());
+        };
+        if let Some(cur_conf) = current.and_then(|c| c.ipvlan.as_ref()) {
+            if ipvlan_conf.base_iface.is_none() {
+                ipvlan_conf.base_iface = cur_conf.base_iface.clone();
+            }
+            if ipvlan_conf.mode.is_none() {
+                ipvlan_conf.mode = cur_conf.mode;
+            }
+        }
+        if ipvlan_conf.base_iface.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ipvlan.base-iface` is mandatory for creating new IPVLAN \
+                     {}",
+                    self.base.name
+                ),
+            ));
+        }
+        if ipvlan_conf.mode == Some(IpVlanMode::Unknown) {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ipvlan.mode` of IPVLAN {} should be one of: l2, l3, l3s",
+                    self.base.name
+                ),
+            ));
+        }
+        if ipvlan_conf.private == Some(true) && ipvlan_conf.vepa == Some(true) {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`ipvlan.private` and `ipvlan.vepa` of IPVLAN {} cannot \
+                     be both enabled",
+                    self.base.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Include both base-iface and mode if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_conf) = desired.ipvlan.as_ref()
+            && let Some(cur_conf) = current.ipvlan.as_ref()
+            && des_conf != cur_conf
+        {
+            let mut diff_conf = des_conf.clone();
+            if diff_conf.base_iface.is_none() {
+                diff_conf.base_iface = cur_conf.base_iface.clone();
+            }
+            if diff_conf.mode.is_none() {
+                diff_conf.mode = cur_conf.mode;
+            }
+            self.ipvlan = Some(diff_conf);
+        }
+    }
+
+    /// Include both base-iface and mode when reverting.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(conf) = self.ipvlan.as_mut()
+            && let Some(pre_conf) = pre_apply.ipvlan.as_ref()
+        {
+            if conf.base_iface.is_none() {
+                conf.base_iface = pre_conf.base_iface.clone();
+            }
+            if conf.mode.is_none() {
+                conf.mode = pre_conf.mode;
+            }
+        }
+    }
+
+    /// Kernel does not support changing base-iface of existing IPVLAN.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_conf) = self.ipvlan.as_ref()
+            && let Some(cur_conf) = current.ipvlan.as_ref()
+        {
+            des_conf.base_iface.is_some()
+                && des_conf.base_iface != cur_conf.base_iface
+        } else {
+            false
+        }
+    }
+
+    /// Kernel report `private` and `vepa` as false when not enabled.
+    fn sanitize_before_verify_iface_specfic(&mut self, current: &mut Self) {
+        if let Some(conf) = current.ipvlan.as_mut() {
+            if conf.private.is_none() {
+                conf.private = Some(false);
+            }
+            if conf.vepa.is_none() {
+                conf.vepa = Some(false);
+            }
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpVlanConfig {
+    /// Parent interface. Mandatory for new IPVLAN.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Default to `l3` if not defined for new IPVLAN.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<IpVlanMode>,
+    /// Only allow communication with the external network, not other
+    /// IPVLAN interfaces on the same parent.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub private: Option<bool>,
+    /// Forward all traffic to the external network, even for other IPVLAN
+    /// interfaces on the same parent.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub vepa: Option<bool>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum IpVlanMode {
+    /// Deserialize and serialize from/to `l2`.
+    L2,
+    /// Deserialize and serialize from/to `l3`.
+    L3,
+    /// Deserialize and serialize from/to `l3s`.
+    #[serde(rename = "l3s")]
+    L3S,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+//  * Fernando Fernandez Mancera <ffmancera@riseup.net>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel MAC VLAN interface.
+///
+/// The example yaml output of a [crate::NetworkState] with a MAC VLAN
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: mac0
+///   type: mac-vlan
+///   state: up
+///   mac-vlan:
+///     base-iface: eth1
+///     mode: bridge
+///     promiscuous: true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct MacVlanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_vlan: Option<MacVlanConfig>,
+}
+
+impl MacVlanInterface {
+    pub fn new(name: String, mac_vlan: MacVlanConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::MacVlan,
+                ..Default::default()
+            },
+            mac_vlan: Some(mac_vlan),
+        }
+    }
+}
+
+impl Default for MacVlanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::MacVlan,
+                ..Default::default()
+            },
+            mac_vlan: None,
+        }
+    }
+}
+
+impl NipartInterface for MacVlanInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.mac_vlan.as_ref().and_then(|v| v.base_iface.as_deref())
+    }
+
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        MacVlanConfig::sanitize(
+            self.base.name.as_str(),
+            self.base.iface_type.clone(),
+            self.mac_vlan.as_mut(),
+            current.and_then(|c| c.mac_vlan.as_ref()),
+        )
+    }
+
+    /// Include both base-iface and mode if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_conf) = desired.mac_vlan.as_ref()
+            && let Some(cur_conf) = current.mac_vlan.as_ref()
+            && des_conf != cur_conf
+        {
+            self.mac_vlan = Some(des_conf.with_context(cur_conf));
+        }
+    }
+
+    /// Include both base-iface and mode when reverting.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(conf) = self.mac_vlan.as_mut()
+            && let Some(pre_conf) = pre_apply.mac_vlan.as_ref()
+        {
+            *conf = conf.with_context(pre_conf);
+        }
+    }
+
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_conf) = self.mac_vlan.as_ref()
+            && let Some(cur_conf) = current.mac_vlan.as_ref()
+        {
+            des_conf.need_delete_before_change(cur_conf)
+        } else {
+            false
+        }
+    }
+}
+
+/// MAC VLAN configuration, also used by [crate::MacVtapInterface].
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct MacVlanConfig {
+    /// Parent interface. Mandatory for new interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Mandatory for new interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<MacVlanMode>,
+    /// Whether to put parent interface into promiscuous mode.
+    /// Only take effect in `passthru` mode, kernel always put parent
+    /// interface into promiscuous mode for other modes.
+    /// Deserialize from `promiscuous` or `accept-all-mac`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        alias = "accept-all-mac",
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub promiscuous: Option<bool>,
+}
+
+impl MacVlanConfig {
+    /// * Copy base-iface and mode from current if not defined.
+    /// * Base-iface and mode are mandatory for new interface.
+    /// * Mode should be kernel supported mode.
+    /// * Promiscuous can only be disabled in `passthru` mode.
+    pub(crate) fn sanitize(
+        iface_name: &str,
+        iface_type: InterfaceType,
+        desired: Option<&mut Self>,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let Some(desired) = desired else {
+            if current.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`{iface_type}` section is mandatory for creating new \
+                         {iface_type} interface {iface_name}"
+                    ),
+                ));
+            }
+            return Ok(());
+        };
+        if let Some(current) = current {
+            if desired.base_iface.is_none() {
+                desired.base_iface = current.base_iface.clone();
+            }
+            if desired.mode.is_none() {
+                desired.mode = current.mode;
+            }
+        }
+        if desired.base_iface.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`{iface_type}.base-iface` is mandatory for creating new \
+                     {iface_type} interface {iface_name}"
+                ),
+            ));
+        }
+        match desired.mode {
+            None => {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`{iface_type}.mode` is mandatory for creating new \
+                         {iface_type} interface {iface_name}"
+                    ),
+                ));
+            }
+            Some(MacVlanMode::Unknown) => {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`{iface_type}.mode` of interface {iface_name} should \
+                         be one of: vepa, bridge, private, passthru, source"
+                    ),
+                ));
+            }
+            Some(MacVlanMode::Passthru) => (),
+            Some(mode) => {
+                if desired.promiscuous == Some(false) {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Disabling `{iface_type}.promiscuous` is only \
+                             supported in passthru mode, but got {mode} mode \
+                             for interface {iface_name}"
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return a clone of self with base-iface and mode copied from `other` if
+    /// undefined.
+    pub(crate) fn with_context(&self, other: &Self) -> Self {
+        let mut ret = self.clone();
+        if ret.base_iface.is_none() {
+            ret.base_iface = other.base_iface.clone();
+        }
+        if ret.mode.is_none() {
+            ret.mode = other.mode;
+        }
+        ret
+    }
+
+    /// Kernel does not support changing base-iface or mode of existing
+    /// interface.
+    pub(crate) fn need_delete_before_change(&self, current: &Self) -> bool {
+        (self.base_iface.is_some() && self.base_iface != current.base_iface)
+            || (self.mode.is_some() && self.mode != current.mode)
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum MacVlanMode {
+    /// Deserialize and serialize from/to `vepa`.
+    Vepa,
+    /// Deserialize and serialize from/to `bridge`.
+    Bridge,
+    /// Deserialize and serialize from/to `private`.
+    Private,
+    /// Deserialize and serialize from/to `passthru`.
+    Passthru,
+    /// Deserialize and serialize from/to `source`.
+    Source,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
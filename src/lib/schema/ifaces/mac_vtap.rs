@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+//  * Fernando Fernandez Mancera <ffmancera@riseup.net>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, InterfaceType, JsonDisplay, MacVlanConfig, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel MAC VTAP interface, sharing the same configuration
+/// [MacVlanConfig] with [crate::MacVlanInterface].
+///
+/// The example yaml output of a [crate::NetworkState] with a MAC VTAP
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: macvtap0
+///   type: mac-vtap
+///   state: up
+///   mac-vtap:
+///     base-iface: eth1
+///     mode: passthru
+///     promiscuous: false
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct MacVtapInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_vtap: Option<MacVlanConfig>,
+}
+
+impl MacVtapInterface {
+    pub fn new(name: String, mac_vtap: MacVlanConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::MacVtap,
+                ..Default::default()
+            },
+            mac_vtap: Some(mac_vtap),
+        }
+    }
+}
+
+impl Default for MacVtapInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::MacVtap,
+                ..Default::default()
+            },
+            mac_vtap: None,
+        }
+    }
+}
+
+impl NipartInterface for MacVtapInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.mac_vtap.as_ref().and_then(|v| v.base_iface.as_deref())
+    }
+
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        MacVlanConfig::sanitize(
+            self.base.name.as_str(),
+            self.base.iface_type.clone(),
+            self.mac_vtap.as_mut(),
+            current.and_then(|c| c.mac_vtap.as_ref()),
+        )
+    }
+
+    /// Include both base-iface and mode if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_conf) = desired.mac_vtap.as_ref()
+            && let Some(cur_conf) = current.mac_vtap.as_ref()
+            && des_conf != cur_conf
+        {
+            self.mac_vtap = Some(des_conf.with_context(cur_conf));
+        }
+    }
+
+    /// Include both base-iface and mode when reverting.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(conf) = self.mac_vtap.as_mut()
+            && let Some(pre_conf) = pre_apply.mac_vtap.as_ref()
+        {
+            *conf = conf.with_context(pre_conf);
+        }
+    }
+
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_conf) = self.mac_vtap.as_ref()
+            && let Some(cur_conf) = current.mac_vtap.as_ref()
+        {
+            des_conf.need_delete_before_change(cur_conf)
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,453 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+//  * Fernando Fernandez Mancera <ffmancera@riseup.net>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay,
+    JsonDisplayHideSecrets, NipartError, NipartInterface,
+};
+
+/// Linux kernel MACsec(IEEE 802.1AE) interface.
+///
+/// The example yaml output of a [crate::NetworkState] with a MACsec
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: macsec0
+///   type: macsec
+///   state: up
+///   macsec:
+///     base-iface: eth1
+///     port: 1
+///     cipher: gcm-aes-128
+///     encrypt: true
+///     protect: true
+///     validation: strict
+///     mka-cak: <_hidden_>
+///     mka-ckn: <_hidden_>
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplayHideSecrets,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct MacSecInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub macsec: Option<MacSecConfig>,
+}
+
+impl MacSecInterface {
+    pub fn new(name: String, macsec: MacSecConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::MacSec,
+                ..Default::default()
+            },
+            macsec: Some(macsec),
+        }
+    }
+}
+
+impl Default for MacSecInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::MacSec,
+                ..Default::default()
+            },
+            macsec: None,
+        }
+    }
+}
+
+impl NipartInterface for MacSecInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.macsec.as_ref().and_then(|v| v.base_iface.as_deref())
+    }
+
+    fn hide_secrets_iface_specific(&mut self) {
+        if let Some(macsec_conf) = self.macsec.as_mut() {
+            macsec_conf.hide_secrets();
+        }
+    }
+
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        if let Some(macsec_conf) = self.macsec.as_mut() {
+            macsec_conf.sanitize(
+                current.and_then(|c| c.macsec.as_ref()),
+                self.base.name.as_str(),
+            )?;
+        } else if current.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Need macsec section for creating MACsec interface {}",
+                    self.base.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The MKA keys are for daemon storage only and cannot be queried from
+    /// kernel, hence removed from verification.
+    fn sanitize_before_verify_iface_specfic(&mut self, current: &mut Self) {
+        if let Some(macsec_conf) = self.macsec.as_mut() {
+            macsec_conf.remove_secrets();
+        }
+        if let Some(macsec_conf) = current.macsec.as_mut() {
+            macsec_conf.remove_secrets();
+        }
+    }
+
+    /// Include base-iface if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(diff_conf) = self.macsec.as_mut()
+            && diff_conf.base_iface.is_none()
+        {
+            diff_conf.base_iface = desired
+                .parent()
+                .or_else(|| current.parent())
+                .map(|p| p.to_string());
+        }
+    }
+
+    /// Include base-iface when reverting MACsec config.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(conf) = self.macsec.as_mut()
+            && conf.base_iface.is_none()
+        {
+            conf.base_iface = pre_apply.parent().map(|p| p.to_string());
+        }
+    }
+
+    /// Kernel does not support changing base-iface, SCI, port or cipher of
+    /// existing MACsec interface.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_conf) = self.macsec.as_ref()
+            && let Some(cur_conf) = current.macsec.as_ref()
+        {
+            (des_conf.base_iface.is_some()
+                && des_conf.base_iface != cur_conf.base_iface)
+                || (des_conf.sci.is_some() && des_conf.sci != cur_conf.sci)
+                || (des_conf.port.is_some() && des_conf.port != cur_conf.port)
+                || (des_conf.cipher.is_some()
+                    && des_conf.cipher != cur_conf.cipher)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonDisplayHideSecrets,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct MacSecConfig {
+    /// Parent interface. Mandatory for new MACsec interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Secure Channel Identifier. Default to MAC address of the parent
+    /// interface with `port` appended.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u64_or_string"
+    )]
+    pub sci: Option<u64>,
+    /// Port number of Secure Channel Identifier. Cannot be 0.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub port: Option<u16>,
+    /// Cipher suite. Default to `gcm-aes-128`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cipher: Option<MacSecCipher>,
+    /// Whether to encrypt the payload. When disabled, only integrity is
+    /// protected.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub encrypt: Option<bool>,
+    /// Whether to protect the integrity of outgoing frames.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub protect: Option<bool>,
+    /// Validation mode of incoming frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation: Option<MacSecValidate>,
+    /// Hex encoded static MKA Connectivity Association Key(CAK) in 32 or 64
+    /// characters, will be replaced by `<_hidden_>` in display, debug or
+    /// query output. When defined, MKA session is started by wpa_supplicant
+    /// on `base-iface`. Existing MKA session is untouched if defined as None
+    /// or `<_hidden_>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mka_cak: Option<String>,
+    /// Hex encoded static MKA Connectivity Association Key Name(CKN) up to
+    /// 64 characters, will be replaced by `<_hidden_>` in display, debug or
+    /// query output. Existing MKA session is untouched if defined as None or
+    /// `<_hidden_>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mka_ckn: Option<String>,
+}
+
+impl MacSecConfig {
+    const MKA_CAK_LENS: [usize; 2] = [32, 64];
+    const MKA_CKN_MAX_LEN: usize = 64;
+
+    pub fn hide_secrets(&mut self) {
+        if self.mka_cak.is_some() {
+            self.mka_cak =
+                Some(crate::NetworkState::HIDE_SECRET_STR.to_string());
+        }
+        if self.mka_ckn.is_some() {
+            self.mka_ckn =
+                Some(crate::NetworkState::HIDE_SECRET_STR.to_string());
+        }
+    }
+
+    pub(crate) fn remove_secrets(&mut self) {
+        self.mka_cak = None;
+        self.mka_ckn = None;
+    }
+
+    /// * Need `base-iface` for creating interface
+    /// * Port cannot be 0
+    /// * Cannot use `HIDE_SECRET_STR` when current is None
+    /// * Discard `mka-cak` and `mka-ckn` if set to `HIDE_SECRET_STR`
+    /// * `mka-cak` and `mka-ckn` should be hex string in valid length and
+    ///   defined together.
+    /// * MKA cannot be used with explicit `sci` or XPN cipher, as
+    ///   wpa_supplicant always uses MAC address with `port` as SCI and does not
+    ///   support XPN.
+    pub(crate) fn sanitize(
+        &mut self,
+        current: Option<&Self>,
+        iface_name: &str,
+    ) -> Result<(), NipartError> {
+        if let Some(current) = current {
+            if self.base_iface.is_none() {
+                self.base_iface = current.base_iface.clone();
+            }
+        } else if self.base_iface.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Need `macsec.base-iface` for creating MACsec interface \
+                     {iface_name}"
+                ),
+            ));
+        }
+        if self.port == Some(0) {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`macsec.port` of MACsec interface {iface_name} cannot be \
+                     0"
+                ),
+            ));
+        }
+
+        for (name, key) in [
+            ("mka-cak", &mut self.mka_cak),
+            ("mka-ckn", &mut self.mka_ckn),
+        ] {
+            if key.as_deref() == Some(crate::NetworkState::HIDE_SECRET_STR) {
+                if current.is_none() {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "`macsec.{name}` cannot be set to {} for creating \
+                             new MACsec interface {iface_name}",
+                            crate::NetworkState::HIDE_SECRET_STR,
+                        ),
+                    ));
+                } else {
+                    *key = None;
+                }
+            }
+        }
+
+        if let Some(cak) = self.mka_cak.as_deref()
+            && (!Self::MKA_CAK_LENS.contains(&cak.len()) || !is_hex_str(cak))
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`macsec.mka-cak` of MACsec interface {iface_name} should \
+                     be hex string in length of 32 or 64"
+                ),
+            ));
+        }
+        if let Some(ckn) = self.mka_ckn.as_deref()
+            && (ckn.is_empty()
+                || ckn.len() > Self::MKA_CKN_MAX_LEN
+                || ckn.len() % 2 != 0
+                || !is_hex_str(ckn))
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`macsec.mka-ckn` of MACsec interface {iface_name} should \
+                     be hex string in even length up to {}",
+                    Self::MKA_CKN_MAX_LEN
+                ),
+            ));
+        }
+        if self.mka_cak.is_some() != self.mka_ckn.is_some() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`macsec.mka-cak` and `macsec.mka-ckn` should be defined \
+                     together for MACsec interface {iface_name}"
+                ),
+            ));
+        }
+        if self.mka_cak.is_some() {
+            if self.sci.is_some() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`macsec.sci` cannot be used with MKA keys for MACsec \
+                         interface {iface_name}, please use `macsec.port` \
+                         instead"
+                    ),
+                ));
+            }
+            if let Some(
+                cipher @ (MacSecCipher::GcmAesXpn128
+                | MacSecCipher::GcmAesXpn256),
+            ) = self.cipher
+            {
+                return Err(NipartError::new(
+                    ErrorKind::NoSupport,
+                    format!(
+                        "MKA does not support cipher {cipher} for MACsec \
+                         interface {iface_name}"
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_hex_str(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl std::fmt::Debug for MacSecConfig {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> Result<(), std::fmt::Error> {
+        f.debug_struct("MacSecConfig")
+            .field("base_iface", &self.base_iface)
+            .field("sci", &self.sci)
+            .field("port", &self.port)
+            .field("cipher", &self.cipher)
+            .field("encrypt", &self.encrypt)
+            .field("protect", &self.protect)
+            .field("validation", &self.validation)
+            .field(
+                "mka_cak",
+                &self
+                    .mka_cak
+                    .as_ref()
+                    .map(|_| crate::NetworkState::HIDE_SECRET_STR),
+            )
+            .field(
+                "mka_ckn",
+                &self
+                    .mka_ckn
+                    .as_ref()
+                    .map(|_| crate::NetworkState::HIDE_SECRET_STR),
+            )
+            .finish()
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum MacSecCipher {
+    /// Deserialize and serialize from/to `gcm-aes-128`.
+    #[serde(rename = "gcm-aes-128")]
+    GcmAes128,
+    /// Deserialize and serialize from/to `gcm-aes-256`.
+    #[serde(rename = "gcm-aes-256")]
+    GcmAes256,
+    /// Deserialize and serialize from/to `gcm-aes-xpn-128`.
+    #[serde(rename = "gcm-aes-xpn-128")]
+    GcmAesXpn128,
+    /// Deserialize and serialize from/to `gcm-aes-xpn-256`.
+    #[serde(rename = "gcm-aes-xpn-256")]
+    GcmAesXpn256,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum MacSecValidate {
+    /// Do not validate incoming frames.
+    Disabled,
+    /// Validate incoming frames but do not discard invalid ones.
+    Check,
+    /// Discard invalid incoming frames.
+    Strict,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
@@ -5,13 +5,24 @@ mod bond;
 mod bridge_vlan;
 mod dummy;
 mod ethernet;
+mod hsr;
+mod infiniband;
 mod inter_ifaces;
+mod ip_tunnel;
+mod ipvlan;
 mod linux_bridge;
 mod loopback;
+mod mac_vlan;
+mod mac_vtap;
+mod macsec;
 mod ovs_bridge;
 mod ovs_iface;
+mod sriov;
+mod tun;
 mod unknown;
 mod vlan;
+mod vrf;
+mod vxlan;
 mod wifi;
 mod wireguard;
 
@@ -27,20 +38,31 @@ pub use self::{
     },
     dummy::DummyInterface,
     ethernet::{EthernetConfig, EthernetDuplex, EthernetInterface, VethConfig},
+    hsr::{HsrConfig, HsrInterface, HsrProtocol},
+    infiniband::{InfiniBandConfig, InfiniBandInterface, InfiniBandMode},
     inter_ifaces::Interfaces,
+    ip_tunnel::{IpTunnelConfig, IpTunnelInterface, IpTunnelMode},
+    ipvlan::{IpVlanConfig, IpVlanInterface, IpVlanMode},
     linux_bridge::{
         LinuxBridgeConfig, LinuxBridgeInterface,
         LinuxBridgeMulticastRouterType, LinuxBridgeOptions,
         LinuxBridgePortConfig, LinuxBridgeStpOptions,
     },
     loopback::LoopbackInterface,
+    mac_vlan::{MacVlanConfig, MacVlanInterface, MacVlanMode},
+    mac_vtap::MacVtapInterface,
+    macsec::{MacSecCipher, MacSecConfig, MacSecInterface, MacSecValidate},
     ovs_bridge::{OvsBridgeConfig, OvsBridgeInterface, OvsBridgePortConfig},
     ovs_iface::OvsInterface,
+    sriov::{SrIovConfig, SrIovVfConfig},
+    tun::{TunConfig, TunInterface, TunMode},
     unknown::UnknownInterface,
     vlan::{
         VlanConfig, VlanInterface, VlanProtocol, VlanQosMapping,
         VlanRegistrationProtocol,
     },
+    vrf::{VrfConfig, VrfInterface},
+    vxlan::{VxlanConfig, VxlanInterface},
     wifi::{
         WifiAuthType, WifiCfgInterface, WifiConfig, WifiPhyInterface, WifiState,
     },
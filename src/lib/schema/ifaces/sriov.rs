@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, JsonDisplay, NipartError};
+
+/// Single Root I/O Virtualization(SRIOV) configuration of physical function
+/// (PF). The example yaml output of [crate::NetworkState] with SR-IOV
+/// enabled ethernet interface would be:
+/// ```yml
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+///   ethernet:
+///     sr-iov:
+///       total-vfs: 2
+///       vfs:
+///       - id: 0
+///         iface-name: eth1v0
+///         mac-address: 00:11:22:33:00:FF
+///         spoof-check: true
+///         trust: false
+///         min-tx-rate: 0
+///         max-tx-rate: 0
+///         vlan-id: 0
+///         qos: 0
+///       - id: 1
+///         iface-name: eth1v1
+///         mac-address: 00:11:22:33:00:EF
+///         spoof-check: true
+///         trust: false
+///         min-tx-rate: 0
+///         max-tx-rate: 0
+///         vlan-id: 0
+///         qos: 0
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct SrIovConfig {
+    /// The number of VFs enabled on PF.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub total_vfs: Option<u32>,
+    /// VF specific configurations.
+    /// * Setting to `[]` has no effect.
+    /// * VFs not mentioned will not be changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vfs: Option<Vec<SrIovVfConfig>>,
+}
+
+impl SrIovConfig {
+    pub(crate) const MAX_VLAN_ID: u32 = 4094;
+    pub(crate) const MAX_QOS: u32 = 7;
+
+    /// * Sort VFs by ID and reject duplicate VF ID.
+    /// * VF ID should be smaller than total VFs.
+    /// * Validate VLAN ID, QoS and TX rate range.
+    /// * Uppercase MAC address.
+    pub(crate) fn sanitize(
+        &mut self,
+        iface_name: &str,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let total_vfs = self
+            .total_vfs
+            .or_else(|| current.and_then(|c| c.total_vfs))
+            .unwrap_or_default();
+        if let Some(vfs) = self.vfs.as_mut() {
+            vfs.sort_unstable_by_key(|vf| vf.id);
+            if let Some(dup) =
+                vfs.windows(2).find(|w| w[0].id == w[1].id).map(|w| w[0].id)
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Duplicate SR-IOV VF ID {dup} defined for interface \
+                         {iface_name}"
+                    ),
+                ));
+            }
+            for vf in vfs.iter_mut() {
+                vf.sanitize(iface_name, total_vfs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Only verify VFs mentioned in desired state, and ignore query only
+    /// property `iface-name`.
+    pub(crate) fn sanitize_before_verify(&mut self, current: &mut Self) {
+        if let Some(vfs) = self.vfs.as_mut() {
+            for vf in vfs.iter_mut() {
+                vf.iface_name = String::new();
+            }
+            if let Some(cur_vfs) = current.vfs.as_mut() {
+                cur_vfs
+                    .retain(|cur_vf| vfs.iter().any(|vf| vf.id == cur_vf.id));
+                for cur_vf in cur_vfs.iter_mut() {
+                    cur_vf.iface_name = String::new();
+                }
+            }
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct SrIovVfConfig {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    pub id: u32,
+    /// Interface name for this VF, only for querying, will be ignored
+    /// when applying network state.
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub iface_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub spoof_check: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub trust: Option<bool>,
+    /// Minimum TX rate in Mbps. 0 means disabled.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub min_tx_rate: Option<u32>,
+    /// Maximum TX rate in Mbps. 0 means disabled.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub max_tx_rate: Option<u32>,
+    /// VLAN ID, 0 means VLAN disabled.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub vlan_id: Option<u32>,
+    /// VLAN priority(802.1p), only valid when VLAN ID is not 0.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub qos: Option<u32>,
+}
+
+impl SrIovVfConfig {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+
+    fn sanitize(
+        &mut self,
+        iface_name: &str,
+        total_vfs: u32,
+    ) -> Result<(), NipartError> {
+        if self.id >= total_vfs {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "SR-IOV VF ID {} of interface {iface_name} exceeded the \
+                     total VF count {total_vfs}",
+                    self.id
+                ),
+            ));
+        }
+        self.iface_name = String::new();
+        if let Some(mac) = self.mac_address.as_mut() {
+            mac.make_ascii_uppercase();
+        }
+        if let Some(vlan_id) = self.vlan_id
+            && vlan_id > SrIovConfig::MAX_VLAN_ID
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "SR-IOV VF {} of interface {iface_name} has invalid VLAN \
+                     ID {vlan_id}, should be in the range of 0 to {}",
+                    self.id,
+                    SrIovConfig::MAX_VLAN_ID
+                ),
+            ));
+        }
+        if let Some(qos) = self.qos {
+            if qos > SrIovConfig::MAX_QOS {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "SR-IOV VF {} of interface {iface_name} has invalid \
+                         QoS {qos}, should be in the range of 0 to {}",
+                        self.id,
+                        SrIovConfig::MAX_QOS
+                    ),
+                ));
+            }
+            if qos != 0 && self.vlan_id == Some(0) {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "SR-IOV VF {} of interface {iface_name} has QoS {qos} \
+                         defined without VLAN ID",
+                        self.id
+                    ),
+                ));
+            }
+        }
+        if let (Some(min_rate), Some(max_rate)) =
+            (self.min_tx_rate, self.max_tx_rate)
+            && max_rate != 0
+            && min_rate > max_rate
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "SR-IOV VF {} of interface {iface_name} has min-tx-rate \
+                     {min_rate} bigger than max-tx-rate {max_rate}",
+                    self.id
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel TUN/TAP interface.
+///
+/// Nipart can only create persistent TUN/TAP interface, the application
+/// should attach to it afterwards.
+///
+/// The example yaml output of a [crate::NetworkState] with a TAP interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: tap0
+///   type: tun
+///   state: up
+///   tun:
+///     mode: tap
+///     owner: 1000
+///     group: 1000
+///     multi-queue: true
+///     persist: true
+///     vnet-hdr: true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct TunInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tun: Option<TunConfig>,
+}
+
+impl TunInterface {
+    pub fn new(name: String, tun: TunConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::Tun,
+                ..Default::default()
+            },
+            tun: Some(tun),
+        }
+    }
+}
+
+impl Default for TunInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Tun,
+                ..Default::default()
+            },
+            tun: None,
+        }
+    }
+}
+
+impl NipartInterface for TunInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    /// * New TUN/TAP interface is created in `tun` mode and persistent by
+    ///   default.
+    /// * Changing persistent TUN/TAP to non-persistent is not allowed as kernel
+    ///   will remove it once nipart detached.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let cur_tun_conf = current.and_then(|c| c.tun.as_ref());
+        if cur_tun_conf.is_none() {
+            let tun_conf = self.tun.get_or_insert_with(TunConfig::default);
+            if tun_conf.mode.is_none() {
+                tun_conf.mode = Some(TunMode::Tun);
+            }
+            if tun_conf.persist.is_none() {
+                tun_conf.persist = Some(true);
+            }
+        }
+        if let Some(tun_conf) = self.tun.as_ref()
+            && tun_conf.persist == Some(false)
+            && cur_tun_conf.and_then(|c| c.persist) != Some(false)
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Non-persistent TUN/TAP interface {} can only be created \
+                     by the application holding its file descriptor, please \
+                     use `state: absent` to remove it",
+                    self.base.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Removed owner or group is shown as undefined in current.
+    fn sanitize_before_verify_iface_specfic(&mut self, _current: &mut Self) {
+        if let Some(tun_conf) = self.tun.as_mut() {
+            if tun_conf.owner == Some(TunConfig::UNSET_ID) {
+                tun_conf.owner = None;
+            }
+            if tun_conf.group == Some(TunConfig::UNSET_ID) {
+                tun_conf.group = None;
+            }
+        }
+    }
+
+    /// Include mode and multi-queue which are required for attaching to
+    /// existing TUN/TAP interface.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_tun_conf) = desired.tun.as_ref()
+            && let Some(cur_tun_conf) = current.tun.as_ref()
+            && des_tun_conf != cur_tun_conf
+        {
+            let mut diff_tun_conf = des_tun_conf.clone();
+            if diff_tun_conf.mode.is_none() {
+                diff_tun_conf.mode = cur_tun_conf.mode;
+            }
+            if diff_tun_conf.multi_queue.is_none() {
+                diff_tun_conf.multi_queue = cur_tun_conf.multi_queue;
+            }
+            self.tun = Some(diff_tun_conf);
+        }
+    }
+
+    /// Kernel does not support changing mode, multi-queue and vnet-hdr of
+    /// existing TUN/TAP interface without impacting its running
+    /// application.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_tun_conf) = self.tun.as_ref()
+            && let Some(cur_tun_conf) = current.tun.as_ref()
+        {
+            (des_tun_conf.mode.is_some()
+                && des_tun_conf.mode != cur_tun_conf.mode)
+                || (des_tun_conf.multi_queue.is_some()
+                    && des_tun_conf.multi_queue != cur_tun_conf.multi_queue)
+                || (des_tun_conf.vnet_hdr.is_some()
+                    && des_tun_conf.vnet_hdr != cur_tun_conf.vnet_hdr)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct TunConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<TunMode>,
+    /// User ID allowed to attach to this interface.
+    /// Set to [TunConfig::UNSET_ID] to remove the restriction.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub owner: Option<u32>,
+    /// Group ID allowed to attach to this interface.
+    /// Set to [TunConfig::UNSET_ID] to remove the restriction.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub group: Option<u32>,
+    /// Allow multiple file descriptors(queues) to attach to this interface.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub multi_queue: Option<bool>,
+    /// Keep the interface after all file descriptors closed.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub persist: Option<bool>,
+    /// Prepend `virtio_net_hdr` to packets.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub vnet_hdr: Option<bool>,
+}
+
+impl TunConfig {
+    /// Kernel `INVALID_UID` and `INVALID_GID`, i.e. `4294967295`, used for
+    /// removing `owner` or `group` of TUN/TAP interface.
+    pub const UNSET_ID: u32 = u32::MAX;
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum TunMode {
+    /// Layer 3 IP packets.
+    /// Deserialize and serialize from/to `tun`.
+    Tun,
+    /// Layer 2 ethernet frames.
+    /// Deserialize and serialize from/to `tap`.
+    Tap,
+    /// Deserialize and serialize from/to `unknown`.
+    Unknown,
+}
@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+//  * Fernando Fernandez Mancera <ffmancera@riseup.net>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel Virtual Routing and Forwarding(VRF) interface.
+///
+/// The example yaml output of a [crate::NetworkState] with a VRF interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: vrf0
+///   type: vrf
+///   state: up
+///   vrf:
+///     ports:
+///     - eth1
+///     - eth2
+///     route-table-id: 100
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct VrfInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vrf: Option<VrfConfig>,
+}
+
+impl VrfInterface {
+    pub fn new(name: String, vrf: VrfConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::Vrf,
+                ..Default::default()
+            },
+            vrf: Some(vrf),
+        }
+    }
+
+    pub(crate) fn table_id(&self) -> Option<u32> {
+        self.vrf.as_ref().and_then(|v| v.table_id)
+    }
+}
+
+impl Default for VrfInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Vrf,
+                ..Default::default()
+            },
+            vrf: None,
+        }
+    }
+}
+
+impl NipartInterface for VrfInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn ports(&self) -> Option<Vec<&str>> {
+        self.vrf.as_ref().and_then(|vrf_conf| {
+            vrf_conf
+                .ports
+                .as_ref()
+                .map(|ports| ports.iter().map(|p| p.as_str()).collect())
+        })
+    }
+
+    /// * Route table ID is mandatory for new VRF.
+    /// * Route table ID cannot be 0 or main(254) or local(255) route table.
+    /// * Sort and dedup ports.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        let cur_table_id = current.and_then(|c| c.table_id());
+        if let Some(vrf_conf) = self.vrf.as_mut() {
+            if vrf_conf.table_id.is_none() {
+                vrf_conf.table_id = cur_table_id;
+            }
+            if let Some(ports) = vrf_conf.ports.as_mut() {
+                ports.sort_unstable();
+                ports.dedup();
+            }
+        }
+        match self.table_id() {
+            None | Some(0) => {
+                if current.is_none() {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "`vrf.route-table-id` is mandatory and cannot be \
+                             0 for creating new VRF {}",
+                            self.name()
+                        ),
+                    ));
+                }
+            }
+            Some(t)
+                if t == VrfConfig::MAIN_ROUTE_TABLE_ID
+                    || t == VrfConfig::LOCAL_ROUTE_TABLE_ID =>
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "VRF {} cannot use reserved route table {t}",
+                        self.name()
+                    ),
+                ));
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Always include route table ID when VRF config changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(vrf_conf) = self.vrf.as_mut()
+            && vrf_conf.table_id.is_none()
+        {
+            vrf_conf.table_id = desired.table_id().or(current.table_id());
+        }
+    }
+
+    /// Always include route table ID when reverting VRF config.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(vrf_conf) = self.vrf.as_mut()
+            && vrf_conf.table_id.is_none()
+        {
+            vrf_conf.table_id = pre_apply.table_id();
+        }
+    }
+
+    /// Kernel does not support changing route table ID of existing VRF.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        self.is_up()
+            && self.table_id().is_some()
+            && current.table_id().is_some()
+            && self.table_id() != current.table_id()
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct VrfConfig {
+    /// Port list.
+    /// Deserialize from `port` or `ports`.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "port")]
+    pub ports: Option<Vec<String>>,
+    /// Route table ID of this VRF. Cannot be 0, main(254) or local(255)
+    /// route table.
+    /// Serialize and deserialize to/from `route-table-id`.
+    #[serde(
+        rename = "route-table-id",
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub table_id: Option<u32>,
+}
+
+impl VrfConfig {
+    pub(crate) const MAIN_ROUTE_TABLE_ID: u32 = 254;
+    pub(crate) const LOCAL_ROUTE_TABLE_ID: u32 = 255;
+}
@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is based on the work of nmstate project(https://nmstate.io/) which
+// is under license of Apache 2.0, authors of original file are:
+//  * Gris Ge <fge@redhat.com>
+//  * Fernando Fernandez Mancera <ffmancera@riseup.net>
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, JsonDisplay, NipartError,
+    NipartInterface,
+};
+
+/// Linux kernel Virtual eXtensible Local Area Network(VXLAN) interface.
+///
+/// The example yaml output of a [crate::NetworkState] with a VXLAN interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: vxlan100
+///   type: vxlan
+///   state: up
+///   vxlan:
+///     base-iface: eth1
+///     id: 100
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+///     destination-port: 4789
+///     learning: true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct VxlanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vxlan: Option<VxlanConfig>,
+}
+
+impl VxlanInterface {
+    pub fn new(name: String, vxlan: VxlanConfig) -> Self {
+        Self {
+            base: BaseInterface {
+                name: name.to_string(),
+                iface_type: InterfaceType::Vxlan,
+                ..Default::default()
+            },
+            vxlan: Some(vxlan),
+        }
+    }
+}
+
+impl Default for VxlanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Vxlan,
+                ..Default::default()
+            },
+            vxlan: None,
+        }
+    }
+}
+
+impl NipartInterface for VxlanInterface {
+    fn base_iface(&self) -> &BaseInterface {
+        &self.base
+    }
+
+    fn base_iface_mut(&mut self) -> &mut BaseInterface {
+        &mut self.base
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.vxlan.as_ref().and_then(|v| v.base_iface.as_deref())
+    }
+
+    /// * VXLAN ID is mandatory for new VXLAN.
+    /// * Copy the VXLAN ID and base-iface from current if not defined.
+    /// * Local and remote address should be in the same IP family.
+    fn sanitize_iface_specfic(
+        &mut self,
+        current: Option<&Self>,
+    ) -> Result<(), NipartError> {
+        if let Some(vxlan_conf) = self.vxlan.as_mut() {
+            if let Some(cur_vxlan_conf) =
+                current.as_ref().and_then(|c| c.vxlan.as_ref())
+            {
+                if vxlan_conf.id.is_none() {
+                    vxlan_conf.id = cur_vxlan_conf.id;
+                }
+                if vxlan_conf.base_iface.is_none() {
+                    vxlan_conf.base_iface = cur_vxlan_conf.base_iface.clone();
+                }
+            } else if vxlan_conf.id.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`vxlan.id` is mandatory for creating new VXLAN {}",
+                        self.name()
+                    ),
+                ));
+            }
+            if let Some(id) = vxlan_conf.id
+                && id > VxlanConfig::MAX_VNI
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`vxlan.id` {id} of VXLAN {} exceeded the maximum {}",
+                        self.name(),
+                        VxlanConfig::MAX_VNI
+                    ),
+                ));
+            }
+            if let (Some(local), Some(remote)) =
+                (vxlan_conf.local, vxlan_conf.remote)
+                && local.is_ipv4() != remote.is_ipv4()
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "`vxlan.local` {local} and `vxlan.remote` {remote} of \
+                         VXLAN {} are not in the same IP family",
+                        self.name()
+                    ),
+                ));
+            }
+        } else if current.is_none() {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "`vxlan` section with `id` is mandatory for creating new \
+                     VXLAN {}",
+                    self.name()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Include both base-iface and VXLAN ID if changed.
+    fn include_diff_context_iface_specific(
+        &mut self,
+        desired: &Self,
+        current: &Self,
+    ) {
+        if let Some(des_vxlan_conf) = desired.vxlan.as_ref()
+            && let Some(cur_vxlan_conf) = current.vxlan.as_ref()
+            && des_vxlan_conf != cur_vxlan_conf
+        {
+            let mut diff_vxlan_conf = des_vxlan_conf.clone();
+            if diff_vxlan_conf.base_iface.is_none() {
+                diff_vxlan_conf.base_iface = cur_vxlan_conf.base_iface.clone();
+            }
+            if diff_vxlan_conf.id.is_none() {
+                diff_vxlan_conf.id = cur_vxlan_conf.id;
+            }
+            self.vxlan = Some(diff_vxlan_conf);
+        }
+    }
+
+    /// Include both base-iface and VXLAN ID when reverting VXLAN config.
+    fn include_revert_context_iface_specific(
+        &mut self,
+        _desired: &Self,
+        pre_apply: &Self,
+    ) {
+        if let Some(vxlan_conf) = self.vxlan.as_mut()
+            && let Some(pre_vxlan_conf) = pre_apply.vxlan.as_ref()
+        {
+            if vxlan_conf.id.is_none() {
+                vxlan_conf.id = pre_vxlan_conf.id;
+            }
+            if vxlan_conf.base_iface.is_none() {
+                vxlan_conf.base_iface = pre_vxlan_conf.base_iface.clone();
+            }
+        }
+    }
+
+    /// Kernel does not support changing VXLAN ID, base-iface, local, remote
+    /// or destination port of existing VXLAN.
+    fn need_delete_before_change(&self, current: &Self) -> bool {
+        if self.is_up()
+            && let Some(des_vxlan_conf) = self.vxlan.as_ref()
+            && let Some(cur_vxlan_conf) = current.vxlan.as_ref()
+        {
+            (des_vxlan_conf.id.is_some()
+                && des_vxlan_conf.id != cur_vxlan_conf.id)
+                || (des_vxlan_conf.base_iface.is_some()
+                    && des_vxlan_conf.base_iface != cur_vxlan_conf.base_iface)
+                || (des_vxlan_conf.local.is_some()
+                    && des_vxlan_conf.local != cur_vxlan_conf.local)
+                || (des_vxlan_conf.remote.is_some()
+                    && des_vxlan_conf.remote != cur_vxlan_conf.remote)
+                || (des_vxlan_conf.dst_port.is_some()
+                    && des_vxlan_conf.dst_port != cur_vxlan_conf.dst_port)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct VxlanConfig {
+    /// Underlay interface used for sending and receiving VXLAN packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// VXLAN Network Identifier(VNI), maximum is 16777215.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub id: Option<u32>,
+    /// Source IP address of outgoing VXLAN packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<IpAddr>,
+    /// Unicast or multicast destination IP address of outgoing VXLAN
+    /// packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<IpAddr>,
+    /// UDP destination port of remote VXLAN tunnel endpoint.
+    /// Serialize and deserialize to/from `destination-port`.
+    #[serde(
+        rename = "destination-port",
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub dst_port: Option<u16>,
+    /// Learn the MAC addresses of remote VXLAN tunnel endpoints.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub learning: Option<bool>,
+}
+
+impl VxlanConfig {
+    pub(crate) const MAX_VNI: u32 = 0xFFFFFF;
+}
@@ -20,6 +20,7 @@ use crate::{ErrorKind, JsonDisplay, NipartError};
 const IPV4_ADDR_LEN: usize = 32;
 const IPV6_ADDR_LEN: usize = 128;
 const FOREVER: &str = "forever";
+const DEFAULT_ROUTE_TABLE_ID: u32 = 254; // main route table ID
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -108,6 +109,61 @@ pub struct InterfaceIpv4 {
     pub dhcp: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dhcp_state: Option<DhcpState>,
+    /// Expiry time of current DHCPv4 lease in RFC 3339 format, only for
+    /// querying, will be ignored when applying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_lease_expiry: Option<String>,
+    /// Whether to apply routes(including default gateway) learned from
+    /// DHCPv4. Undefined means true.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub auto_routes: Option<bool>,
+    /// Whether to apply default gateway learned from DHCPv4. Ignored
+    /// when `auto-routes` is false. Undefined means true.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub auto_gateway: Option<bool>,
+    /// Whether to apply DNS learned from DHCPv4. Undefined means true.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub auto_dns: Option<bool>,
+    /// Route table to hold routes learned from DHCPv4.
+    /// Undefined or 0 means main route table(254).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub auto_route_table_id: Option<u32>,
+    /// Metric of routes learned from DHCPv4. Undefined means metric
+    /// generated by nipart.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub auto_route_metric: Option<u32>,
+    /// DHCPv4 client identifier(option 61) sent to DHCP server.
+    /// Undefined means [DhcpClientId::Mac].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_client_id: Option<DhcpClientId>,
+    /// DHCP Unique Identifier used by [DhcpClientId::Duid] and
+    /// [DhcpClientId::IaidDuid]. Undefined means [DhcpDuid::Llt].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_duid: Option<DhcpDuid>,
+    /// IPv4 link-local address(RFC 3927) selected from 169.254.0.0/16
+    /// by the daemon. Undefined means [Ipv4LinkLocal::Disabled].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_local: Option<Ipv4LinkLocal>,
     /// IPv4 addresses.
     /// When applying with `None`, current IP address will be preserved.
     /// When applying with `Some(Vec::new())`, all IP address will be removed.
@@ -135,6 +191,15 @@ impl InterfaceIpv4 {
             enabled: Some(false),
             dhcp: None,
             dhcp_state: None,
+            dhcp_lease_expiry: None,
+            auto_routes: None,
+            auto_gateway: None,
+            auto_dns: None,
+            auto_route_table_id: None,
+            auto_route_metric: None,
+            dhcp_client_id: None,
+            dhcp_duid: None,
+            link_local: None,
             addresses: None,
         }
     }
@@ -147,19 +212,87 @@ impl InterfaceIpv4 {
         self.is_enabled() && self.dhcp == Some(true)
     }
 
+    /// Whether IPv4 link-local address should be assigned regardless DHCP
+    /// result.
+    pub fn is_link_local(&self) -> bool {
+        self.is_enabled() && self.link_local == Some(Ipv4LinkLocal::Enabled)
+    }
+
+    /// Whether IPv4 link-local address should be assigned when DHCP
+    /// failed or timeout.
+    pub fn is_link_local_fallback(&self) -> bool {
+        self.is_auto() && self.link_local == Some(Ipv4LinkLocal::Fallback)
+    }
+
     pub fn is_static(&self) -> bool {
         self.is_enabled()
             && !self.is_auto()
             && !self.addresses.as_deref().unwrap_or_default().is_empty()
     }
 
-    // * Remove DHCP state
+    pub fn is_auto_routes(&self) -> bool {
+        self.auto_routes != Some(false)
+    }
+
+    pub fn is_auto_gateway(&self) -> bool {
+        self.is_auto_routes() && self.auto_gateway != Some(false)
+    }
+
+    pub fn is_auto_dns(&self) -> bool {
+        self.auto_dns != Some(false)
+    }
+
+    /// Route table ID for routes learned from DHCP.
+    pub fn auto_table_id(&self) -> u32 {
+        self.auto_route_table_id
+            .filter(|t| *t != 0)
+            .unwrap_or(DEFAULT_ROUTE_TABLE_ID)
+    }
+
+    /// Override `auto-*` options with the ones in `other`.
+    pub fn copy_auto_options(&mut self, other: &Self) {
+        self.auto_routes = other.auto_routes;
+        self.auto_gateway = other.auto_gateway;
+        self.auto_dns = other.auto_dns;
+        self.auto_route_table_id = other.auto_route_table_id;
+        self.auto_route_metric = other.auto_route_metric;
+    }
+
+    /// Override DHCP client identity options with the ones in `other`.
+    pub fn copy_dhcp_id_options(&mut self, other: &Self) {
+        self.dhcp_client_id.clone_from(&other.dhcp_client_id);
+        self.dhcp_duid.clone_from(&other.dhcp_duid);
+    }
+
+    // Backend might not able to report `auto-*` and DHCP client identity
+    // options, use desired value for undefined ones.
+    fn include_auto_options_if_none(&mut self, other: &Self) {
+        self.auto_routes = self.auto_routes.or(other.auto_routes);
+        self.auto_gateway = self.auto_gateway.or(other.auto_gateway);
+        self.auto_dns = self.auto_dns.or(other.auto_dns);
+        self.auto_route_table_id =
+            self.auto_route_table_id.or(other.auto_route_table_id);
+        self.auto_route_metric =
+            self.auto_route_metric.or(other.auto_route_metric);
+        if self.dhcp_client_id.is_none() {
+            self.dhcp_client_id.clone_from(&other.dhcp_client_id);
+        }
+        if self.dhcp_duid.is_none() {
+            self.dhcp_duid.clone_from(&other.dhcp_duid);
+        }
+        self.link_local = self.link_local.or(other.link_local);
+    }
+
+    // * Remove DHCP state and lease expiry
     // * Disable DHCP and remove address if enabled: false
+    // * Remove DHCP client identity options if DHCP is not enabled in desired
+    //   or current(when desired does not mention `dhcp`)
     pub(crate) fn sanitize(
         &mut self,
-        _current: Option<&Self>,
+        current: Option<&Self>,
     ) -> Result<(), NipartError> {
         self.dhcp_state = None;
+        self.dhcp_lease_expiry = None;
         if self.is_auto()
             && let Some(addrs) = self.addresses.as_ref()
         {
@@ -197,41 +330,51 @@ impl InterfaceIpv4 {
                         a.valid_life_time = None;
                         a.preferred_life_time = None
                     }
+                    a.origin = None;
                 });
             }
         }
 
+        let is_dhcp = self.is_enabled()
+            && self.dhcp.or_else(|| current.and_then(|c| c.dhcp)) == Some(true);
+        if !is_dhcp {
+            self.copy_dhcp_id_options(&Self::new_disabled());
+        }
+
+        if self.link_local == Some(Ipv4LinkLocal::Fallback) && !self.is_auto() {
+            log::info!(
+                "IPv4 link-local fallback mode only take effect when DHCP is \
+                 enabled"
+            );
+        }
+
         if !self.is_enabled() {
             self.dhcp = None;
             self.addresses = None;
+            self.link_local = None;
+            self.copy_auto_options(&Self::new_disabled());
         }
         Ok(())
     }
 
-    /// * Sync `valid_life_time` and `preferred_life_time` because there might
-    ///   be latency after applied and query back.
+    /// * Sync `valid_life_time`, `preferred_life_time` and `origin` because
+    ///   there might be latency after applied and query back.
+    /// * Ignore current link-local addresses and also dynamic addresses when
+    ///   DHCP or autoconf enabled, unless mentioned in desired.
     /// * Set current DHCP none to false.
+    /// * Set current `auto-*` options none to desired.
     /// * Set current address none to empty array.
     pub(crate) fn sanitize_before_verify(&mut self, current: &mut Self) {
-        if let Some(addrs) = self.addresses.as_mut() {
-            for addr in addrs {
-                if let Some(cur_addr) =
-                    current.addresses.as_ref().and_then(|cur_addrs| {
-                        cur_addrs.iter().find(|cur_addr| {
-                            cur_addr.ip == addr.ip
-                                && cur_addr.prefix_length == addr.prefix_length
-                        })
-                    })
-                {
-                    addr.valid_life_time = cur_addr.valid_life_time.clone();
-                    addr.preferred_life_time =
-                        cur_addr.preferred_life_time.clone();
-                }
-            }
+        let is_auto = self.is_auto();
+        if let Some(addrs) = self.addresses.as_mut()
+            && let Some(cur_addrs) = current.addresses.as_mut()
+        {
+            sanitize_addrs_before_verify(addrs, cur_addrs, is_auto);
         }
         if current.dhcp.is_none() {
             current.dhcp = Some(false);
         }
+        current.include_auto_options_if_none(self);
         if current.addresses.is_none() {
             current.addresses = Some(Vec::new());
         }
@@ -280,6 +423,13 @@ pub struct InterfaceIpv6 {
         deserialize_with = "crate::deserializer::option_bool_or_string"
     )]
     pub dhcp: Option<bool>,
+    /// DHCPv6 mode, only valid when `dhcp: true`.
+    /// Undefined means [DhcpV6Mode::Stateful] when DHCPv6 is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_mode: Option<DhcpV6Mode>,
+    /// DHCPv6 client state, only for querying, will be ignored when applying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_state: Option<DhcpState>,
     /// Whether autoconf via IPv6 router announcement enabled.
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -287,6 +437,64 @@ pub struct InterfaceIpv6 {
         deserialize_with = "crate::deserializer::option_bool_or_string"
     )]
     pub autoconf: Option<bool>,
+    /// Mode of generating interface identifier for IPv6 link-local and
+    /// autoconf addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addr_gen_mode: Option<Ipv6AddrGenMode>,
+    /// Interface identifier token for IPv6 autoconf addresses, only lower
+    /// 64 bits are used. Setting to `::` removes the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Ipv6Addr>,
+    /// IPv6 privacy extensions(RFC 4941) for generating temporary
+    /// autoconf addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<Ipv6Privacy>,
+    /// Whether to apply routes(including default gateway) learned from
+    /// IPv6 router announcement or delegated prefix of DHCPv6.
+    /// Undefined means true.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub auto_routes: Option<bool>,
+    /// Whether to apply default gateway learned from IPv6 router
+    /// announcement. Ignored when `auto-routes` is false.
+    /// Undefined means true.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub auto_gateway: Option<bool>,
+    /// Whether to apply DNS learned from DHCPv6. Undefined means true.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub auto_dns: Option<bool>,
+    /// Route table to hold routes for delegated prefix of DHCPv6. Kernel
+    /// always stores routes learned from IPv6 router announcement in main
+    /// route table. Undefined or 0 means main route table(254).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub auto_route_table_id: Option<u32>,
+    /// Metric of routes learned from IPv6 router announcement or delegated
+    /// prefix of DHCPv6. Undefined means metric generated by nipart.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub auto_route_metric: Option<u32>,
+    /// DHCP Unique Identifier sent as DHCPv6 client identifier.
+    /// Undefined means [DhcpDuid::Llt].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dhcp_duid: Option<DhcpDuid>,
     /// IPv6 addresses. Will be ignored when applying with
     /// DHCPv6 or autoconf is enabled.
     /// When applying with `None`, current IP address will be preserved.
@@ -309,7 +517,18 @@ impl InterfaceIpv6 {
         Self {
             enabled: Some(false),
             dhcp: None,
+            dhcp_mode: None,
+            dhcp_state: None,
             autoconf: None,
+            addr_gen_mode: None,
+            token: None,
+            privacy: None,
+            auto_routes: None,
+            auto_gateway: None,
+            auto_dns: None,
+            auto_route_table_id: None,
+            auto_route_metric: None,
+            dhcp_duid: None,
             addresses: None,
         }
     }
@@ -329,15 +548,76 @@ impl InterfaceIpv6 {
             && !self.addresses.as_deref().unwrap_or_default().is_empty()
     }
 
+    pub fn is_dhcp(&self) -> bool {
+        self.is_enabled() && self.dhcp == Some(true)
+    }
+
+    pub fn is_auto_routes(&self) -> bool {
+        self.auto_routes != Some(false)
+    }
+
+    pub fn is_auto_gateway(&self) -> bool {
+        self.is_auto_routes() && self.auto_gateway != Some(false)
+    }
+
+    pub fn is_auto_dns(&self) -> bool {
+        self.auto_dns != Some(false)
+    }
+
+    /// Route table ID for routes learned from DHCP.
+    pub fn auto_table_id(&self) -> u32 {
+        self.auto_route_table_id
+            .filter(|t| *t != 0)
+            .unwrap_or(DEFAULT_ROUTE_TABLE_ID)
+    }
+
+    /// Override `auto-*` options with the ones in `other`.
+    pub fn copy_auto_options(&mut self, other: &Self) {
+        self.auto_routes = other.auto_routes;
+        self.auto_gateway = other.auto_gateway;
+        self.auto_dns = other.auto_dns;
+        self.auto_route_table_id = other.auto_route_table_id;
+        self.auto_route_metric = other.auto_route_metric;
+    }
+
+    /// Override DHCP client identity options with the ones in `other`.
+    pub fn copy_dhcp_id_options(&mut self, other: &Self) {
+        self.dhcp_duid.clone_from(&other.dhcp_duid);
+    }
+
+    // Backend might not able to report `auto-*` and DHCP client identity
+    // options, use desired value for undefined ones.
+    fn include_auto_options_if_none(&mut self, other: &Self) {
+        self.auto_routes = self.auto_routes.or(other.auto_routes);
+        self.auto_gateway = self.auto_gateway.or(other.auto_gateway);
+        self.auto_dns = self.auto_dns.or(other.auto_dns);
+        self.auto_route_table_id =
+            self.auto_route_table_id.or(other.auto_route_table_id);
+        self.auto_route_metric =
+            self.auto_route_metric.or(other.auto_route_metric);
+        if self.dhcp_duid.is_none() {
+            self.dhcp_duid.clone_from(&other.dhcp_duid);
+        }
+    }
+
+    // * Remove DHCP state
     // * Disable DHCP and remove address if enabled: false
-    // * Set DHCP options to None if DHCP is false
+    // * Set DHCP mode and DUID to None if DHCP is not enabled in desired or
+    //   current(when desired does not mention `dhcp`)
+    // * Preserve dynamic address only when DHCPv6 is enabled, so DHCPv6 lease
+    //   could be applied.
+    // * Reject IPv6 token with non-zero prefix bits.
     pub(crate) fn sanitize(
         &mut self,
-        _current: Option<&Self>,
+        current: Option<&Self>,
     ) -> Result<(), NipartError> {
+        self.dhcp_state = None;
+        let is_dhcp = self.is_dhcp();
         if let Some(addrs) = self.addresses.as_mut() {
-            for addr in addrs.as_slice().iter().filter(|a| a.is_auto()) {
-                log::info!("Ignoring Auto IP address {addr}");
+            if !is_dhcp {
+                for addr in addrs.as_slice().iter().filter(|a| a.is_auto()) {
+                    log::info!("Ignoring Auto IP address {addr}");
+                }
             }
             if let Some(addr) = addrs.iter().find(|a| a.ip.is_ipv4()) {
                 return Err(NipartError::new(
@@ -358,17 +638,22 @@ impl InterfaceIpv6 {
                     ),
                 ));
             }
-            addrs.retain(|addr| {
-                if addr.is_auto() {
-                    log::info!("Ignoring dynamic addresses {addr}");
-                    false
-                } else {
-                    true
-                }
-            });
+            if !is_dhcp {
+                addrs.retain(|addr| {
+                    if addr.is_auto() {
+                        log::info!("Ignoring dynamic addresses {addr}");
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
             addrs.iter_mut().for_each(|a| {
-                a.valid_life_time = None;
-                a.preferred_life_time = None
+                if !a.is_auto() {
+                    a.valid_life_time = None;
+                    a.preferred_life_time = None
+                }
+                a.origin = None;
             });
         }
 
@@ -391,45 +676,358 @@ impl InterfaceIpv6 {
             })
         };
 
+        if self.dhcp.or_else(|| current.and_then(|c| c.dhcp)) != Some(true) {
+            self.dhcp_mode = None;
+            self.dhcp_duid = None;
+        }
+
+        if let Some(token) = self.token
+            && token.segments()[..4] != [0u16; 4]
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid IPv6 token {token}, only lower 64 bits are \
+                     allowed, for example ::1:2:3:4"
+                ),
+            ));
+        }
+
         if !self.is_enabled() {
             self.dhcp = None;
+            self.dhcp_mode = None;
+            self.dhcp_duid = None;
             self.autoconf = None;
+            self.addr_gen_mode = None;
+            self.token = None;
+            self.privacy = None;
             self.addresses = None;
+            self.copy_auto_options(&Self::new_disabled());
         }
 
         Ok(())
     }
 
-    /// * Sync `valid_life_time` and `preferred_life_time` because there might
-    ///   be latency after applied and query back.
+    /// * Sync `valid_life_time`, `preferred_life_time` and `origin` because
+    ///   there might be latency after applied and query back.
+    /// * Ignore current link-local addresses and also dynamic addresses when
+    ///   DHCP or autoconf enabled, unless mentioned in desired.
     /// * Set current DHCP none to false.
+    /// * Set current `auto-*` options none to desired.
     /// * Set current address none to empty array.
     pub(crate) fn sanitize_before_verify(&mut self, current: &mut Self) {
-        if let Some(addrs) = self.addresses.as_mut() {
-            for addr in addrs {
-                if let Some(cur_addr) =
-                    current.addresses.as_ref().and_then(|cur_addrs| {
-                        cur_addrs.iter().find(|cur_addr| {
-                            cur_addr.ip == addr.ip
-                                && cur_addr.prefix_length == addr.prefix_length
-                        })
-                    })
-                {
-                    addr.valid_life_time = cur_addr.valid_life_time.clone();
-                    addr.preferred_life_time =
-                        cur_addr.preferred_life_time.clone();
-                }
-            }
+        let is_auto = self.is_auto();
+        if let Some(addrs) = self.addresses.as_mut()
+            && let Some(cur_addrs) = current.addresses.as_mut()
+        {
+            sanitize_addrs_before_verify(addrs, cur_addrs, is_auto);
         }
         if current.dhcp.is_none() {
             current.dhcp = Some(false);
         }
+        current.include_auto_options_if_none(self);
         if current.addresses.is_none() {
             current.addresses = Some(Vec::new());
         }
+        if self.token == Some(Ipv6Addr::UNSPECIFIED) && current.token.is_none()
+        {
+            current.token = Some(Ipv6Addr::UNSPECIFIED);
+        }
+    }
+}
+
+/// IPv4 link-local address(RFC 3927) mode.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Ipv4LinkLocal {
+    /// No IPv4 link-local address.
+    /// Deserialize and serialize from/to `disabled`.
+    #[default]
+    Disabled,
+    /// Always assign IPv4 link-local address along with static or DHCP
+    /// addresses.
+    /// Deserialize and serialize from/to `enabled`.
+    Enabled,
+    /// Only assign IPv4 link-local address when DHCP failed or no lease
+    /// acquired in time, and remove it once DHCP lease acquired.
+    /// Deserialize and serialize from/to `fallback`.
+    Fallback,
+}
+
+/// Mode of generating interface identifier for IPv6 link-local and
+/// autoconf addresses.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Ipv6AddrGenMode {
+    /// Generated from MAC address using modified EUI-64 format.
+    /// Deserialize and serialize from/to `eui64`.
+    Eui64,
+    /// Stable but not predictable from MAC address(RFC 7217) using host
+    /// wide secret generated by nipart and stored in `/etc/nipart`.
+    /// Deserialize and serialize from/to `stable-privacy`.
+    StablePrivacy,
+    /// Random interface identifier.
+    /// Deserialize and serialize from/to `random`.
+    Random,
+}
+
+/// IPv6 privacy extensions(RFC 4941).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Ipv6Privacy {
+    /// Do not generate temporary address.
+    /// Deserialize and serialize from/to `disabled`.
+    Disabled,
+    /// Generate temporary address, but prefer public address as source
+    /// address.
+    /// Deserialize and serialize from/to `prefer-public`.
+    PreferPublic,
+    /// Generate temporary address and prefer it over public address as
+    /// source address.
+    /// Deserialize and serialize from/to `prefer-temporary`.
+    PreferTemporary,
+}
+
+/// DHCPv6 mode.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum DhcpV6Mode {
+    /// Request non-temporary address(IA_NA) from DHCPv6 server.
+    /// Deserialize and serialize from/to `stateful`.
+    #[default]
+    Stateful,
+    /// Request delegated prefix(IA_PD) from DHCPv6 server. The delegated
+    /// prefix is not assigned to this interface but installed as
+    /// unreachable route to prevent routing loop.
+    /// Deserialize and serialize from/to `prefix-delegation`.
+    PrefixDelegation,
+    /// Stateless DHCPv6 via Information-request message, only request
+    /// other configurations like DNS servers. Address is expected to be
+    /// provided by IPv6 autoconf.
+    /// Deserialize and serialize from/to `info-request`.
+    InfoRequest,
+}
+
+/// DHCPv4 client identifier(option 61).
+/// Serialize and deserialize from/to string `mac`, `duid`, `iaid+duid` or
+/// custom hex string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(try_from = "String", into = "String")]
+pub enum DhcpClientId {
+    /// Hardware type 1(Ethernet) followed by MAC address of interface.
+    Mac,
+    /// Type 255 followed by host DUID defined in `dhcp-duid`, which is
+    /// shared by all interfaces and not changed when NIC replaced.
+    Duid,
+    /// RFC 4361 node-specific client identifier: type 255 followed by
+    /// IAID generated from interface name and host DUID defined in
+    /// `dhcp-duid`.
+    IaidDuid,
+    /// Custom client identifier in hex string separated by `:`, the first
+    /// byte is the type, e.g. `00:6e:69:70:61:72:74`.
+    Custom(String),
+}
+
+impl DhcpClientId {
+    /// Bytes of custom client identifier, `None` for other variants.
+    pub(crate) fn custom_bytes(&self) -> Option<Vec<u8>> {
+        if let Self::Custom(v) = self {
+            hex_str_to_bytes(v)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::convert::From<DhcpClientId> for String {
+    fn from(val: DhcpClientId) -> Self {
+        match val {
+            DhcpClientId::Mac => "mac".into(),
+            DhcpClientId::Duid => "duid".into(),
+            DhcpClientId::IaidDuid => "iaid+duid".into(),
+            DhcpClientId::Custom(v) => v,
+        }
     }
 }
 
+impl std::convert::TryFrom<String> for DhcpClientId {
+    type Error = NipartError;
+
+    fn try_from(value: String) -> Result<Self, NipartError> {
+        match value.as_str() {
+            "mac" => Ok(Self::Mac),
+            "duid" => Ok(Self::Duid),
+            "iaid+duid" => Ok(Self::IaidDuid),
+            value => match hex_str_to_bytes(value) {
+                Some(bytes) if bytes.len() >= 2 => {
+                    Ok(Self::Custom(bytes_to_hex_str(&bytes)))
+                }
+                _ => Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid DHCP client identifier {value}, valid values \
+                         are mac, duid, iaid+duid or hex string separated by \
+                         `:` holding at least 2 bytes"
+                    ),
+                )),
+            },
+        }
+    }
+}
+
+/// DHCP Unique Identifier(DUID).
+/// Serialize and deserialize from/to string `llt`, `ll`, `uuid` or custom
+/// hex string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(try_from = "String", into = "String")]
+pub enum DhcpDuid {
+    /// DUID based on link-layer address plus time(DUID-LLT), generated
+    /// once and stored in `/etc/nipart`, hence stable for this host.
+    Llt,
+    /// DUID based on link-layer address(DUID-LL) of the interface.
+    Ll,
+    /// DUID based on UUID(DUID-UUID), generated once and stored in
+    /// `/etc/nipart`, hence stable for this host.
+    Uuid,
+    /// Custom DUID in hex string separated by `:`, including the 2 bytes
+    /// DUID type, e.g. `00:04:...`.
+    Custom(String),
+}
+
+impl DhcpDuid {
+    // RFC 8415: DUID type(2 bytes) plus up to 128 bytes
+    const MAX_LEN: usize = 130;
+
+    pub(crate) fn new_custom(bytes: &[u8]) -> Self {
+        Self::Custom(bytes_to_hex_str(bytes))
+    }
+
+    /// Bytes of custom DUID, `None` for other variants.
+    pub(crate) fn custom_bytes(&self) -> Option<Vec<u8>> {
+        if let Self::Custom(v) = self {
+            hex_str_to_bytes(v)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::convert::From<DhcpDuid> for String {
+    fn from(val: DhcpDuid) -> Self {
+        match val {
+            DhcpDuid::Llt => "llt".into(),
+            DhcpDuid::Ll => "ll".into(),
+            DhcpDuid::Uuid => "uuid".into(),
+            DhcpDuid::Custom(v) => v,
+        }
+    }
+}
+
+impl std::convert::TryFrom<String> for DhcpDuid {
+    type Error = NipartError;
+
+    fn try_from(value: String) -> Result<Self, NipartError> {
+        match value.as_str() {
+            "llt" => Ok(Self::Llt),
+            "ll" => Ok(Self::Ll),
+            "uuid" => Ok(Self::Uuid),
+            value => match hex_str_to_bytes(value) {
+                Some(bytes)
+                    if bytes.len() > 2 && bytes.len() <= Self::MAX_LEN =>
+                {
+                    Ok(Self::Custom(bytes_to_hex_str(&bytes)))
+                }
+                _ => Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid DHCP DUID {value}, valid values are llt, ll, \
+                         uuid or hex string separated by `:` holding 3 to {} \
+                         bytes",
+                        Self::MAX_LEN
+                    ),
+                )),
+            },
+        }
+    }
+}
+
+fn sanitize_addrs_before_verify(
+    addrs: &mut [InterfaceIpAddr],
+    cur_addrs: &mut Vec<InterfaceIpAddr>,
+    is_auto: bool,
+) {
+    for addr in addrs.iter_mut() {
+        if let Some(cur_addr) = cur_addrs.iter().find(|cur_addr| {
+            cur_addr.ip == addr.ip
+                && cur_addr.prefix_length == addr.prefix_length
+        }) {
+            addr.valid_life_time = cur_addr.valid_life_time.clone();
+            addr.preferred_life_time = cur_addr.preferred_life_time.clone();
+            addr.origin = cur_addr.origin;
+        }
+    }
+    cur_addrs.retain(|cur_addr| {
+        !((cur_addr.is_link_local() || (is_auto && cur_addr.is_auto()))
+            && !addrs.iter().any(|addr| {
+                addr.ip == cur_addr.ip
+                    && addr.prefix_length == cur_addr.prefix_length
+            }))
+    });
+}
+
+/// Convert hex string separated by `:` to bytes.
+fn hex_str_to_bytes(value: &str) -> Option<Vec<u8>> {
+    value
+        .split(':')
+        .map(|b| {
+            if b.len() == 2 {
+                u8::from_str_radix(b, 16).ok()
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Convert bytes to lower case hex string separated by `:`.
+fn bytes_to_hex_str(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<String>>()
+        .join(":")
+}
+
 /// IP Address
 ///
 /// When `valid_life_time` or `preferred_life_time` not equal to `None` or
@@ -478,6 +1076,10 @@ pub struct InterfaceIpAddr {
         alias = "preferred-lft"
     )]
     pub preferred_life_time: Option<String>,
+    /// Origin of this IP address, only for querying, will be ignored when
+    /// applying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<InterfaceIpAddrOrigin>,
 }
 
 impl Default for InterfaceIpAddr {
@@ -487,6 +1089,7 @@ impl Default for InterfaceIpAddr {
             prefix_length: 128,
             valid_life_time: None,
             preferred_life_time: None,
+            origin: None,
         }
     }
 }
@@ -502,9 +1105,70 @@ impl InterfaceIpAddr {
 }
 
 impl InterfaceIpAddr {
+    /// Whether this address is learned from DHCP or IPv6 autoconf. When
+    /// origin is unknown, address with finite life time is treated as
+    /// dynamic.
     pub(crate) fn is_auto(&self) -> bool {
-        self.valid_life_time.is_some()
-            && self.valid_life_time.as_deref() != Some(FOREVER)
+        match self.origin {
+            Some(origin) => origin.is_auto(),
+            None => {
+                self.valid_life_time.is_some()
+                    && self.valid_life_time.as_deref() != Some(FOREVER)
+            }
+        }
+    }
+
+    /// Whether this address is link-local address. When origin is unknown,
+    /// only IPv6 link-local address is treated as link-local.
+    pub(crate) fn is_link_local(&self) -> bool {
+        match self.origin {
+            Some(origin) => origin == InterfaceIpAddrOrigin::LinkLocal,
+            None => {
+                if let IpAddr::V6(ip_addr) = self.ip {
+                    ip_addr.is_unicast_link_local()
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Origin of IP address.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum InterfaceIpAddrOrigin {
+    /// Configured statically by nipart or other tools.
+    /// Deserialize and serialize from/to `static`.
+    Static,
+    /// Learned from DHCP lease.
+    /// Deserialize and serialize from/to `dhcp`.
+    Dhcp,
+    /// Generated by kernel via IPv6 stateless address autoconfiguration
+    /// from router announcement.
+    /// Deserialize and serialize from/to `autoconf`.
+    Autoconf,
+    /// IPv6 link-local address generated by kernel or IPv4 link-local
+    /// address in 169.254.0.0/16.
+    /// Deserialize and serialize from/to `link-local`.
+    LinkLocal,
+}
+
+impl InterfaceIpAddrOrigin {
+    pub fn is_auto(&self) -> bool {
+        matches!(self, Self::Dhcp | Self::Autoconf)
     }
 }
 
@@ -539,6 +1203,7 @@ impl std::convert::TryFrom<&str> for InterfaceIpAddr {
             prefix_length,
             valid_life_time: None,
             preferred_life_time: None,
+            origin: None,
         })
     }
 }
@@ -102,6 +102,7 @@ impl MergedInterfaces {
     ) -> Result<(), NipartError> {
         self.handle_changed_ports()?;
         self.check_overbook_ports()?;
+        self.check_hsr_ports()?;
         self.check_infiniband_as_ports()?;
         self.validate_controller_and_port_list_confliction()?;
         Ok(())
@@ -338,6 +339,71 @@ impl MergedInterfaces {
         Ok(())
     }
 
+    // HSR interface requires two different ports which should not be
+    // removed or marked as down.
+    fn check_hsr_ports(&self) -> Result<(), NipartError> {
+        for iface in self.kernel_ifaces.values().filter(|i| {
+            i.is_desired()
+                && i.merged.is_up()
+                && i.merged.iface_type() == &InterfaceType::Hsr
+        }) {
+            let ports = iface.merged.ports().unwrap_or_default();
+            if ports.len() != 2 || ports[0] == ports[1] {
+                let e = NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "HSR interface {} requires two different ports, but \
+                         got {ports:?}",
+                        iface.merged.name()
+                    ),
+                );
+                log::error!("{e}");
+                return Err(e);
+            }
+            for port in ports {
+                if port == iface.merged.name() {
+                    let e = NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "HSR interface {port} cannot use itself as port"
+                        ),
+                    );
+                    log::error!("{e}");
+                    return Err(e);
+                }
+                match self.kernel_ifaces.get(port).map(|p| &p.merged) {
+                    Some(port_iface) if port_iface.is_up() => (),
+                    Some(port_iface) => {
+                        let e = NipartError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Port {port} of HSR interface {} is desired \
+                                 as state {}",
+                                iface.merged.name(),
+                                port_iface.base_iface().state
+                            ),
+                        );
+                        log::error!("{e}");
+                        return Err(e);
+                    }
+                    None => {
+                        let e = NipartError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Port {port} of HSR interface {} does not \
+                                 exist",
+                                iface.merged.name()
+                            ),
+                        );
+                        log::error!("{e}");
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     // Infiniband over IP can only be port of active_backup bond as it is a
     // layer 3 interface like tun.
     fn check_infiniband_as_ports(&self) -> Result<(), NipartError> {
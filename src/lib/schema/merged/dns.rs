@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    DnsResolver, DnsResolverConfig, ErrorKind, JsonDisplay, NipartError,
+};
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct MergedDnsResolver {
+    pub desired: DnsResolver,
+    pub current: DnsResolver,
+    /// Static DNS resolver configuration to apply, `None` means no change
+    /// required.
+    pub for_apply: Option<DnsResolverConfig>,
+}
+
+impl MergedDnsResolver {
+    /// * Desired `config: {}` purges all static configurations.
+    /// * Otherwise, properties not mentioned in desired `config` are preserved
+    ///   from current.
+    pub fn new(
+        desired: DnsResolver,
+        current: DnsResolver,
+    ) -> Result<Self, NipartError> {
+        let mut for_apply = None;
+        if let Some(des_conf) = desired.config.as_ref() {
+            let mut merged_conf = des_conf.clone();
+            merged_conf.sanitize()?;
+            if merged_conf != DnsResolverConfig::default()
+                && let Some(cur_conf) = current.config.as_ref()
+            {
+                if merged_conf.server.is_none() {
+                    merged_conf.server.clone_from(&cur_conf.server);
+                }
+                if merged_conf.search.is_none() {
+                    merged_conf.search.clone_from(&cur_conf.search);
+                }
+                if merged_conf.options.is_none() {
+                    merged_conf.options.clone_from(&cur_conf.options);
+                }
+            }
+            if Some(&merged_conf) != current.config.as_ref()
+                && !(merged_conf.is_empty() && current.config.is_none())
+            {
+                for_apply = Some(merged_conf);
+            }
+        }
+        Ok(Self {
+            desired,
+            current,
+            for_apply,
+        })
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.for_apply.is_some()
+    }
+
+    pub fn gen_state_for_apply(&self) -> DnsResolver {
+        DnsResolver {
+            config: self.for_apply.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Static DNS servers and search domains should be found in running
+    /// DNS resolver configuration.
+    pub(crate) fn verify(
+        &self,
+        current: &DnsResolver,
+    ) -> Result<(), NipartError> {
+        let Some(apply_conf) = self.for_apply.as_ref() else {
+            return Ok(());
+        };
+        let empty_conf = DnsResolverConfig::default();
+        let running_conf = current.running.as_ref().unwrap_or(&empty_conf);
+        for (prop_name, desired_items, running_items) in [
+            (
+                "server",
+                apply_conf.server.as_deref(),
+                running_conf.server.as_deref(),
+            ),
+            (
+                "search",
+                apply_conf.search.as_deref(),
+                running_conf.search.as_deref(),
+            ),
+        ] {
+            let running_items = running_items.unwrap_or_default();
+            if let Some(missing) = desired_items
+                .unwrap_or_default()
+                .iter()
+                .find(|i| !running_items.contains(i))
+            {
+                return Err(NipartError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: desired DNS {prop_name} \
+                         '{missing}' not found in running DNS {prop_name} \
+                         {running_items:?}"
+                    ),
+                ));
+            }
+        }
+        let cur_conf = current.config.as_ref().unwrap_or(&empty_conf);
+        if apply_conf != cur_conf {
+            return Err(NipartError::new(
+                ErrorKind::VerificationError,
+                format!(
+                    "Verification failure: desired DNS config {apply_conf}, \
+                     but current is {cur_conf}"
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, HostNameState, JsonDisplay, NipartError};
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct MergedHostNameState {
+    pub desired: Option<HostNameState>,
+    pub current: Option<HostNameState>,
+    /// Host name properties changed, `None` means no change required.
+    pub for_apply: Option<HostNameState>,
+}
+
+impl MergedHostNameState {
+    pub fn new(
+        desired: Option<HostNameState>,
+        current: Option<HostNameState>,
+    ) -> Result<Self, NipartError> {
+        let mut for_apply = None;
+        if let Some(mut des) = desired.clone() {
+            des.sanitize()?;
+            let cur = current.clone().unwrap_or_default();
+            let mut apply = HostNameState::default();
+            if des.config.is_some()
+                && des.config != cur.config
+                && !(des.config.as_deref() == Some("") && cur.config.is_none())
+            {
+                apply.config = des.config;
+            }
+            if des.dhcp_override.is_some()
+                && des.dhcp_override.unwrap_or_default()
+                    != cur.dhcp_override.unwrap_or_default()
+            {
+                apply.dhcp_override = des.dhcp_override;
+            }
+            if !apply.is_empty() {
+                for_apply = Some(apply);
+            }
+        }
+        Ok(Self {
+            desired,
+            current,
+            for_apply,
+        })
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.for_apply.is_some()
+    }
+
+    pub fn gen_state_for_apply(&self) -> Option<HostNameState> {
+        self.for_apply.clone()
+    }
+
+    pub(crate) fn verify(
+        &self,
+        current: Option<&HostNameState>,
+    ) -> Result<(), NipartError> {
+        let Some(apply) = self.for_apply.as_ref() else {
+            return Ok(());
+        };
+        let empty = HostNameState::default();
+        let current = current.unwrap_or(&empty);
+        if let Some(des_config) = apply.config.as_deref() {
+            let cur_config = current.config.as_deref().unwrap_or_default();
+            if des_config != cur_config {
+                return Err(NipartError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: desired static host name \
+                         '{des_config}', but current is '{cur_config}'"
+                    ),
+                ));
+            }
+        }
+        if let Some(des_override) = apply.dhcp_override
+            && Some(des_override) != current.dhcp_override
+        {
+            return Err(NipartError::new(
+                ErrorKind::VerificationError,
+                format!(
+                    "Verification failure: desired host name dhcp-override \
+                     {des_override}, but current is {:?}",
+                    current.dhcp_override
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -327,42 +327,78 @@ impl MergedInterfaces {
         }
 
         // If not remaining unknown up_priority, we set up the parent/child
-        // up_priority
+        // up_priority. The parent might be placed after its child in insert
+        // order or be a child of another interface(e.g. VXLAN over VLAN),
+        // hence repeat till nothing changed.
         if ret {
-            for (iface_name, iface_type) in &self.insert_order {
-                let iface = match self.get_iface(iface_name, iface_type.clone())
-                {
-                    Some(i) => {
-                        if let Some(i) = i.for_apply.as_ref() {
-                            i
-                        } else {
-                            continue;
-                        }
+            for _ in 0..self.insert_order.len() {
+                let mut changed = false;
+                for (iface_name, iface_type) in &self.insert_order {
+                    let iface =
+                        match self.get_iface(iface_name, iface_type.clone()) {
+                            Some(i) => {
+                                if let Some(i) = i.for_apply.as_ref() {
+                                    i
+                                } else {
+                                    continue;
+                                }
+                            }
+                            None => continue,
+                        };
+                    if !iface.is_up() {
+                        continue;
                     }
-                    None => continue,
-                };
-                if !iface.is_up() {
-                    continue;
-                }
-                if let Some(parent) = iface.parent() {
-                    let parent_priority = pending_changes.get(parent).cloned();
-                    if let Some(parent_priority) = parent_priority {
-                        pending_changes.insert(
-                            iface_name.to_string(),
-                            parent_priority + 1,
-                        );
-                    } else if let Some(parent_iface) = self
+                    let Some(parent) = iface.parent() else {
+                        continue;
+                    };
+                    // Parent not changed in this apply is already up,
+                    // no need to order against it.
+                    let Some(parent_iface) = self
                         .kernel_ifaces
                         .get(parent)
                         .and_then(|i| i.for_apply.as_ref())
-                        && parent_iface.base_iface().is_up_priority_valid()
-                    {
-                        pending_changes.insert(
-                            iface_name.to_string(),
-                            parent_iface.base_iface().up_priority + 1,
+                    else {
+                        continue;
+                    };
+                    // Top-level parent(no controller) holds base priority 0
+                    let parent_priority =
+                        pending_changes.get(parent).cloned().or_else(|| {
+                            parent_iface
+                                .base_iface()
+                                .is_up_priority_valid()
+                                .then_some(
+                                    parent_iface.base_iface().up_priority,
+                                )
+                        });
+                    if let Some(parent_priority) = parent_priority {
+                        // Child should never be brought up before its
+                        // controller or its parent.
+                        let cur_priority = pending_changes
+                            .get(iface_name.as_str())
+                            .cloned()
+                            .unwrap_or(iface.base_iface().up_priority);
+                        let new_priority =
+                            std::cmp::max(cur_priority, parent_priority + 1);
+                        if new_priority != cur_priority
+                            || !pending_changes.contains_key(iface_name)
+                        {
+                            pending_changes
+                                .insert(iface_name.to_string(), new_priority);
+                            changed = true;
+                        }
+                    } else {
+                        // Controller of parent has no up priority yet,
+                        // retry in next round.
+                        log::debug!(
+                            "Parent {parent} of {iface_name} has no up \
+                             priority"
                         );
+                        ret = false;
                     }
                 }
+                if !changed {
+                    break;
+                }
             }
         }
 
@@ -1,7 +1,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::net::IpAddr;
-
 use crate::{InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6};
 
 impl InterfaceIpv4 {
@@ -20,6 +18,7 @@ impl InterfaceIpv4 {
             addrs.as_mut_slice().iter_mut().for_each(|a| {
                 a.valid_life_time = None;
                 a.preferred_life_time = None;
+                a.origin = None;
             });
         }
     }
@@ -41,6 +40,7 @@ impl InterfaceIpv6 {
             addrs.as_mut_slice().iter_mut().for_each(|a| {
                 a.valid_life_time = None;
                 a.preferred_life_time = None;
+                a.origin = None;
             });
         }
     }
@@ -48,12 +48,6 @@ impl InterfaceIpv6 {
 
 fn is_ip_addrs_none_or_all_auto(addrs: Option<&[InterfaceIpAddr]>) -> bool {
     addrs.is_none_or(|addrs| {
-        addrs.iter().all(|a| {
-            if let IpAddr::V6(ip_addr) = a.ip {
-                ip_addr.is_unicast_link_local() || a.is_auto()
-            } else {
-                a.is_auto()
-            }
-        })
+        addrs.iter().all(|a| a.is_link_local() || a.is_auto())
     })
 }
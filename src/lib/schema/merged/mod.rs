@@ -2,16 +2,24 @@
 
 mod base_iface;
 mod controller;
+mod dns;
 mod ethernet;
+mod hostname;
 mod iface;
 mod inter_iface;
 mod ip;
 mod loopback;
 mod net_state;
+mod nexthop;
 mod route;
+mod route_metric_policy;
+mod route_rule;
 mod wifi;
 
 pub use self::{
+    dns::MergedDnsResolver, hostname::MergedHostNameState,
     iface::MergedInterface, inter_iface::MergedInterfaces,
-    net_state::MergedNetworkState, route::MergedRoutes,
+    net_state::MergedNetworkState, nexthop::MergedNextHops,
+    route::MergedRoutes, route_metric_policy::MergedRouteMetricPolicy,
+    route_rule::MergedRouteRules,
 };
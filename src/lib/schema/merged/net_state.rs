@@ -3,9 +3,10 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    InterfaceType, JsonDisplayHideSecrets, MergedInterfaces, MergedRoutes,
-    NetworkState, NipartApplyOption, NipartError, NipartInterface,
-    NipartWaitOnline,
+    InterfaceType, JsonDisplayHideSecrets, MergedDnsResolver,
+    MergedHostNameState, MergedInterfaces, MergedNextHops,
+    MergedRouteMetricPolicy, MergedRouteRules, MergedRoutes, NetworkState,
+    NipartApplyOption, NipartError, NipartInterface, NipartWaitOnline,
 };
 
 #[derive(
@@ -23,6 +24,11 @@ pub struct MergedNetworkState {
     pub description: Option<String>,
     pub ifaces: MergedInterfaces,
     pub routes: MergedRoutes,
+    pub route_rules: MergedRouteRules,
+    pub nexthops: MergedNextHops,
+    pub dns: MergedDnsResolver,
+    pub hostname: MergedHostNameState,
+    pub route_metric_policy: MergedRouteMetricPolicy,
     pub wait_online: NipartWaitOnline,
     pub option: NipartApplyOption,
     pub desired: NetworkState,
@@ -39,12 +45,28 @@ impl MergedNetworkState {
             MergedInterfaces::new(desired.ifaces, current.ifaces)?;
         let merged_routes =
             MergedRoutes::new(desired.routes, current.routes, &merged_ifaces)?;
+        let merged_route_rules =
+            MergedRouteRules::new(desired.route_rules, current.route_rules)?;
+        let merged_nexthops =
+            MergedNextHops::new(desired.nexthops, current.nexthops)?;
+        let merged_dns = MergedDnsResolver::new(desired.dns, current.dns)?;
+        let merged_hostname =
+            MergedHostNameState::new(desired.hostname, current.hostname)?;
+        let merged_route_metric_policy = MergedRouteMetricPolicy::new(
+            desired.route_metric_policy,
+            current.route_metric_policy,
+        )?;
 
         Ok(Self {
             version: desired.version,
             description: desired.description.clone(),
             ifaces: merged_ifaces,
             routes: merged_routes,
+            route_rules: merged_route_rules,
+            nexthops: merged_nexthops,
+            dns: merged_dns,
+            hostname: merged_hostname,
+            route_metric_policy: merged_route_metric_policy,
             wait_online: desired
                 .wait_online
                 .or(current.wait_online)
@@ -55,13 +77,24 @@ impl MergedNetworkState {
     }
 
     pub fn verify(&self, current: &NetworkState) -> Result<(), NipartError> {
-        self.ifaces.verify(&current.ifaces)
+        self.ifaces.verify(&current.ifaces)?;
+        self.route_rules.verify(&current.route_rules)?;
+        self.nexthops.verify(&current.nexthops)?;
+        self.dns.verify(&current.dns)?;
+        self.hostname.verify(current.hostname.as_ref())?;
+        self.route_metric_policy
+            .verify(current.route_metric_policy.as_ref())
     }
 
     pub fn gen_state_for_apply(&self) -> NetworkState {
         NetworkState {
             ifaces: self.ifaces.gen_state_for_apply(),
             routes: self.routes.gen_state_for_apply(),
+            route_rules: self.route_rules.gen_state_for_apply(),
+            nexthops: self.nexthops.gen_state_for_apply(),
+            dns: self.dns.gen_state_for_apply(),
+            hostname: self.hostname.gen_state_for_apply(),
+            route_metric_policy: self.route_metric_policy.gen_state_for_apply(),
             wait_online: self.desired.wait_online.clone(),
             version: self.version,
             description: self.description.clone(),
@@ -123,6 +156,22 @@ impl NetworkState {
                 .or_else(|| self.description.clone()),
             ifaces: self.ifaces.merge(&new_state.ifaces)?,
             routes: self.routes.merge(&new_state.routes)?,
+            route_rules: self.route_rules.merge(&new_state.route_rules)?,
+            nexthops: self.nexthops.merge(&new_state.nexthops)?,
+            dns: if new_state.dns.is_empty() && new_state.dns.running.is_none()
+            {
+                self.dns.clone()
+            } else {
+                new_state.dns.clone()
+            },
+            hostname: new_state
+                .hostname
+                .clone()
+                .or_else(|| self.hostname.clone()),
+            route_metric_policy: new_state
+                .route_metric_policy
+                .clone()
+                .or_else(|| self.route_metric_policy.clone()),
             wait_online: new_state
                 .wait_online
                 .clone()
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ErrorKind, JsonDisplay, NextHopEntry, NextHops, NipartError, RouteState,
+};
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct MergedNextHops {
+    // The `changed_nexthops` contains desired nexthop objects to add or
+    // replace, and also including current nexthop objects been marked as
+    // absent. Not including desired nexthop object equal to current one.
+    pub changed_nexthops: Vec<NextHopEntry>,
+    // Desired non-absent nexthop objects after sanitize, used for
+    // verification.
+    pub(crate) desired_nexthops: Vec<NextHopEntry>,
+    // Desired absent nexthop objects after sanitize, used for verification.
+    pub(crate) absent_nexthops: Vec<NextHopEntry>,
+    pub desired: NextHops,
+    pub current: NextHops,
+}
+
+impl MergedNextHops {
+    pub fn new(
+        desired: NextHops,
+        current: NextHops,
+    ) -> Result<Self, NipartError> {
+        desired.validate()?;
+
+        let mut desired_nexthops = Vec::new();
+        let mut absent_nexthops = Vec::new();
+        for nh in desired.config.as_deref().unwrap_or_default() {
+            let mut nh = nh.clone();
+            nh.sanitize()?;
+            if nh.is_absent() {
+                absent_nexthops.push(nh);
+            } else {
+                desired_nexthops.push(nh);
+            }
+        }
+
+        let mut changed_nexthops: Vec<NextHopEntry> = Vec::new();
+        let cur_nexthops = current.config.as_deref().unwrap_or_default();
+
+        for cur_nh in cur_nexthops {
+            if absent_nexthops
+                .iter()
+                .any(|absent_nh| absent_nh.is_match(cur_nh))
+                && !desired_nexthops.iter().any(|nh| nh.id == cur_nh.id)
+            {
+                let mut new_nh = cur_nh.clone();
+                new_nh.state = Some(RouteState::Absent);
+                changed_nexthops.push(new_nh);
+            }
+        }
+
+        for nh in desired_nexthops.as_slice() {
+            if !cur_nexthops
+                .iter()
+                .any(|cur_nh| is_same_nexthop(nh, cur_nh))
+            {
+                changed_nexthops.push(nh.clone());
+            }
+        }
+        changed_nexthops.sort_unstable_by_key(|nh| (!nh.is_absent(), nh.id));
+
+        Ok(Self {
+            changed_nexthops,
+            desired_nexthops,
+            absent_nexthops,
+            desired,
+            current,
+        })
+    }
+
+    pub(crate) fn is_changed(&self) -> bool {
+        !self.changed_nexthops.is_empty()
+    }
+
+    pub(crate) fn gen_state_for_apply(&self) -> NextHops {
+        NextHops {
+            config: if self.changed_nexthops.is_empty() {
+                None
+            } else {
+                Some(self.changed_nexthops.clone())
+            },
+        }
+    }
+
+    pub(crate) fn verify(&self, current: &NextHops) -> Result<(), NipartError> {
+        let cur_nexthops = current.config.as_deref().unwrap_or_default();
+        for nh in self.desired_nexthops.as_slice() {
+            if !cur_nexthops
+                .iter()
+                .any(|cur_nh| is_same_nexthop(nh, cur_nh))
+            {
+                return Err(NipartError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: desired nexthop object {nh} \
+                         not found after applied"
+                    ),
+                ));
+            }
+        }
+        for absent_nh in self.absent_nexthops.as_slice() {
+            if let Some(cur_nh) = cur_nexthops.iter().find(|cur_nh| {
+                absent_nh.is_match(cur_nh)
+                    && !self
+                        .desired_nexthops
+                        .iter()
+                        .any(|nh| nh.id == cur_nh.id)
+            }) {
+                return Err(NipartError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: nexthop object {cur_nh} still \
+                         exists after applied with {absent_nh}"
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_same_nexthop(desired: &NextHopEntry, current: &NextHopEntry) -> bool {
+    desired.id == current.id && desired.is_match(current)
+}
+
+impl NextHops {
+    /// Return new NextHops data contains the merged data.
+    pub(crate) fn merge(
+        &self,
+        new_nexthops: &Self,
+    ) -> Result<Self, NipartError> {
+        new_nexthops.validate()?;
+
+        if let Some(new_nexthops) = new_nexthops.config.as_ref() {
+            let mut nexthops: Vec<NextHopEntry> = new_nexthops
+                .iter()
+                .filter(|nh| !nh.is_absent())
+                .cloned()
+                .collect();
+            for old_nh in self.config.as_deref().unwrap_or_default() {
+                if nexthops.iter().any(|nh| nh.id == old_nh.id) {
+                    continue;
+                }
+                if new_nexthops
+                    .iter()
+                    .any(|nh| nh.is_absent() && nh.is_match(old_nh))
+                {
+                    let mut absent_nh = old_nh.clone();
+                    absent_nh.state = Some(RouteState::Absent);
+                    nexthops.push(absent_nh);
+                } else {
+                    nexthops.push(old_nh.clone());
+                }
+            }
+            nexthops.sort_unstable_by_key(|nh| (!nh.is_absent(), nh.id));
+
+            Ok(NextHops {
+                config: Some(nexthops),
+            })
+        } else {
+            Ok(self.clone())
+        }
+    }
+}
@@ -54,6 +54,14 @@ impl MergedRoutes {
             for rt in rts {
                 let mut rt = rt.clone();
                 rt.sanitize()?;
+                if !rt.is_absent() {
+                    rt.resolve_vrf(|iface_name| {
+                        merged_ifaces
+                            .kernel_ifaces
+                            .get(iface_name)
+                            .map(|i| &i.merged)
+                    })?;
+                }
                 desired_routes.push(rt);
             }
         }
@@ -121,7 +129,7 @@ impl MergedRoutes {
                     ));
                 }
                 changed_ifaces.insert(via.as_str());
-            } else if rt.route_type.is_some() {
+            } else if rt.route_type.is_some() || rt.nexthop_id.is_some() {
                 changed_ifaces.insert(LOOPBACK_IFACE_NAME);
             }
         }
@@ -170,6 +178,21 @@ impl MergedRoutes {
                     } else {
                         merged_routes.push(rt.clone());
                     }
+                } else if rt.nexthop_id.is_some() {
+                    // Route referring nexthop object has no next hop
+                    // interface.
+                    if desired_routes
+                        .as_slice()
+                        .iter()
+                        .filter(|r| r.is_absent())
+                        .any(|absent_rt| absent_rt.is_match(rt))
+                    {
+                        let mut new_rt = rt.clone();
+                        new_rt.state = Some(RouteState::Absent);
+                        changed_routes.insert(new_rt);
+                    } else {
+                        merged_routes.push(rt.clone());
+                    }
                 }
             }
         }
@@ -205,7 +228,7 @@ impl MergedRoutes {
                         Entry::Vacant(v) => v.insert(Vec::new()),
                     };
                 rts.push(rt);
-            } else if rt.route_type.is_some() {
+            } else if rt.route_type.is_some() || rt.nexthop_id.is_some() {
                 let rts: &mut Vec<RouteEntry> =
                     match merged.entry(LOOPBACK_IFACE_NAME.to_string()) {
                         Entry::Occupied(o) => o.into_mut(),
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, JsonDisplay, NipartError, RouteMetricPolicy};
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct MergedRouteMetricPolicy {
+    pub desired: Option<RouteMetricPolicy>,
+    pub current: Option<RouteMetricPolicy>,
+    /// Full route metric policy to apply, `None` means no change required.
+    pub for_apply: Option<RouteMetricPolicy>,
+}
+
+impl MergedRouteMetricPolicy {
+    pub fn new(
+        desired: Option<RouteMetricPolicy>,
+        current: Option<RouteMetricPolicy>,
+    ) -> Result<Self, NipartError> {
+        let mut for_apply = None;
+        if let Some(des) = desired.as_ref().filter(|d| !d.is_empty()) {
+            let mut cur = current.clone().unwrap_or_default();
+            cur.include_default();
+            let mut merged = des.clone();
+            if merged.base_metrics.is_none() {
+                merged.base_metrics.clone_from(&cur.base_metrics);
+            }
+            if merged.default_base_metric.is_none() {
+                merged.default_base_metric = cur.default_base_metric;
+            }
+            if merged != cur {
+                for_apply = Some(merged);
+            }
+        }
+        Ok(Self {
+            desired,
+            current,
+            for_apply,
+        })
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.for_apply.is_some()
+    }
+
+    /// Route metric policy in effect after applied, with undefined
+    /// properties set to default.
+    pub fn merged(&self) -> RouteMetricPolicy {
+        let mut policy = self
+            .for_apply
+            .clone()
+            .or_else(|| self.current.clone())
+            .unwrap_or_default();
+        policy.include_default();
+        policy
+    }
+
+    pub fn gen_state_for_apply(&self) -> Option<RouteMetricPolicy> {
+        self.for_apply.clone()
+    }
+
+    pub(crate) fn verify(
+        &self,
+        current: Option<&RouteMetricPolicy>,
+    ) -> Result<(), NipartError> {
+        let Some(apply) = self.for_apply.as_ref() else {
+            return Ok(());
+        };
+        let mut current = current.cloned().unwrap_or_default();
+        current.include_default();
+        if apply != &current {
+            return Err(NipartError::new(
+                ErrorKind::VerificationError,
+                format!(
+                    "Verification failure: desired route metric policy \
+                     {apply}, but current is {current}"
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
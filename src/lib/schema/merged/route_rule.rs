@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ErrorKind, JsonDisplay, NipartError, RouteRuleEntry, RouteRules, RouteState,
+};
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct MergedRouteRules {
+    // The `changed_rules` contains desired new rules and also including
+    // current rules been marked as absent. Not including desired rule equal
+    // to current rule.
+    pub changed_rules: Vec<RouteRuleEntry>,
+    // Desired non-absent rules after sanitize, used for verification.
+    pub(crate) desired_rules: Vec<RouteRuleEntry>,
+    // Desired absent rules after sanitize, used for verification.
+    pub(crate) absent_rules: Vec<RouteRuleEntry>,
+    pub desired: RouteRules,
+    pub current: RouteRules,
+}
+
+impl MergedRouteRules {
+    pub fn new(
+        desired: RouteRules,
+        current: RouteRules,
+    ) -> Result<Self, NipartError> {
+        desired.validate()?;
+
+        let mut desired_rules = Vec::new();
+        let mut absent_rules = Vec::new();
+        for rule in desired.config.as_deref().unwrap_or_default() {
+            let mut rule = rule.clone();
+            rule.sanitize()?;
+            if rule.is_absent() {
+                absent_rules.push(rule);
+            } else {
+                desired_rules.push(rule);
+            }
+        }
+
+        let mut changed_rules: HashSet<RouteRuleEntry> = HashSet::new();
+        let cur_rules = current.config.as_deref().unwrap_or_default();
+
+        for cur_rule in cur_rules {
+            if absent_rules
+                .iter()
+                .any(|absent_rule| absent_rule.is_match(cur_rule))
+                && !desired_rules.iter().any(|r| r.is_match(cur_rule))
+            {
+                let mut new_rule = cur_rule.clone();
+                new_rule.state = Some(RouteState::Absent);
+                changed_rules.insert(new_rule);
+            }
+        }
+
+        for rule in desired_rules.as_slice() {
+            if !cur_rules.iter().any(|cur_rule| rule.is_match(cur_rule)) {
+                changed_rules.insert(rule.clone());
+            }
+        }
+
+        let mut changed_rules: Vec<RouteRuleEntry> =
+            changed_rules.drain().collect();
+        changed_rules.sort_unstable();
+
+        Ok(Self {
+            changed_rules,
+            desired_rules,
+            absent_rules,
+            desired,
+            current,
+        })
+    }
+
+    pub(crate) fn is_changed(&self) -> bool {
+        !self.changed_rules.is_empty()
+    }
+
+    pub(crate) fn gen_state_for_apply(&self) -> RouteRules {
+        RouteRules {
+            config: if self.changed_rules.is_empty() {
+                None
+            } else {
+                Some(self.changed_rules.clone())
+            },
+        }
+    }
+
+    pub(crate) fn verify(
+        &self,
+        current: &RouteRules,
+    ) -> Result<(), NipartError> {
+        let cur_rules = current.config.as_deref().unwrap_or_default();
+        for rule in self.desired_rules.as_slice() {
+            if !cur_rules.iter().any(|cur_rule| rule.is_match(cur_rule)) {
+                return Err(NipartError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: desired route rule {rule} not \
+                         found after applied"
+                    ),
+                ));
+            }
+        }
+        for absent_rule in self.absent_rules.as_slice() {
+            if let Some(cur_rule) = cur_rules.iter().find(|cur_rule| {
+                absent_rule.is_match(cur_rule)
+                    && !self.desired_rules.iter().any(|r| r.is_match(cur_rule))
+            }) {
+                return Err(NipartError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: route rule {cur_rule} still \
+                         exists after applied with {absent_rule}"
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RouteRules {
+    /// Return new RouteRules data contains the merged data.
+    pub(crate) fn merge(&self, new_rules: &Self) -> Result<Self, NipartError> {
+        new_rules.validate()?;
+
+        if let Some(new_rules) = new_rules.config.as_ref() {
+            let mut rule_sets: HashSet<RouteRuleEntry> = HashSet::new();
+            for new_rule in new_rules.iter().filter(|r| !r.is_absent()) {
+                rule_sets.insert(new_rule.clone());
+            }
+            if let Some(old_rules) = self.config.as_ref() {
+                for old_rule in old_rules {
+                    if new_rules
+                        .iter()
+                        .any(|r| r.is_absent() && r.is_match(old_rule))
+                    {
+                        let mut absent_rule = old_rule.clone();
+                        absent_rule.state = Some(RouteState::Absent);
+                        rule_sets.insert(absent_rule);
+                    } else {
+                        rule_sets.insert(old_rule.clone());
+                    }
+                }
+            }
+            let mut rules: Vec<RouteRuleEntry> =
+                rule_sets.into_iter().collect();
+            rules.sort_unstable();
+
+            Ok(RouteRules {
+                config: Some(rules),
+            })
+        } else {
+            Ok(self.clone())
+        }
+    }
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod dns;
 mod gen_diff;
+mod hostname;
 mod iface;
 mod iface_state;
 mod iface_trait;
@@ -10,8 +12,11 @@ mod ip;
 mod link_state;
 mod merged;
 mod net_state;
+mod nexthop;
 mod revert;
 mod route;
+mod route_metric_policy;
+mod route_rule;
 mod state_options;
 mod trigger;
 mod value;
@@ -24,6 +29,8 @@ pub(crate) mod deserializer;
 pub(crate) mod serializer;
 
 pub use self::{
+    dns::{DnsResolver, DnsResolverConfig},
+    hostname::HostNameState,
     iface::Interface,
     iface_state::InterfaceState,
     iface_trait::NipartInterface,
@@ -34,25 +41,45 @@ pub use self::{
         BondLacpRate, BondMode, BondOptions, BondPortConfig,
         BondPrimaryReselect, BondXmitHashPolicy, BridgeVlanConfig,
         BridgeVlanMode, BridgeVlanRange, BridgeVlanTrunkTag, DummyInterface,
-        EthernetConfig, EthernetDuplex, EthernetInterface, Interfaces,
+        EthernetConfig, EthernetDuplex, EthernetInterface, HsrConfig,
+        HsrInterface, HsrProtocol, InfiniBandConfig, InfiniBandInterface,
+        InfiniBandMode, Interfaces, IpTunnelConfig, IpTunnelInterface,
+        IpTunnelMode, IpVlanConfig, IpVlanInterface, IpVlanMode,
         LinuxBridgeConfig, LinuxBridgeInterface,
         LinuxBridgeMulticastRouterType, LinuxBridgeOptions,
         LinuxBridgePortConfig, LinuxBridgeStpOptions, LoopbackInterface,
+        MacSecCipher, MacSecConfig, MacSecInterface, MacSecValidate,
+        MacVlanConfig, MacVlanInterface, MacVlanMode, MacVtapInterface,
         OvsBridgeConfig, OvsBridgeInterface, OvsBridgePortConfig, OvsInterface,
+        SrIovConfig, SrIovVfConfig, TunConfig, TunInterface, TunMode,
         UnknownInterface, VethConfig, VlanConfig, VlanInterface, VlanProtocol,
-        VlanQosMapping, VlanRegistrationProtocol, WifiAuthType,
-        WifiCfgInterface, WifiConfig, WifiPhyInterface, WifiState,
-        WireguardConfig, WireguardInterface, WireguardIpAddress,
-        WireguardPeerConfig,
+        VlanQosMapping, VlanRegistrationProtocol, VrfConfig, VrfInterface,
+        VxlanConfig, VxlanInterface, WifiAuthType, WifiCfgInterface,
+        WifiConfig, WifiPhyInterface, WifiState, WireguardConfig,
+        WireguardInterface, WireguardIpAddress, WireguardPeerConfig,
+    },
+    ip::{
+        DhcpClientId, DhcpDuid, DhcpState, DhcpV6Mode, InterfaceIpAddr,
+        InterfaceIpAddrOrigin, InterfaceIpv4, InterfaceIpv6, Ipv4LinkLocal,
+        Ipv6AddrGenMode, Ipv6Privacy,
     },
-    ip::{DhcpState, InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6},
     link_state::InterfaceLinkState,
     merged::{
-        MergedInterface, MergedInterfaces, MergedNetworkState, MergedRoutes,
+        MergedDnsResolver, MergedHostNameState, MergedInterface,
+        MergedInterfaces, MergedNetworkState, MergedNextHops,
+        MergedRouteMetricPolicy, MergedRouteRules, MergedRoutes,
     },
     net_state::NetworkState,
+    nexthop::{NextHopEntry, NextHopGroupMember, NextHopResilient, NextHops},
     route::{RouteEntry, RouteState, RouteType, Routes},
-    state_options::{NipartApplyOption, NipartQueryOption, NipartStateKind},
+    route_metric_policy::RouteMetricPolicy,
+    route_rule::{
+        RouteRuleAction, RouteRuleEntry, RouteRuleFamily, RouteRules,
+    },
+    state_options::{
+        NipartApplyOption, NipartIpFamily, NipartQueryOption,
+        NipartRouteFilter, NipartRouteProtocol, NipartStateKind,
+    },
     trigger::InterfaceTrigger,
     version::CUR_SCHEMA_VERSION,
     wait_online::{NipartWaitOnline, NipartWaitOnlineCondition},
@@ -3,8 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    CUR_SCHEMA_VERSION, ErrorKind, Interfaces, JsonDisplayHideSecrets,
-    NipartError, NipartWaitOnline, Routes,
+    CUR_SCHEMA_VERSION, DnsResolver, ErrorKind, HostNameState, Interfaces,
+    JsonDisplayHideSecrets, NextHops, NipartError, NipartWaitOnline,
+    RouteMetricPolicy, RouteRules, Routes,
 };
 
 #[derive(
@@ -24,9 +25,28 @@ pub struct NetworkState {
     /// previous saved configuration.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wait_online: Option<NipartWaitOnline>,
+    /// Host name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<HostNameState>,
+    /// DNS resolver
+    #[serde(default, rename = "dns-resolver")]
+    pub dns: DnsResolver,
+    /// Route metric policy for routes learned from DHCP
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "route-metric-policy"
+    )]
+    pub route_metric_policy: Option<RouteMetricPolicy>,
     /// Routes
     #[serde(default)]
     pub routes: Routes,
+    /// Route rules
+    #[serde(default, rename = "route-rules")]
+    pub route_rules: RouteRules,
+    /// Kernel nexthop objects
+    #[serde(default)]
+    pub nexthops: NextHops,
     /// Network interfaces
     #[serde(default, rename = "interfaces")]
     pub ifaces: Interfaces,
@@ -38,8 +58,13 @@ impl Default for NetworkState {
             version: Some(CUR_SCHEMA_VERSION),
             description: None,
             wait_online: None,
+            hostname: None,
+            route_metric_policy: None,
             ifaces: Default::default(),
+            dns: Default::default(),
             routes: Default::default(),
+            route_rules: Default::default(),
+            nexthops: Default::default(),
         }
     }
 }
@@ -64,6 +89,14 @@ impl NetworkState {
             ..Default::default()
         } || (self.ifaces.is_empty()
             && self.routes.is_empty()
+            && self.route_rules.is_empty()
+            && self.nexthops.is_empty()
+            && self.dns.is_empty()
+            && self.hostname.as_ref().is_none_or(|h| h.is_empty())
+            && self
+                .route_metric_policy
+                .as_ref()
+                .is_none_or(|p| p.is_empty())
             && self.wait_online.is_none())
     }
 
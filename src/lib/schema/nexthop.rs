@@ -0,0 +1,373 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::IpAddr, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, JsonDisplay, NipartError, NipartIpFamily, RouteState};
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Kernel nexthop objects, also known as `ip nexthop`.
+/// Routes could reference nexthop object via [crate::RouteEntry.nexthop_id].
+pub struct NextHops {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// When applying, `None` means preserve current nexthop objects.
+    /// This property is not overriding but adding specified nexthop objects
+    /// to existing ones. Nexthop object with the same ID will be replaced.
+    /// To delete a nexthop object, please set [NextHopEntry.state] as
+    /// [RouteState::Absent]. Any property of absent [NextHopEntry] set to
+    /// `None` means wildcard. For example, this [crate::NetworkState] could
+    /// remove all nexthop objects using interface eth1(showing in yaml):
+    /// ```yaml
+    /// nexthops:
+    ///   config:
+    ///   - device: eth1
+    ///     state: absent
+    /// ```
+    pub config: Option<Vec<NextHopEntry>>,
+}
+
+impl NextHops {
+    /// Whether configured nexthop objects is empty or undefined.
+    pub fn is_empty(&self) -> bool {
+        self.config.as_ref().is_none_or(|nhs| nhs.is_empty())
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), NipartError> {
+        let mut ids: Vec<u32> = Vec::new();
+        for nh in self
+            .config
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|nh| !nh.is_absent())
+        {
+            nh.validate()?;
+            // The `NextHopEntry::validate()` confirmed ID is defined.
+            let id = nh.id.unwrap_or_default();
+            if ids.contains(&id) {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Duplicate nexthop object ID {id}"),
+                ));
+            }
+            ids.push(id);
+        }
+        Ok(())
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Nexthop object entry
+pub struct NextHopEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Only used for delete nexthop object when applying.
+    pub state: Option<RouteState>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Nexthop object ID, mandatory for non-absent nexthop object.
+    pub id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Gateway IP address, `device` is mandatory when defined.
+    pub gateway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Outgoing interface name.
+    pub device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// IP family of nexthop object, only required for nexthop object
+    /// without `gateway`, for example blackhole or device only nexthop
+    /// object. Undefined means the IP family of `gateway` or
+    /// [NipartIpFamily::Ipv4]. Cannot be used along with `group`.
+    pub family: Option<NipartIpFamily>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Silently drop the packets. Cannot be used along with `gateway`,
+    /// `device` or `group`.
+    pub blackhole: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Members of nexthop group. Cannot be used along with `gateway`,
+    /// `device` or `blackhole`.
+    pub group: Option<Vec<NextHopGroupMember>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Use resilient hashing for this nexthop group, undefined means
+    /// multipath hashing. Only valid when `group` defined.
+    pub resilient: Option<NextHopResilient>,
+}
+
+impl NextHopEntry {
+    pub(crate) fn is_absent(&self) -> bool {
+        matches!(self.state, Some(RouteState::Absent))
+    }
+
+    pub(crate) fn is_group(&self) -> bool {
+        self.group.is_some()
+    }
+
+    pub(crate) fn is_blackhole(&self) -> bool {
+        self.blackhole == Some(true)
+    }
+
+    fn validate(&self) -> Result<(), NipartError> {
+        if self.id.is_none() || self.id == Some(0) {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!("Nexthop object {self} should have non-zero ID"),
+            ));
+        }
+        if self.is_blackhole()
+            && (self.gateway.is_some()
+                || self.device.is_some()
+                || self.group.is_some())
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Blackhole nexthop object {self} cannot have gateway, \
+                     device or group"
+                ),
+            ));
+        }
+        if let Some(members) = self.group.as_deref() {
+            if self.gateway.is_some()
+                || self.device.is_some()
+                || self.family.is_some()
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Nexthop group {self} cannot have gateway, device or \
+                         family"
+                    ),
+                ));
+            }
+            if members.is_empty() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Nexthop group {self} should have members"),
+                ));
+            }
+            for member in members {
+                if Some(member.id) == self.id {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Nexthop group {self} cannot hold itself as member"
+                        ),
+                    ));
+                }
+                if let Some(weight) = member.weight
+                    && !(1..=256).contains(&weight)
+                {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid nexthop group member weight {weight}, \
+                             should be in the range of 1 to 256"
+                        ),
+                    ));
+                }
+            }
+        } else {
+            if self.resilient.is_some() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Nexthop object {self} is not nexthop group, cannot \
+                         have resilient setting"
+                    ),
+                ));
+            }
+            if !self.is_blackhole() && self.device.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Nexthop object {self} should have device defined \
+                         unless it is blackhole or nexthop group"
+                    ),
+                ));
+            }
+        }
+        if let Some(gateway) = self.gateway.as_deref() {
+            let Ok(gateway) = IpAddr::from_str(gateway) else {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid gateway IP address {gateway} of nexthop \
+                         object {self}"
+                    ),
+                ));
+            };
+            if let Some(family) = self.family
+                && family != ip_family(&gateway)
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Nexthop object {self} has gateway {gateway} not \
+                         matching with family {family}"
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the desired nexthop object (self) matches with another.
+    /// Undefined property of absent nexthop object means wildcard.
+    pub(crate) fn is_match(&self, other: &Self) -> bool {
+        if self.id.is_some() && self.id != other.id {
+            return false;
+        }
+        if self.gateway.is_some() && self.gateway != other.gateway {
+            return false;
+        }
+        if self.device.is_some() && self.device != other.device {
+            return false;
+        }
+        if self.family.is_some() && self.family != other.family {
+            return false;
+        }
+        if self.blackhole.is_some()
+            && self.is_blackhole() != other.is_blackhole()
+        {
+            return false;
+        }
+        if self.group.is_some() && self.group != other.group {
+            return false;
+        }
+        if self.resilient.is_some() && self.resilient != other.resilient {
+            return false;
+        }
+        true
+    }
+
+    // * Sanitize gateway IP address
+    // * Set weight of nexthop group member to 1 if undefined
+    // * Remove `blackhole: false`
+    // * Set undefined family of non-group nexthop object to the family of
+    //   gateway or IPv4
+    pub(crate) fn sanitize(&mut self) -> Result<(), NipartError> {
+        let mut gateway_family = None;
+        if let Some(gateway) = self.gateway.as_ref()
+            && let Ok(ip) = IpAddr::from_str(gateway)
+        {
+            gateway_family = Some(ip_family(&ip));
+            let new_gateway = ip.to_string();
+            if gateway != &new_gateway {
+                log::warn!(
+                    "Nexthop gateway {gateway} sanitized to {new_gateway}"
+                );
+                self.gateway = Some(new_gateway);
+            }
+        }
+        if !self.is_absent() {
+            if self.blackhole == Some(false) {
+                self.blackhole = None;
+            }
+            if !self.is_group() && self.family.is_none() {
+                self.family =
+                    Some(gateway_family.unwrap_or(NipartIpFamily::Ipv4));
+            }
+            if let Some(members) = self.group.as_mut() {
+                for member in members.iter_mut() {
+                    if member.weight.is_none() {
+                        member.weight = Some(1);
+                    }
+                }
+                members.sort_unstable();
+            }
+        }
+        Ok(())
+    }
+}
+
+fn ip_family(ip: &IpAddr) -> NipartIpFamily {
+    if ip.is_ipv6() {
+        NipartIpFamily::Ipv6
+    } else {
+        NipartIpFamily::Ipv4
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct NextHopGroupMember {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    /// ID of member nexthop object
+    pub id: u32,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Weight of this member, the valid range is 1-256. Undefined means 1.
+    pub weight: Option<u16>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct NextHopResilient {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Number of nexthop buckets. Undefined means kernel default 128.
+    pub buckets: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Seconds of idle time before nexthop bucket could be migrated.
+    pub idle_timer: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Seconds of group been unbalanced before forcing bucket migration.
+    pub unbalanced_timer: Option<u32>,
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DnsResolver, MergedDnsResolver};
+
+impl MergedDnsResolver {
+    /// Restore static DNS resolver configuration to pre-apply state, purge
+    /// all if pre-apply state has no static configuration.
+    pub(crate) fn generate_revert(&self) -> DnsResolver {
+        DnsResolver {
+            config: if self.is_changed() {
+                Some(self.current.config.clone().unwrap_or_default())
+            } else {
+                None
+            },
+            ..Default::default()
+        }
+    }
+}
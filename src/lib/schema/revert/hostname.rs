@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{HostNameState, MergedHostNameState};
+
+impl MergedHostNameState {
+    /// Restore changed host name properties to pre-apply state.
+    pub(crate) fn generate_revert(&self) -> Option<HostNameState> {
+        let apply = self.for_apply.as_ref()?;
+        let cur = self.current.clone().unwrap_or_default();
+        Some(HostNameState {
+            config: apply
+                .config
+                .as_ref()
+                .map(|_| cur.config.clone().unwrap_or_default()),
+            dhcp_override: apply
+                .dhcp_override
+                .map(|_| cur.dhcp_override.unwrap_or_default()),
+            ..Default::default()
+        })
+    }
+}
@@ -1,7 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod base_iface;
+mod dns;
+mod hostname;
 mod iface;
 mod inter_ifaces;
 mod net_state;
+mod nexthop;
+mod route_metric_policy;
+mod route_rule;
 mod value;
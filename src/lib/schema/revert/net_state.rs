@@ -17,6 +17,13 @@ impl NetworkState {
         )?;
         Ok(Self {
             ifaces: merged_state.ifaces.generate_revert()?,
+            route_rules: merged_state.route_rules.generate_revert(),
+            nexthops: merged_state.nexthops.generate_revert(),
+            dns: merged_state.dns.generate_revert(),
+            hostname: merged_state.hostname.generate_revert(),
+            route_metric_policy: merged_state
+                .route_metric_policy
+                .generate_revert(),
             ..Default::default()
         })
     }
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedNextHops, NextHopEntry, NextHops, RouteState};
+
+impl MergedNextHops {
+    /// Restore nexthop objects to pre-apply state: removed or replaced
+    /// nexthop objects are restored and newly added ones are marked as
+    /// absent.
+    pub(crate) fn generate_revert(&self) -> NextHops {
+        if !self.is_changed() {
+            return NextHops::default();
+        }
+        let cur_nexthops = self.current.config.as_deref().unwrap_or_default();
+        let mut nexthops: Vec<NextHopEntry> = self
+            .changed_nexthops
+            .iter()
+            .map(|nh| {
+                if nh.is_absent() {
+                    let mut nh = nh.clone();
+                    nh.state = None;
+                    nh
+                } else if let Some(cur_nh) =
+                    cur_nexthops.iter().find(|cur_nh| cur_nh.id == nh.id)
+                {
+                    cur_nh.clone()
+                } else {
+                    NextHopEntry {
+                        id: nh.id,
+                        state: Some(RouteState::Absent),
+                        ..Default::default()
+                    }
+                }
+            })
+            .collect();
+        nexthops.sort_unstable_by_key(|nh| (!nh.is_absent(), nh.id));
+        NextHops {
+            config: Some(nexthops),
+        }
+    }
+}
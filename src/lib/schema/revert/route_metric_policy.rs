@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedRouteMetricPolicy, RouteMetricPolicy};
+
+impl MergedRouteMetricPolicy {
+    /// Restore route metric policy to pre-apply state.
+    pub(crate) fn generate_revert(&self) -> Option<RouteMetricPolicy> {
+        self.for_apply.as_ref()?;
+        let mut cur = self.current.clone().unwrap_or_default();
+        cur.include_default();
+        Some(cur)
+    }
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedRouteRules, RouteRuleEntry, RouteRules, RouteState};
+
+impl MergedRouteRules {
+    /// Restore route rules to pre-apply state: removed rules are added back
+    /// and newly added rules are marked as absent.
+    pub(crate) fn generate_revert(&self) -> RouteRules {
+        if !self.is_changed() {
+            return RouteRules::default();
+        }
+        let mut rules: Vec<RouteRuleEntry> = self
+            .changed_rules
+            .iter()
+            .map(|rule| {
+                let mut rule = rule.clone();
+                rule.state = if rule.is_absent() {
+                    None
+                } else {
+                    Some(RouteState::Absent)
+                };
+                rule
+            })
+            .collect();
+        rules.sort_unstable();
+        RouteRules {
+            config: Some(rules),
+        }
+    }
+}
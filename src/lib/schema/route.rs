@@ -18,10 +18,15 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use super::ip::{is_ipv6_addr, sanitize_ip_network};
-use crate::{ErrorKind, Interfaces, JsonDisplay, NipartError, NipartInterface};
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, JsonDisplay, NipartError,
+    NipartInterface,
+};
 
 const DEFAULT_TABLE_ID: u32 = 254; // main route table ID
 const LOOPBACK_IFACE_NAME: &str = "lo";
+// Kernel clamps bigger advmss to 65535 minus 40 bytes of IPv4 and TCP headers
+const MAX_ADVMSS: u32 = 65535 - 40;
 
 #[derive(
     Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
@@ -97,7 +102,21 @@ impl Routes {
                                 route.route_type.unwrap()
                             ),
                         ));
+                    } else if route.nexthop_id.is_some()
+                        && (route.next_hop_iface.is_some()
+                            || route.next_hop_addr.is_some()
+                            || route.weight.is_some())
+                    {
+                        return Err(NipartError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Route referring nexthop object cannot have \
+                                 next hop interface, next hop address or \
+                                 weight: {route:?}"
+                            ),
+                        ));
                     } else if route.next_hop_iface.is_none()
+                        && route.nexthop_id.is_none()
                         && route.is_unicast()
                     {
                         return Err(NipartError::new(
@@ -275,9 +294,21 @@ pub struct RouteEntry {
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
     pub advmss: Option<u32>,
-    // TODO: Store the routes to the route table specified VRF bind to.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub vrf_name: Option<String>,
+    /// ID of kernel nexthop object defined in [crate::NextHops].
+    /// Cannot be used along with `next-hop-interface`, `next-hop-address`
+    /// or `weight`.
+    /// Serialize and deserialize to/from `next-hop-id`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        rename = "next-hop-id",
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub nexthop_id: Option<u32>,
+    /// The VRF interface holding the next hop interface of this route.
+    /// Route table ID will be set to the one VRF bind to. Internal use only.
+    #[serde(skip)]
+    pub(crate) vrf_name: Option<String>,
 }
 
 #[derive(
@@ -372,9 +403,12 @@ impl RouteEntry {
         if self.advmss.is_some() && self.advmss != other.advmss {
             return false;
         }
-        // if self.vrf_name.is_some() && self.vrf_name != other.vrf_name {
-        //    return false;
-        // }
+        if self.nexthop_id.is_some() && self.nexthop_id != other.nexthop_id {
+            return false;
+        }
+        if self.vrf_name.is_some() && self.vrf_name != other.vrf_name {
+            return false;
+        }
         true
     }
 
@@ -400,7 +434,7 @@ impl RouteEntry {
                 self.destination.as_deref().unwrap_or(""),
                 self.next_hop_addr.as_deref().unwrap_or(""),
                 self.source.as_deref().unwrap_or(""),
-                // self.vrf_name.as_deref().unwrap_or(""),
+                self.vrf_name.as_deref().unwrap_or(""),
             ],
             vec![
                 self.table_id.unwrap_or(DEFAULT_TABLE_ID),
@@ -415,6 +449,7 @@ impl RouteEntry {
                     .unwrap_or_default()
                     .into(),
                 self.advmss.unwrap_or_default(),
+                self.nexthop_id.unwrap_or_default(),
             ],
         )
     }
@@ -457,25 +492,16 @@ impl RouteEntry {
                 self.source = Some(new_src);
             }
         }
-        if let Some(weight) = self.weight {
-            if !(1..=256).contains(&weight) {
-                return Err(NipartError::new(
-                    ErrorKind::InvalidArgument,
-                    format!(
-                        "Invalid ECMP route weight {weight}, should be in the \
-                         range of 1 to 256"
-                    ),
-                ));
-            }
-            if let Some(dst) = self.destination.as_deref()
-                && is_ipv6_addr(dst)
-            {
-                return Err(NipartError::new(
-                    ErrorKind::NoSupport,
-                    "IPv6 ECMP route with weight is not supported yet"
-                        .to_string(),
-                ));
-            }
+        if let Some(weight) = self.weight
+            && !(1..=256).contains(&weight)
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid ECMP route weight {weight}, should be in the \
+                     range of 1 to 256"
+                ),
+            ));
         }
         if let Some(cwnd) = self.cwnd
             && cwnd == 0
@@ -497,6 +523,22 @@ impl RouteEntry {
                 "The value of 'advmss' cannot be 0".to_string(),
             ));
         }
+        if let Some(advmss) = self.advmss
+            && advmss > MAX_ADVMSS
+        {
+            return Err(NipartError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The value of 'advmss' cannot be bigger than \
+                     {MAX_ADVMSS}, got {advmss}"
+                ),
+            ));
+        }
+        // Kernel does not store route metric with value 0, hence disabled
+        // quickack is identical to undefined.
+        if self.quickack == Some(false) {
+            self.quickack = None;
+        }
         Ok(())
     }
 
@@ -505,6 +547,62 @@ impl RouteEntry {
             == Some(true)
     }
 
+    /// Place route into the route table of VRF if its next hop interface is
+    /// VRF or VRF port.
+    pub(crate) fn resolve_vrf<'a, F>(
+        &mut self,
+        get_kernel_iface: F,
+    ) -> Result<(), NipartError>
+    where
+        F: Fn(&str) -> Option<&'a Interface>,
+    {
+        let Some(via_iface) =
+            self.next_hop_iface.as_deref().and_then(&get_kernel_iface)
+        else {
+            return Ok(());
+        };
+        let vrf_iface = if let Interface::Vrf(vrf_iface) = via_iface {
+            Some(vrf_iface)
+        } else if via_iface.base_iface().controller_type
+            == Some(InterfaceType::Vrf)
+            && let Some(Interface::Vrf(vrf_iface)) = via_iface
+                .base_iface()
+                .controller
+                .as_deref()
+                .and_then(&get_kernel_iface)
+        {
+            Some(vrf_iface)
+        } else {
+            None
+        };
+        let Some(vrf_iface) = vrf_iface else {
+            return Ok(());
+        };
+        let Some(vrf_table_id) = vrf_iface.table_id() else {
+            return Ok(());
+        };
+        match self.table_id {
+            None | Some(RouteEntry::USE_DEFAULT_ROUTE_TABLE) => {
+                self.table_id = Some(vrf_table_id);
+            }
+            Some(table_id) if table_id != vrf_table_id => {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Route {self} is next hop to interface {} which is \
+                         bind to VRF {} with route table {vrf_table_id}, but \
+                         desired route table {table_id}",
+                        via_iface.name(),
+                        vrf_iface.name(),
+                    ),
+                ));
+            }
+            _ => (),
+        }
+        self.vrf_name = Some(vrf_iface.name().to_string());
+        Ok(())
+    }
+
     pub(crate) fn is_unicast(&self) -> bool {
         self.route_type.is_none()
             || u8::from(self.route_type.unwrap()) == RTN_UNICAST
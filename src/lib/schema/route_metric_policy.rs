@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{InterfaceType, JsonDisplay};
+
+/// Policy of assigning metric to routes learned from DHCP, so the preferred
+/// default gateway does not depend on interface index.
+/// The example yaml output of [crate::NetworkState] with default policy
+/// would be:
+/// ```yml
+/// route-metric-policy:
+///   base-metrics:
+///     bond: 100
+///     ethernet: 100
+///     veth: 100
+///     vlan: 100
+///     wifi-phy: 600
+///     wireguard: 1000
+///   default-base-metric: 800
+/// ```
+/// The metric of route is `base-metric + iface-index + index` where
+/// `iface-index` is the kernel index of interface, so routes from different
+/// interfaces of the same type do not share metric, and `index` is the order
+/// of gateway provided by DHCP server. The `auto-route-metric` of interface
+/// IP configuration takes precedence over this policy.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct RouteMetricPolicy {
+    /// Base metric per interface type. When applying, `None` means
+    /// preserve current setting, otherwise replace current setting
+    /// entirely. Undefined in stored policy means
+    /// [RouteMetricPolicy::default_base_metrics()].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_metrics: Option<BTreeMap<InterfaceType, u32>>,
+    /// Base metric for interface type not listed in `base-metrics`.
+    /// When applying, `None` means preserve current setting. Undefined in
+    /// stored policy means [RouteMetricPolicy::DEFAULT_BASE_METRIC].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_base_metric: Option<u32>,
+}
+
+impl RouteMetricPolicy {
+    pub const DEFAULT_BASE_METRIC: u32 = 800;
+
+    /// Wired network is preferred over wireless, and VPN is least preferred
+    /// as default gateway.
+    pub fn default_base_metrics() -> BTreeMap<InterfaceType, u32> {
+        BTreeMap::from([
+            (InterfaceType::Ethernet, 100),
+            (InterfaceType::Veth, 100),
+            (InterfaceType::Bond, 100),
+            (InterfaceType::Vlan, 100),
+            (InterfaceType::WifiPhy, 600),
+            (InterfaceType::Wireguard, 1000),
+        ])
+    }
+
+    /// Policy with all properties set to default values.
+    pub fn new_default() -> Self {
+        Self {
+            base_metrics: Some(Self::default_base_metrics()),
+            default_base_metric: Some(Self::DEFAULT_BASE_METRIC),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.base_metrics.is_none() && self.default_base_metric.is_none()
+    }
+
+    /// Base metric of specified interface type.
+    pub fn base_metric(&self, iface_type: &InterfaceType) -> u32 {
+        let default_base_metric = self
+            .default_base_metric
+            .unwrap_or(Self::DEFAULT_BASE_METRIC);
+        match self.base_metrics.as_ref() {
+            Some(metrics) => metrics.get(iface_type).copied(),
+            None => Self::default_base_metrics().get(iface_type).copied(),
+        }
+        .unwrap_or(default_base_metric)
+    }
+
+    /// Fill undefined properties with default values.
+    pub fn include_default(&mut self) {
+        if self.base_metrics.is_none() {
+            self.base_metrics = Some(Self::default_base_metrics());
+        }
+        if self.default_base_metric.is_none() {
+            self.default_base_metric = Some(Self::DEFAULT_BASE_METRIC);
+        }
+    }
+}
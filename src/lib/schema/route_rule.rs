@@ -0,0 +1,382 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use super::ip::{is_ipv6_addr, sanitize_ip_network};
+use crate::{ErrorKind, JsonDisplay, NipartError, RouteState};
+
+const DEFAULT_TABLE_ID: u32 = 254; // main route table ID
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, JsonDisplay,
+)]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Routing policy rules, also known as `ip rule`.
+/// Rules created by kernel(e.g. lookup `local`, `main` and `default` route
+/// tables) are not included.
+pub struct RouteRules {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// When applying, `None` means preserve current rules.
+    /// This property is not overriding but adding specified rules to
+    /// existing rules. To delete a rule entry, please set
+    /// [RouteRuleEntry.state] as [RouteState::Absent]. Any property of absent
+    /// [RouteRuleEntry] set to `None` means wildcard. For example, this
+    /// [crate::NetworkState] could remove all rules looking up route table
+    /// 500(showing in yaml):
+    /// ```yaml
+    /// route-rules:
+    ///   config:
+    ///   - table-id: 500
+    ///     state: absent
+    /// ```
+    pub config: Option<Vec<RouteRuleEntry>>,
+}
+
+impl RouteRules {
+    /// Whether configured rules is empty or undefined.
+    pub fn is_empty(&self) -> bool {
+        self.config.as_ref().is_none_or(|rules| rules.is_empty())
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), NipartError> {
+        for rule in self.config.as_deref().unwrap_or_default() {
+            if rule.is_absent() {
+                continue;
+            }
+            if rule.fwmask.is_some() && rule.fwmark.is_none() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Route rule {rule} has fwmask without fwmark"),
+                ));
+            }
+            if !rule.is_lookup_table() && rule.table_id.is_some() {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Route rule {rule} with action {} cannot have table-id",
+                        rule.action.unwrap_or_default()
+                    ),
+                ));
+            }
+            if !rule.is_lookup_table() && rule.suppress_prefix_length.is_some()
+            {
+                return Err(NipartError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Route rule {rule} with action {} cannot have \
+                         suppress-prefix-length",
+                        rule.action.unwrap_or_default()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum RouteRuleFamily {
+    /// Deserialize and serialize from/to `ipv4`.
+    Ipv4,
+    /// Deserialize and serialize from/to `ipv6`.
+    Ipv6,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonDisplay,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum RouteRuleAction {
+    /// Lookup route table defined in `table-id`.
+    /// Deserialize and serialize from/to `table`.
+    #[default]
+    Table,
+    /// Silently drop the packet.
+    /// Deserialize and serialize from/to `blackhole`.
+    Blackhole,
+    /// Drop the packet with ICMP network unreachable.
+    /// Deserialize and serialize from/to `unreachable`.
+    Unreachable,
+    /// Drop the packet with ICMP communication administratively prohibited.
+    /// Deserialize and serialize from/to `prohibit`.
+    Prohibit,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonDisplay)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Route rule entry
+pub struct RouteRuleEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Only used for delete rule when applying.
+    pub state: Option<RouteState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// IP family of this rule. Undefined means the family of `from` or `to`
+    /// address, or IPv4 if neither defined.
+    pub family: Option<RouteRuleFamily>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Priority of this rule, lower value has higher priority. Undefined
+    /// means kernel choose the one before the lowest priority existing rule.
+    pub priority: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Source address or network to match.
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Destination address or network to match.
+    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Incoming interface to match.
+    pub iif: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Outgoing interface to match.
+    pub oif: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Firewall mark to match.
+    pub fwmark: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Mask applied to firewall mark before matching. Undefined means
+    /// 0xffffffff.
+    pub fwmask: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Route table to lookup. Undefined means main route table 254 when
+    /// action is [RouteRuleAction::Table].
+    pub table_id: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Reject routing decision with prefix length less than or equal to
+    /// specified value.
+    pub suppress_prefix_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Action of this rule. Undefined means [RouteRuleAction::Table].
+    pub action: Option<RouteRuleAction>,
+}
+
+impl RouteRuleEntry {
+    pub(crate) fn is_absent(&self) -> bool {
+        matches!(self.state, Some(RouteState::Absent))
+    }
+
+    pub(crate) fn is_ipv6(&self) -> bool {
+        self.family == Some(RouteRuleFamily::Ipv6)
+    }
+
+    pub(crate) fn is_lookup_table(&self) -> bool {
+        self.action.unwrap_or_default() == RouteRuleAction::Table
+    }
+
+    /// Whether the desired rule (self) matches with another.
+    /// Undefined property of absent rule means wildcard.
+    pub(crate) fn is_match(&self, other: &Self) -> bool {
+        if self.family.is_some() && self.family != other.family {
+            return false;
+        }
+        if self.priority.is_some() && self.priority != other.priority {
+            return false;
+        }
+        if self.from.is_some() && self.from != other.from {
+            return false;
+        }
+        if self.to.is_some() && self.to != other.to {
+            return false;
+        }
+        if self.iif.is_some() && self.iif != other.iif {
+            return false;
+        }
+        if self.oif.is_some() && self.oif != other.oif {
+            return false;
+        }
+        if self.fwmark.is_some() && self.fwmark != other.fwmark {
+            return false;
+        }
+        if self.fwmask.is_some() && self.fwmask != other.fwmask {
+            return false;
+        }
+        if self.table_id.is_some() && self.table_id != other.table_id {
+            return false;
+        }
+        if self.suppress_prefix_length.is_some()
+            && self.suppress_prefix_length != other.suppress_prefix_length
+        {
+            return false;
+        }
+        if (self.action.is_some() || !self.is_absent())
+            && self.action.unwrap_or_default()
+                != other.action.unwrap_or_default()
+        {
+            return false;
+        }
+        true
+    }
+
+    // Return tuple of all properties with default value unwrapped.
+    fn sort_key(
+        &self,
+    ) -> (
+        bool,
+        RouteRuleFamily,
+        u32,
+        Vec<&str>,
+        Vec<u32>,
+        RouteRuleAction,
+    ) {
+        (
+            !self.is_absent(),
+            self.family.unwrap_or(RouteRuleFamily::Ipv4),
+            self.priority.unwrap_or_default(),
+            vec![
+                self.from.as_deref().unwrap_or(""),
+                self.to.as_deref().unwrap_or(""),
+                self.iif.as_deref().unwrap_or(""),
+                self.oif.as_deref().unwrap_or(""),
+            ],
+            vec![
+                self.fwmark.unwrap_or_default(),
+                self.fwmask.unwrap_or(u32::MAX),
+                self.table_id.unwrap_or_default(),
+                self.suppress_prefix_length.unwrap_or(u32::MAX),
+            ],
+            self.action.unwrap_or_default(),
+        )
+    }
+
+    // * Sanitize `from` and `to` to network address
+    // * Set `family` based on `from` and `to`, default to IPv4 for non-absent
+    //   rule
+    // * Set `table-id` to main route table for non-absent rule looking up route
+    //   table
+    // * Remove `fwmask` 0xffffffff which is kernel default
+    pub(crate) fn sanitize(&mut self) -> Result<(), NipartError> {
+        for (name, addr) in [("from", &mut self.from), ("to", &mut self.to)] {
+            if let Some(ip_net) = addr.as_ref() {
+                if ip_net.is_empty() {
+                    *addr = None;
+                    continue;
+                }
+                let new_ip_net = sanitize_ip_network(ip_net)?;
+                if ip_net != &new_ip_net {
+                    log::warn!(
+                        "Route rule {name} {ip_net} sanitized to {new_ip_net}"
+                    );
+                    *addr = Some(new_ip_net);
+                }
+            }
+        }
+        for ip_net in [self.from.as_deref(), self.to.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            let family = if is_ipv6_addr(ip_net) {
+                RouteRuleFamily::Ipv6
+            } else {
+                RouteRuleFamily::Ipv4
+            };
+            match self.family {
+                None => self.family = Some(family),
+                Some(f) if f != family => {
+                    return Err(NipartError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Route rule {self} is holding {ip_net} which is \
+                             not {f} address"
+                        ),
+                    ));
+                }
+                _ => (),
+            }
+        }
+        if self.fwmask == Some(u32::MAX) {
+            self.fwmask = None;
+        }
+        if !self.is_absent() {
+            if self.family.is_none() {
+                self.family = Some(RouteRuleFamily::Ipv4);
+            }
+            if self.action == Some(RouteRuleAction::Table) {
+                self.action = None;
+            }
+            if self.is_lookup_table() && self.table_id.is_none() {
+                self.table_id = Some(DEFAULT_TABLE_ID);
+            }
+        }
+        Ok(())
+    }
+}
+
+// For Vec::dedup()
+impl PartialEq for RouteRuleEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.sort_key() == other.sort_key()
+    }
+}
+
+// For Vec::sort_unstable()
+impl Ord for RouteRuleEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+// For ord
+impl Eq for RouteRuleEntry {}
+
+// For ord
+impl PartialOrd for RouteRuleEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for RouteRuleEntry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sort_key().hash(state);
+    }
+}
@@ -17,6 +17,15 @@ pub struct NipartQueryOption {
     /// Whether include secrets/passwords, default to false.
     #[serde(default)]
     pub include_secrets: bool,
+    /// Filter on routes to query, useful for host holding large routing
+    /// table. Undefined means all routes. Only effective on
+    /// [NipartStateKind::RunningNetworkState].
+    #[serde(
+        default,
+        rename = "route-filter",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub route_filter: Option<NipartRouteFilter>,
 }
 
 impl Default for NipartQueryOption {
@@ -25,6 +34,7 @@ impl Default for NipartQueryOption {
             version: CUR_SCHEMA_VERSION,
             kind: NipartStateKind::default(),
             include_secrets: false,
+            route_filter: None,
         }
     }
 }
@@ -48,6 +58,110 @@ impl NipartQueryOption {
         self.include_secrets = value;
         self
     }
+
+    pub fn route_filter(mut self, value: NipartRouteFilter) -> Self {
+        self.route_filter = Some(value);
+        self
+    }
+}
+
+#[derive(
+    Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonDisplay,
+)]
+#[non_exhaustive]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct NipartRouteFilter {
+    /// Only include routes created by specified protocols. Undefined means
+    /// all supported protocols.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocols: Option<Vec<NipartRouteProtocol>>,
+    /// Only include routes in specified route tables. Undefined means all
+    /// route tables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tables: Option<Vec<u32>>,
+    /// Only include routes of specified IP families. Undefined means both
+    /// IPv4 and IPv6. Routes of other IP families are not dumped from
+    /// kernel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub families: Option<Vec<NipartIpFamily>>,
+    /// Maximum number of routes to include in running routes and config
+    /// routes respectively. Undefined means unlimited.
+    /// This does not limit the kernel route dump, routes are still fully
+    /// dumped from kernel and then truncated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<u32>,
+    /// Do not include running routes, only query static routes for
+    /// `routes.config`. Default to false.
+    #[serde(default)]
+    pub skip_running: bool,
+}
+
+impl NipartRouteFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn protocols(mut self, value: Vec<NipartRouteProtocol>) -> Self {
+        self.protocols = Some(value);
+        self
+    }
+
+    pub fn tables(mut self, value: Vec<u32>) -> Self {
+        self.tables = Some(value);
+        self
+    }
+
+    pub fn families(mut self, value: Vec<NipartIpFamily>) -> Self {
+        self.families = Some(value);
+        self
+    }
+
+    pub fn max_entries(mut self, value: u32) -> Self {
+        self.max_entries = Some(value);
+        self
+    }
+
+    pub fn skip_running(mut self) -> Self {
+        self.skip_running = true;
+        self
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonDisplay,
+)]
+#[non_exhaustive]
+#[serde(rename_all = "kebab-case")]
+/// Protocol(origin) of route
+pub enum NipartRouteProtocol {
+    /// Route installed during boot
+    Boot,
+    /// Route installed by administrator
+    Static,
+    /// Route installed by IPv6 router advertisement
+    Ra,
+    /// Route installed by DHCP
+    Dhcp,
+    /// Route installed by multicast routing daemon
+    Mrouted,
+    /// Route installed by keepalived daemon
+    #[serde(rename = "keepalived")]
+    KeepAlived,
+    /// Route installed by babel daemon
+    Babel,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonDisplay,
+)]
+#[non_exhaustive]
+#[serde(rename_all = "kebab-case")]
+/// IP family
+pub enum NipartIpFamily {
+    /// Deserialize and serialize from/to `ipv4`.
+    Ipv4,
+    /// Deserialize and serialize from/to `ipv6`.
+    Ipv6,
 }
 
 #[derive(
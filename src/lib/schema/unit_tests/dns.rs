@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DnsResolver, DnsResolverConfig, ErrorKind, MergedDnsResolver};
+
+fn gen_current() -> DnsResolver {
+    serde_yaml::from_str(
+        r#"
+        config:
+          server:
+          - 192.0.2.1
+          search:
+          - example.org
+          options:
+          - rotate
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_dns_sanitize_server_and_search() {
+    let mut desired: DnsResolver = serde_yaml::from_str(
+        r#"
+        config:
+          server:
+          - 2001:db8:0::1
+          - 192.0.2.1
+          - 2001:db8::1
+          search:
+          - Example.ORG.
+          - example.org
+        "#,
+    )
+    .unwrap();
+    desired.config.as_mut().unwrap().sanitize().unwrap();
+
+    let conf = desired.config.unwrap();
+    assert_eq!(
+        conf.server,
+        Some(vec!["2001:db8::1".to_string(), "192.0.2.1".to_string()])
+    );
+    assert_eq!(conf.search, Some(vec!["example.org".to_string()]));
+}
+
+#[test]
+fn test_dns_sanitize_invalid_server() {
+    for server in ["192.0.2.256", "fe80::1", "192.0.2.1%eth1"] {
+        let mut conf =
+            DnsResolverConfig::new(vec![server.to_string()], Vec::new());
+        let result = conf.sanitize();
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_dns_link_local_server_with_iface() {
+    let mut conf =
+        DnsResolverConfig::new(vec!["fe80::1%eth1".to_string()], Vec::new());
+    conf.sanitize().unwrap();
+    assert_eq!(conf.server, Some(vec!["fe80::1%eth1".to_string()]));
+}
+
+#[test]
+fn test_dns_partial_desire_preserve_current() {
+    let desired: DnsResolver = serde_yaml::from_str(
+        r#"
+        config:
+          server:
+          - 192.0.2.2
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedDnsResolver::new(desired, gen_current()).unwrap();
+
+    let apply_conf = merged.for_apply.unwrap();
+    assert_eq!(apply_conf.server, Some(vec!["192.0.2.2".to_string()]));
+    assert_eq!(apply_conf.search, Some(vec!["example.org".to_string()]));
+    assert_eq!(apply_conf.options, Some(vec!["rotate".to_string()]));
+}
+
+#[test]
+fn test_dns_purge_static_config() {
+    let desired: DnsResolver = serde_yaml::from_str("config: {}").unwrap();
+
+    let merged = MergedDnsResolver::new(desired, gen_current()).unwrap();
+
+    assert!(merged.for_apply.unwrap().is_empty());
+}
+
+#[test]
+fn test_dns_no_change() {
+    let merged =
+        MergedDnsResolver::new(DnsResolver::default(), gen_current()).unwrap();
+    assert!(!merged.is_changed());
+
+    let merged = MergedDnsResolver::new(gen_current(), gen_current()).unwrap();
+    assert!(!merged.is_changed());
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, HostNameState, MergedHostNameState};
+
+fn gen_current() -> Option<HostNameState> {
+    serde_yaml::from_str(
+        r#"
+        running: host-b.example.org
+        config: host-a
+        dhcp-override: false
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_hostname_invalid() {
+    for name in ["-host", "host_a", "host..example.org", &"a".repeat(65)] {
+        let desired: HostNameState =
+            serde_yaml::from_str(&format!("config: \"{name}\"")).unwrap();
+        let result = MergedHostNameState::new(Some(desired), gen_current());
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_hostname_only_changed_properties_for_apply() {
+    let desired: HostNameState = serde_yaml::from_str(
+        r#"
+        config: host-a.
+        dhcp-override: true
+        "#,
+    )
+    .unwrap();
+
+    let merged =
+        MergedHostNameState::new(Some(desired), gen_current()).unwrap();
+
+    let apply = merged.for_apply.unwrap();
+    assert_eq!(apply.config, None);
+    assert_eq!(apply.dhcp_override, Some(true));
+}
+
+#[test]
+fn test_hostname_running_is_ignored() {
+    let desired: HostNameState =
+        serde_yaml::from_str("running: host-c").unwrap();
+
+    let merged =
+        MergedHostNameState::new(Some(desired), gen_current()).unwrap();
+
+    assert!(!merged.is_changed());
+}
+
+#[test]
+fn test_hostname_revert() {
+    let desired: HostNameState =
+        serde_yaml::from_str("config: host-c").unwrap();
+
+    let merged =
+        MergedHostNameState::new(Some(desired), gen_current()).unwrap();
+    let revert = merged.generate_revert().unwrap();
+
+    assert_eq!(revert.config.as_deref(), Some("host-a"));
+    assert_eq!(revert.dhcp_override, None);
+}
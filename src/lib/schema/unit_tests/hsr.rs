@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, MergedNetworkState, NetworkState};
+
+fn merge_desired(yml: &str) -> Result<MergedNetworkState, crate::NipartError> {
+    MergedNetworkState::new(
+        serde_yaml::from_str(yml).unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    )
+}
+
+fn assert_invalid_argument(
+    result: Result<MergedNetworkState, crate::NipartError>,
+) {
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_hsr_ports_controller() {
+    let merged = merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: hsr0
+          type: hsr
+          hsr:
+            port1: dummy1
+            port2: dummy2
+            supervision-address: 01:15:4e:00:01:2d
+            protocol: hsr
+            version: 1
+        - name: dummy1
+          type: dummy
+        - name: dummy2
+          type: dummy
+        "#,
+    )
+    .unwrap();
+
+    for port in ["dummy1", "dummy2"] {
+        let port_iface = merged
+            .ifaces
+            .kernel_ifaces
+            .get(port)
+            .and_then(|i| i.for_apply.as_ref())
+            .unwrap();
+        assert_eq!(port_iface.base_iface().controller.as_deref(), Some("hsr0"));
+    }
+}
+
+#[test]
+fn test_hsr_same_ports() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: hsr0
+          type: hsr
+          hsr:
+            port1: dummy1
+            port2: dummy1
+        - name: dummy1
+          type: dummy
+        "#,
+    ));
+}
+
+#[test]
+fn test_hsr_port_overbooked_by_bond() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: hsr0
+          type: hsr
+          hsr:
+            port1: dummy1
+            port2: dummy2
+        - name: bond0
+          type: bond
+          link-aggregation:
+            mode: active-backup
+            ports:
+            - dummy2
+        - name: dummy1
+          type: dummy
+        - name: dummy2
+          type: dummy
+        "#,
+    ));
+}
+
+#[test]
+fn test_hsr_missing_port() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: hsr0
+          type: hsr
+          hsr:
+            port1: dummy1
+        - name: dummy1
+          type: dummy
+        "#,
+    ));
+}
+
+#[test]
+fn test_prp_with_version() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: prp0
+          type: hsr
+          hsr:
+            port1: dummy1
+            port2: dummy2
+            protocol: prp
+            version: 1
+        - name: dummy1
+          type: dummy
+        - name: dummy2
+          type: dummy
+        "#,
+    ));
+}
+
+#[test]
+fn test_hsr_invalid_supervision_address() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: hsr0
+          type: hsr
+          hsr:
+            port1: dummy1
+            port2: dummy2
+            supervision-address: 00:11:22:33:44:55
+        - name: dummy1
+          type: dummy
+        - name: dummy2
+          type: dummy
+        "#,
+    ));
+}
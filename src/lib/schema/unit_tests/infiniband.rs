@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, MergedNetworkState, NetworkState};
+
+fn merge_desired(yml: &str) -> Result<MergedNetworkState, crate::NipartError> {
+    MergedNetworkState::new(
+        serde_yaml::from_str(yml).unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    )
+}
+
+#[test]
+fn test_ib_pkey_full_membership_bit() {
+    let merged = merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ib0.0001
+          type: infiniband
+          infiniband:
+            base-iface: ib0
+            pkey: "0x0001"
+            mode: connected
+        "#,
+    )
+    .unwrap();
+
+    let iface = merged
+        .ifaces
+        .kernel_ifaces
+        .get("ib0.0001")
+        .and_then(|i| i.for_apply.as_ref())
+        .unwrap();
+    if let Interface::InfiniBand(iface) = iface {
+        let ib_conf = iface.infiniband.as_ref().unwrap();
+        assert_eq!(ib_conf.pkey, Some(0x8001));
+        assert_eq!(ib_conf.base_iface.as_deref(), Some("ib0"));
+    } else {
+        panic!("Expecting InfiniBand interface, but got {iface:?}");
+    }
+}
+
+#[test]
+fn test_ib_pkey_iface_without_pkey() {
+    let result = merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ib0.8001
+          type: infiniband
+          infiniband:
+            base-iface: ib0
+        "#,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ib_reserved_pkey() {
+    let result = merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ib0.8000
+          type: infiniband
+          infiniband:
+            base-iface: ib0
+            pkey: "0x8000"
+        "#,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -1,7 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::ip::sanitize_ip_network;
-use crate::ErrorKind;
+use crate::{
+    DhcpClientId, DhcpDuid, DhcpState, DhcpV6Mode, ErrorKind,
+    InterfaceIpAddrOrigin, InterfaceIpv4, InterfaceIpv6, Ipv4LinkLocal,
+    Ipv6AddrGenMode, Ipv6Privacy,
+};
 
 #[test]
 fn test_sanitize_ip_network_empty_str() {
@@ -87,3 +91,436 @@ fn test_sanitize_ip_network_ipv6_net() {
         "2001:db8:1::/64"
     );
 }
+
+#[test]
+fn test_ipv6_sanitize_dhcp_mode_without_dhcp() {
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: false
+        dhcp-mode: prefix-delegation
+        dhcp-state: done
+        "#,
+    )
+    .unwrap();
+    ipv6_conf.sanitize(None).unwrap();
+    assert_eq!(ipv6_conf.dhcp_mode, None);
+    assert_eq!(ipv6_conf.dhcp_state, None);
+}
+
+#[test]
+fn test_ipv6_sanitize_keep_dynamic_addr_when_dhcp() {
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        dhcp-mode: stateful
+        address:
+        - ip: 2001:db8:a::100
+          prefix-length: 128
+          valid-life-time: 3600sec
+          preferred-life-time: 1800sec
+        "#,
+    )
+    .unwrap();
+    ipv6_conf.sanitize(None).unwrap();
+    assert_eq!(ipv6_conf.dhcp_mode, Some(DhcpV6Mode::Stateful));
+    let addrs = ipv6_conf.addresses.as_ref().unwrap();
+    assert_eq!(addrs.len(), 1);
+    assert_eq!(addrs[0].valid_life_time.as_deref(), Some("3600sec"));
+}
+
+#[test]
+fn test_ipv6_sanitize_drop_dynamic_addr_when_not_dhcp() {
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: false
+        autoconf: true
+        address:
+        - ip: 2001:db8:a::100
+          prefix-length: 64
+          valid-life-time: 3600sec
+          preferred-life-time: 1800sec
+        "#,
+    )
+    .unwrap();
+    ipv6_conf.sanitize(None).unwrap();
+    assert_eq!(ipv6_conf.addresses, Some(Vec::new()));
+}
+
+#[test]
+fn test_dhcp_state_serialize() {
+    let state: DhcpState =
+        serde_yaml::from_str("error:no DHCPv6 server found").unwrap();
+    assert_eq!(
+        state,
+        DhcpState::Error("no DHCPv6 server found".to_string())
+    );
+}
+
+#[test]
+fn test_ipv4_auto_options() {
+    let ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        auto-routes: true
+        auto-gateway: false
+        auto-dns: "false"
+        auto-route-table-id: 100
+        auto-route-metric: "500"
+        "#,
+    )
+    .unwrap();
+    assert!(ipv4_conf.is_auto_routes());
+    assert!(!ipv4_conf.is_auto_gateway());
+    assert!(!ipv4_conf.is_auto_dns());
+    assert_eq!(ipv4_conf.auto_table_id(), 100);
+    assert_eq!(ipv4_conf.auto_route_metric, Some(500));
+}
+
+#[test]
+fn test_ipv4_auto_routes_off_implies_no_gateway() {
+    let ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        auto-routes: false
+        auto-route-table-id: 0
+        "#,
+    )
+    .unwrap();
+    assert!(!ipv4_conf.is_auto_gateway());
+    assert_eq!(ipv4_conf.auto_table_id(), 254);
+}
+
+#[test]
+fn test_ipv4_verify_auto_options_not_reported() {
+    let mut desired: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        auto-gateway: false
+        "#,
+    )
+    .unwrap();
+    let mut current: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        "#,
+    )
+    .unwrap();
+    desired.sanitize_before_verify(&mut current);
+    assert_eq!(current.auto_gateway, Some(false));
+}
+
+#[test]
+fn test_ipv4_dhcp_client_id_and_duid() {
+    let ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        dhcp-client-id: iaid+duid
+        dhcp-duid: uuid
+        "#,
+    )
+    .unwrap();
+    assert_eq!(ipv4_conf.dhcp_client_id, Some(DhcpClientId::IaidDuid));
+    assert_eq!(ipv4_conf.dhcp_duid, Some(DhcpDuid::Uuid));
+}
+
+#[test]
+fn test_dhcp_custom_client_id_and_duid_lower_case() {
+    let client_id: DhcpClientId =
+        serde_yaml::from_str("00:6E:69:70:61:72:74").unwrap();
+    assert_eq!(
+        client_id,
+        DhcpClientId::Custom("00:6e:69:70:61:72:74".to_string())
+    );
+    assert_eq!(client_id.custom_bytes().unwrap().len(), 7);
+
+    let duid: DhcpDuid = serde_yaml::from_str("00:04:AB:CD").unwrap();
+    assert_eq!(serde_yaml::to_string(&duid).unwrap(), "00:04:ab:cd\n");
+}
+
+#[test]
+fn test_dhcp_invalid_client_id_and_duid() {
+    assert!(serde_yaml::from_str::<DhcpClientId>("ff").is_err());
+    assert!(serde_yaml::from_str::<DhcpClientId>("not-hex").is_err());
+    assert!(serde_yaml::from_str::<DhcpDuid>("00:04").is_err());
+    assert!(serde_yaml::from_str::<DhcpDuid>("00:4:ab").is_err());
+}
+
+#[test]
+fn test_ipv6_dhcp_duid_removed_when_dhcp_off() {
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: false
+        dhcp-duid: ll
+        "#,
+    )
+    .unwrap();
+    ipv6_conf.sanitize(None).unwrap();
+    assert_eq!(ipv6_conf.dhcp_duid, None);
+}
+
+#[test]
+fn test_ipv4_dhcp_client_id_kept_when_current_dhcp_enabled() {
+    let current: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        "#,
+    )
+    .unwrap();
+    let mut ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp-client-id: duid
+        dhcp-duid: uuid
+        "#,
+    )
+    .unwrap();
+    ipv4_conf.sanitize(Some(&current)).unwrap();
+    assert_eq!(ipv4_conf.dhcp_client_id, Some(DhcpClientId::Duid));
+    assert_eq!(ipv4_conf.dhcp_duid, Some(DhcpDuid::Uuid));
+
+    let mut ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp-client-id: duid
+        "#,
+    )
+    .unwrap();
+    ipv4_conf.sanitize(None).unwrap();
+    assert_eq!(ipv4_conf.dhcp_client_id, None);
+}
+
+#[test]
+fn test_ipv6_dhcp_duid_kept_when_current_dhcp_enabled() {
+    let current: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        "#,
+    )
+    .unwrap();
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp-duid: ll
+        "#,
+    )
+    .unwrap();
+    ipv6_conf.sanitize(Some(&current)).unwrap();
+    assert_eq!(ipv6_conf.dhcp_duid, Some(DhcpDuid::Ll));
+}
+
+#[test]
+fn test_ipv4_addr_origin_static_with_life_time() {
+    let ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: false
+        address:
+        - ip: 192.0.2.1
+          prefix-length: 24
+          valid-life-time: 60sec
+          preferred-life-time: 60sec
+          origin: static
+        - ip: 192.0.2.2
+          prefix-length: 24
+          valid-life-time: 60sec
+          preferred-life-time: 60sec
+        "#,
+    )
+    .unwrap();
+    let addrs = ipv4_conf.addresses.as_ref().unwrap();
+
+    assert!(!addrs[0].is_auto());
+    assert!(addrs[1].is_auto());
+}
+
+#[test]
+fn test_ipv6_verify_ignore_link_local_and_dhcp_addrs() {
+    let mut desired: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        address:
+        - ip: 2001:db8:1::1
+          prefix-length: 64
+        "#,
+    )
+    .unwrap();
+    let mut current: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        address:
+        - ip: 2001:db8:1::1
+          prefix-length: 64
+          origin: static
+        - ip: 2001:db8:2::9
+          prefix-length: 128
+          valid-life-time: 60sec
+          preferred-life-time: 60sec
+          origin: dhcp
+        - ip: fe80::1
+          prefix-length: 64
+          origin: link-local
+        "#,
+    )
+    .unwrap();
+
+    desired.sanitize_before_verify(&mut current);
+
+    assert_eq!(current.addresses.as_ref().unwrap().len(), 1);
+    assert_eq!(desired.addresses, current.addresses);
+}
+
+#[test]
+fn test_ipv4_dhcp_to_static_convert_dhcp_addr() {
+    let old: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        address:
+        - ip: 192.0.2.9
+          prefix-length: 24
+          valid-life-time: 60sec
+          preferred-life-time: 60sec
+          origin: dhcp
+        "#,
+    )
+    .unwrap();
+    let mut merged = old.clone();
+    merged.dhcp = Some(false);
+
+    merged.post_merge(&old);
+
+    let addr = &merged.addresses.as_ref().unwrap()[0];
+    assert_eq!(addr.valid_life_time, None);
+    assert_eq!(addr.preferred_life_time, None);
+    assert_eq!(addr.origin, None);
+}
+
+#[test]
+fn test_ip_addr_origin_serialize() {
+    assert_eq!(
+        serde_yaml::to_string(&InterfaceIpAddrOrigin::LinkLocal).unwrap(),
+        "link-local\n"
+    );
+}
+
+#[test]
+fn test_ipv6_addr_gen_mode_token_privacy() {
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        autoconf: true
+        addr-gen-mode: stable-privacy
+        token: ::1:2:3:4
+        privacy: prefer-temporary
+        "#,
+    )
+    .unwrap();
+
+    ipv6_conf.sanitize(None).unwrap();
+
+    assert_eq!(
+        ipv6_conf.addr_gen_mode,
+        Some(Ipv6AddrGenMode::StablePrivacy)
+    );
+    assert_eq!(ipv6_conf.token, Some("::1:2:3:4".parse().unwrap()));
+    assert_eq!(ipv6_conf.privacy, Some(Ipv6Privacy::PreferTemporary));
+}
+
+#[test]
+fn test_ipv6_token_with_prefix() {
+    let mut ipv6_conf: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        token: 2001:db8::1
+        "#,
+    )
+    .unwrap();
+
+    let result = ipv6_conf.sanitize(None);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipv6_remove_token_verify() {
+    let mut desired: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        token: "::"
+        "#,
+    )
+    .unwrap();
+    let mut current: InterfaceIpv6 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        "#,
+    )
+    .unwrap();
+
+    desired.sanitize_before_verify(&mut current);
+
+    assert_eq!(current.token, desired.token);
+}
+
+#[test]
+fn test_ipv4_link_local_fallback() {
+    let mut ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: true
+        link-local: fallback
+        "#,
+    )
+    .unwrap();
+
+    ipv4_conf.sanitize(None).unwrap();
+
+    assert_eq!(ipv4_conf.link_local, Some(Ipv4LinkLocal::Fallback));
+    assert!(ipv4_conf.is_link_local_fallback());
+    assert!(!ipv4_conf.is_link_local());
+}
+
+#[test]
+fn test_ipv4_link_local_fallback_without_dhcp() {
+    let ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: true
+        dhcp: false
+        link-local: fallback
+        "#,
+    )
+    .unwrap();
+
+    assert!(!ipv4_conf.is_link_local_fallback());
+}
+
+#[test]
+fn test_ipv4_disabled_remove_link_local() {
+    let mut ipv4_conf: InterfaceIpv4 = serde_yaml::from_str(
+        r#"
+        enabled: false
+        link-local: enabled
+        "#,
+    )
+    .unwrap();
+
+    ipv4_conf.sanitize(None).unwrap();
+
+    assert_eq!(ipv4_conf.link_local, None);
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, MergedNetworkState, NetworkState,
+    NipartInterface,
+};
+
+fn merge_desired(yml: &str) -> Result<MergedNetworkState, crate::NipartError> {
+    MergedNetworkState::new(
+        serde_yaml::from_str(yml).unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    )
+}
+
+fn assert_invalid_argument(
+    result: Result<MergedNetworkState, crate::NipartError>,
+) {
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip_tunnel_deserialize_types() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"
+        version: 1
+        interfaces:
+        - name: gre1
+          type: gre
+          ip-tunnel:
+            remote: 192.0.2.2
+            key: 100
+        - name: ip6tnl1
+          type: ip6tnl
+          ip-tunnel:
+            remote: 2001:db8::2
+            encap-limit: 4
+        "#,
+    )
+    .unwrap();
+
+    let gre_iface = net_state.ifaces.kernel_ifaces.get("gre1").unwrap();
+    assert!(matches!(gre_iface, Interface::IpTunnel(_)));
+    assert_eq!(gre_iface.iface_type(), &InterfaceType::Gre);
+    let ip6tnl_iface = net_state.ifaces.kernel_ifaces.get("ip6tnl1").unwrap();
+    assert!(matches!(ip6tnl_iface, Interface::IpTunnel(_)));
+    assert_eq!(ip6tnl_iface.iface_type(), &InterfaceType::Ip6Tnl);
+}
+
+#[test]
+fn test_ip_tunnel_missing_remote() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: gre1
+          type: gre
+          ip-tunnel:
+            local: 192.0.2.1
+        "#,
+    ));
+}
+
+#[test]
+fn test_ip_tunnel_wrong_ip_family() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ip6gre1
+          type: ip6gre
+          ip-tunnel:
+            remote: 192.0.2.2
+        "#,
+    ));
+}
+
+#[test]
+fn test_ip_tunnel_key_on_non_gre() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ipip1
+          type: ipip
+          ip-tunnel:
+            remote: 192.0.2.2
+            key: 100
+        "#,
+    ));
+}
+
+#[test]
+fn test_ip_tunnel_encap_limit_on_ipv4_tunnel() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: sit1
+          type: sit
+          ip-tunnel:
+            remote: 192.0.2.2
+            encap-limit: 4
+        "#,
+    ));
+}
+
+#[test]
+fn test_ip_tunnel_mode_on_non_ip6tnl() {
+    assert_invalid_argument(merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ip6gre1
+          type: ip6gre
+          ip-tunnel:
+            remote: 2001:db8::2
+            mode: ipip6
+        "#,
+    ));
+}
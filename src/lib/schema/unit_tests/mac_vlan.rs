@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, MacVlanMode, MergedNetworkState, NetworkState,
+};
+
+fn merge_desired(yml: &str) -> Result<MergedNetworkState, crate::NipartError> {
+    MergedNetworkState::new(
+        serde_yaml::from_str(yml).unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    )
+}
+
+#[test]
+fn test_mac_vlan_deserialize_mode() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"
+        name: mac0
+        type: mac-vlan
+        mac-vlan:
+          base-iface: eth1
+          mode: passthru
+          accept-all-mac: false
+        "#,
+    )
+    .unwrap();
+    if let Interface::MacVlan(iface) = iface {
+        let conf = iface.mac_vlan.unwrap();
+        assert_eq!(conf.mode, Some(MacVlanMode::Passthru));
+        assert_eq!(conf.promiscuous, Some(false));
+    } else {
+        panic!("Should be MAC VLAN interface, but got {iface:?}");
+    }
+}
+
+#[test]
+fn test_mac_vlan_invalid_mode() {
+    let result = serde_yaml::from_str::<Interface>(
+        r#"
+        name: mac0
+        type: mac-vlan
+        mac-vlan:
+          base-iface: eth1
+          mode: l3
+        "#,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_mac_vtap_disable_promiscuous_in_bridge_mode() {
+    let result = merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: macvtap0
+          type: mac-vtap
+          mac-vtap:
+            base-iface: eth1
+            mode: bridge
+            promiscuous: false
+        "#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipvlan_private_and_vepa() {
+    let result = merge_desired(
+        r#"
+        version: 1
+        interfaces:
+        - name: ipvlan0
+          type: ipvlan
+          ipvlan:
+            base-iface: eth1
+            mode: l3s
+            private: true
+            vepa: true
+        "#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
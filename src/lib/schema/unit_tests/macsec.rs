@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, MacSecInterface, NetworkState, NipartInterface,
+};
+
+const TEST_CAK: &str = "50b71a8ef0bd5751ea76de6d6c98c03a";
+const TEST_CKN: &str =
+    "f2b4297d39da7330910a74abc0449feb45b5c0b9fc23df1430e1898fcf1c4550";
+
+fn gen_macsec_state() -> NetworkState {
+    serde_yaml::from_str(&format!(
+        r#"---
+        version: 1
+        interfaces:
+        - name: macsec0
+          type: macsec
+          macsec:
+            base-iface: eth1
+            port: 1
+            encrypt: true
+            mka-cak: {TEST_CAK}
+            mka-ckn: {TEST_CKN}
+        "#
+    ))
+    .unwrap()
+}
+
+fn get_macsec_iface(state: &NetworkState) -> &MacSecInterface {
+    if let Some(Interface::MacSec(iface)) =
+        state.ifaces.kernel_ifaces.get("macsec0")
+    {
+        iface
+    } else {
+        panic!("Failed to find MACsec interface macsec0 in {state:?}");
+    }
+}
+
+#[test]
+fn test_macsec_hide_secrets_split() {
+    let mut state = gen_macsec_state();
+    let secret_state = state.hide_secrets();
+
+    let conf = get_macsec_iface(&state).macsec.as_ref().unwrap();
+    assert_eq!(conf.mka_cak.as_deref(), Some(NetworkState::HIDE_SECRET_STR));
+    assert_eq!(conf.mka_ckn.as_deref(), Some(NetworkState::HIDE_SECRET_STR));
+    assert!(!format!("{state}").contains(TEST_CAK));
+
+    let secret_conf = get_macsec_iface(&secret_state).macsec.as_ref().unwrap();
+    assert_eq!(secret_conf.mka_cak.as_deref(), Some(TEST_CAK));
+    assert_eq!(secret_conf.mka_ckn.as_deref(), Some(TEST_CKN));
+}
+
+#[test]
+fn test_macsec_debug_hide_secrets() {
+    let state = gen_macsec_state();
+    let iface = get_macsec_iface(&state);
+
+    assert!(!format!("{iface:?}").contains(TEST_CAK));
+    assert!(!format!("{iface}").contains(TEST_CAK));
+}
+
+#[test]
+fn test_macsec_invalid_cak() {
+    let mut iface: MacSecInterface = serde_yaml::from_str(
+        r#"---
+        name: macsec0
+        type: macsec
+        macsec:
+          base-iface: eth1
+          mka-cak: not-a-hex-string
+          mka-ckn: "0123"
+        "#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(None);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_mka_with_sci() {
+    let mut iface: MacSecInterface = serde_yaml::from_str(&format!(
+        r#"---
+        name: macsec0
+        type: macsec
+        macsec:
+          base-iface: eth1
+          sci: 1234
+          mka-cak: {TEST_CAK}
+          mka-ckn: {TEST_CKN}
+        "#
+    ))
+    .unwrap();
+
+    let result = iface.sanitize(None);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
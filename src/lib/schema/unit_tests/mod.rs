@@ -1,5 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod dns;
+mod hostname;
+mod hsr;
+mod infiniband;
 mod ip;
+mod ip_tunnel;
 mod loopback;
+mod mac_vlan;
+mod macsec;
+mod nexthop;
+mod route;
+mod route_metric_policy;
+mod route_rule;
+mod sriov;
+mod tun;
+mod vrf;
+mod vxlan;
 mod wifi;
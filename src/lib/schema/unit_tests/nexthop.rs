@@ -0,0 +1,258 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, MergedNextHops, NetworkState, NextHops, NipartIpFamily,
+    RouteState,
+};
+
+fn gen_current_nexthops() -> NextHops {
+    serde_yaml::from_str(
+        r#"
+        config:
+        - id: 1
+          gateway: 192.0.2.254
+          device: eth1
+          family: ipv4
+        - id: 2
+          gateway: 198.51.100.254
+          device: eth2
+          family: ipv4
+        - id: 3
+          blackhole: true
+          family: ipv4
+        - id: 10
+          group:
+          - id: 1
+            weight: 1
+          - id: 2
+            weight: 1
+        "#,
+    )
+    .unwrap()
+}
+
+fn assert_invalid(desired: &str) {
+    let desired: NextHops = serde_yaml::from_str(desired).unwrap();
+
+    let result = MergedNextHops::new(desired, NextHops::default());
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_nexthop_without_id() {
+    assert_invalid(
+        r#"
+        config:
+        - gateway: 192.0.2.254
+          device: eth1
+        "#,
+    );
+}
+
+#[test]
+fn test_nexthop_blackhole_with_device() {
+    assert_invalid(
+        r#"
+        config:
+        - id: 1
+          blackhole: true
+          device: eth1
+        "#,
+    );
+}
+
+#[test]
+fn test_nexthop_group_with_family() {
+    assert_invalid(
+        r#"
+        config:
+        - id: 10
+          family: ipv4
+          group:
+          - id: 1
+        "#,
+    );
+}
+
+#[test]
+fn test_nexthop_gateway_not_match_family() {
+    assert_invalid(
+        r#"
+        config:
+        - id: 1
+          gateway: 2001:db8::1
+          device: eth1
+          family: ipv4
+        "#,
+    );
+}
+
+#[test]
+fn test_nexthop_group_invalid_weight() {
+    assert_invalid(
+        r#"
+        config:
+        - id: 10
+          group:
+          - id: 1
+            weight: 257
+        "#,
+    );
+}
+
+#[test]
+fn test_nexthop_resilient_without_group() {
+    assert_invalid(
+        r#"
+        config:
+        - id: 1
+          device: eth1
+          resilient:
+            buckets: 32
+        "#,
+    );
+}
+
+#[test]
+fn test_nexthop_sanitize_group_weight() {
+    let desired: NextHops = serde_yaml::from_str(
+        r#"
+        config:
+        - id: 10
+          group:
+          - id: 2
+          - id: 1
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedNextHops::new(desired, gen_current_nexthops()).unwrap();
+
+    assert!(merged.changed_nexthops.is_empty());
+}
+
+#[test]
+fn test_nexthop_absent_wildcard() {
+    let desired: NextHops = serde_yaml::from_str(
+        r#"
+        config:
+        - device: eth1
+          state: absent
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedNextHops::new(desired, gen_current_nexthops()).unwrap();
+
+    assert_eq!(merged.changed_nexthops.len(), 1);
+    assert_eq!(merged.changed_nexthops[0].id, Some(1));
+    assert_eq!(merged.changed_nexthops[0].state, Some(RouteState::Absent));
+}
+
+#[test]
+fn test_nexthop_gen_diff_and_revert() {
+    let current = NetworkState {
+        nexthops: gen_current_nexthops(),
+        ..Default::default()
+    };
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"
+        nexthops:
+          config:
+          - id: 3
+            state: absent
+          - id: 2
+            gateway: 198.51.100.253
+            device: eth2
+          - id: 20
+            group:
+            - id: 1
+              weight: 3
+            - id: 2
+            resilient:
+              buckets: 32
+        "#,
+    )
+    .unwrap();
+
+    let diff = desired.gen_diff(&current).unwrap();
+    let diff_nexthops = diff.nexthops.config.unwrap();
+    assert_eq!(diff_nexthops.len(), 3);
+
+    let revert = desired.generate_revert(&current).unwrap();
+    let revert_nexthops = revert.nexthops.config.unwrap();
+    assert_eq!(revert_nexthops.len(), 3);
+    assert!(
+        revert_nexthops.iter().any(|nh| nh.id == Some(3)
+            && nh.state.is_none()
+            && nh.is_blackhole())
+    );
+    assert!(revert_nexthops.iter().any(|nh| {
+        nh.id == Some(2) && nh.gateway.as_deref() == Some("198.51.100.254")
+    }));
+    assert!(revert_nexthops.iter().any(|nh| {
+        nh.id == Some(20) && nh.state == Some(RouteState::Absent)
+    }));
+}
+
+#[test]
+fn test_nexthop_blackhole_family() {
+    let desired: NextHops = serde_yaml::from_str(
+        r#"
+        config:
+        - id: 3
+          blackhole: true
+        - id: 4
+          blackhole: true
+          family: ipv6
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedNextHops::new(desired, gen_current_nexthops()).unwrap();
+
+    assert_eq!(merged.changed_nexthops.len(), 1);
+    assert_eq!(merged.changed_nexthops[0].id, Some(4));
+    assert_eq!(
+        merged.changed_nexthops[0].family,
+        Some(NipartIpFamily::Ipv6)
+    );
+}
+
+#[test]
+fn test_nexthop_device_only_family() {
+    let desired: NextHops = serde_yaml::from_str(
+        r#"
+        config:
+        - id: 4
+          device: eth1
+        - id: 5
+          device: eth1
+          family: ipv6
+        - id: 6
+          gateway: 2001:db8::1
+          device: eth1
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedNextHops::new(desired, gen_current_nexthops()).unwrap();
+
+    assert_eq!(merged.changed_nexthops.len(), 3);
+    assert_eq!(
+        merged.changed_nexthops[0].family,
+        Some(NipartIpFamily::Ipv4)
+    );
+    assert_eq!(
+        merged.changed_nexthops[1].family,
+        Some(NipartIpFamily::Ipv6)
+    );
+    assert_eq!(
+        merged.changed_nexthops[2].family,
+        Some(NipartIpFamily::Ipv6)
+    );
+}
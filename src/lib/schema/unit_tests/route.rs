@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, RouteEntry};
+
+#[test]
+fn test_route_ipv6_ecmp_weight() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 2001:db8:2::/64
+        next-hop-interface: eth1
+        next-hop-address: 2001:db8:1::fe
+        weight: 2
+        "#,
+    )
+    .unwrap();
+
+    route.sanitize().unwrap();
+
+    assert_eq!(route.weight, Some(2));
+}
+
+#[test]
+fn test_route_invalid_ecmp_weight() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        next-hop-address: 192.0.2.254
+        weight: 257
+        "#,
+    )
+    .unwrap();
+
+    let result = route.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_quickack_false_sanitized_to_none() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        next-hop-address: 192.0.2.254
+        initcwnd: 20
+        quickack: false
+        "#,
+    )
+    .unwrap();
+
+    route.sanitize().unwrap();
+
+    assert_eq!(route.initcwnd, Some(20));
+    assert_eq!(route.quickack, None);
+}
+
+#[test]
+fn test_route_advmss_too_big() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        next-hop-address: 192.0.2.254
+        advmss: 65536
+        "#,
+    )
+    .unwrap();
+
+    let result = route.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
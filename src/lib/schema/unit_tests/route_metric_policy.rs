@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{InterfaceType, MergedRouteMetricPolicy, RouteMetricPolicy};
+
+#[test]
+fn test_route_metric_policy_default_order() {
+    let policy = RouteMetricPolicy::default();
+
+    let eth = policy.base_metric(&InterfaceType::Ethernet);
+    let wifi = policy.base_metric(&InterfaceType::WifiPhy);
+    let wg = policy.base_metric(&InterfaceType::Wireguard);
+
+    assert!(eth < wifi);
+    assert!(wifi < wg);
+    assert_eq!(
+        policy.base_metric(&InterfaceType::Dummy),
+        RouteMetricPolicy::DEFAULT_BASE_METRIC
+    );
+}
+
+#[test]
+fn test_route_metric_policy_custom_replace_default() {
+    let policy: RouteMetricPolicy = serde_yaml::from_str(
+        r#"
+        base-metrics:
+          wireguard: 50
+        default-base-metric: 2000
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(policy.base_metric(&InterfaceType::Wireguard), 50);
+    assert_eq!(policy.base_metric(&InterfaceType::Ethernet), 2000);
+}
+
+#[test]
+fn test_route_metric_policy_no_change_for_default() {
+    let desired = RouteMetricPolicy::new_default();
+
+    let merged = MergedRouteMetricPolicy::new(Some(desired), None).unwrap();
+
+    assert!(!merged.is_changed());
+}
+
+#[test]
+fn test_route_metric_policy_preserve_current() {
+    let current: RouteMetricPolicy = serde_yaml::from_str(
+        r#"
+        base-metrics:
+          ethernet: 300
+        default-base-metric: 900
+        "#,
+    )
+    .unwrap();
+    let desired: RouteMetricPolicy = serde_yaml::from_str(
+        r#"
+        default-base-metric: 1200
+        "#,
+    )
+    .unwrap();
+
+    let merged =
+        MergedRouteMetricPolicy::new(Some(desired), Some(current.clone()))
+            .unwrap();
+
+    let apply = merged.gen_state_for_apply().unwrap();
+    assert_eq!(apply.base_metric(&InterfaceType::Ethernet), 300);
+    assert_eq!(apply.base_metric(&InterfaceType::WifiPhy), 1200);
+    assert_eq!(merged.generate_revert(), Some(current));
+}
+
+#[test]
+fn test_route_metric_policy_merged_use_current_if_unchanged() {
+    let current: RouteMetricPolicy = serde_yaml::from_str(
+        r#"
+        base-metrics:
+          ethernet: 300
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedRouteMetricPolicy::new(None, Some(current)).unwrap();
+
+    assert!(!merged.is_changed());
+    let policy = merged.merged();
+    assert_eq!(policy.base_metric(&InterfaceType::Ethernet), 300);
+    assert_eq!(
+        policy.default_base_metric,
+        Some(RouteMetricPolicy::DEFAULT_BASE_METRIC)
+    );
+}
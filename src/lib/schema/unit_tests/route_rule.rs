@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, MergedRouteRules, NetworkState, RouteRuleFamily, RouteRules,
+    RouteState,
+};
+
+fn gen_current_rules() -> RouteRules {
+    serde_yaml::from_str(
+        r#"
+        config:
+        - family: ipv4
+          priority: 1000
+          from: 192.0.2.0/24
+          table-id: 500
+        - family: ipv6
+          priority: 1001
+          from: 2001:db8:1::/64
+          table-id: 500
+        - family: ipv4
+          priority: 1002
+          fwmark: 0x10
+          table-id: 501
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_route_rule_sanitize_family_and_table() {
+    let desired: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - priority: 1000
+          from: 2001:db8:1::1
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedRouteRules::new(desired, RouteRules::default()).unwrap();
+
+    assert_eq!(merged.changed_rules.len(), 1);
+    let rule = &merged.changed_rules[0];
+    assert_eq!(rule.family, Some(RouteRuleFamily::Ipv6));
+    assert_eq!(rule.from.as_deref(), Some("2001:db8:1::1/128"));
+    assert_eq!(rule.table_id, Some(254));
+}
+
+#[test]
+fn test_route_rule_family_mismatch() {
+    let desired: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - family: ipv4
+          from: 2001:db8:1::/64
+          table-id: 500
+        "#,
+    )
+    .unwrap();
+
+    let result = MergedRouteRules::new(desired, RouteRules::default());
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_rule_blackhole_with_table() {
+    let desired: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - from: 192.0.2.0/24
+          action: blackhole
+          table-id: 500
+        "#,
+    )
+    .unwrap();
+
+    let result = MergedRouteRules::new(desired, RouteRules::default());
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_rule_absent_wildcard() {
+    let desired: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - table-id: 500
+          state: absent
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedRouteRules::new(desired, gen_current_rules()).unwrap();
+
+    assert_eq!(merged.changed_rules.len(), 2);
+    assert!(merged.changed_rules.iter().all(|r| {
+        r.state == Some(RouteState::Absent) && r.table_id == Some(500)
+    }));
+}
+
+#[test]
+fn test_route_rule_no_change_when_exists() {
+    let desired: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - priority: 1000
+          from: 192.0.2.0/24
+          table-id: 500
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedRouteRules::new(desired, gen_current_rules()).unwrap();
+
+    assert!(merged.changed_rules.is_empty());
+}
+
+#[test]
+fn test_route_rule_gen_diff_and_revert() {
+    let current = NetworkState {
+        route_rules: gen_current_rules(),
+        ..Default::default()
+    };
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"
+        route-rules:
+          config:
+          - priority: 1002
+            state: absent
+          - priority: 2000
+            to: 198.51.100.0/24
+            table-id: 502
+        "#,
+    )
+    .unwrap();
+
+    let diff = desired.gen_diff(&current).unwrap();
+    let diff_rules = diff.route_rules.config.unwrap();
+    assert_eq!(diff_rules.len(), 2);
+
+    let revert = desired.generate_revert(&current).unwrap();
+    let revert_rules = revert.route_rules.config.unwrap();
+    assert_eq!(revert_rules.len(), 2);
+    assert!(revert_rules.iter().any(|r| {
+        r.priority == Some(1002) && r.state.is_none() && r.fwmark == Some(0x10)
+    }));
+    assert!(revert_rules.iter().any(|r| {
+        r.priority == Some(2000) && r.state == Some(RouteState::Absent)
+    }));
+}
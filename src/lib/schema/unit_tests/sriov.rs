@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, SrIovConfig};
+
+fn sanitize_sriov(yml: &str) -> Result<SrIovConfig, crate::NipartError> {
+    let mut sriov_conf: SrIovConfig = serde_yaml::from_str(yml).unwrap();
+    sriov_conf.sanitize("eth1", None)?;
+    Ok(sriov_conf)
+}
+
+fn assert_invalid_argument(result: Result<SrIovConfig, crate::NipartError>) {
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_sriov_vf_sort_and_uppercase_mac() {
+    let sriov_conf = sanitize_sriov(
+        r#"
+        total-vfs: 2
+        vfs:
+        - id: 1
+          mac-address: 00:11:22:33:44:ff
+        - id: 0
+        "#,
+    )
+    .unwrap();
+    let vfs = sriov_conf.vfs.unwrap();
+    assert_eq!(vfs[0].id, 0);
+    assert_eq!(vfs[1].id, 1);
+    assert_eq!(vfs[1].mac_address.as_deref(), Some("00:11:22:33:44:FF"));
+}
+
+#[test]
+fn test_sriov_vf_id_exceed_total_vfs() {
+    assert_invalid_argument(sanitize_sriov(
+        r#"
+        total-vfs: 2
+        vfs:
+        - id: 2
+        "#,
+    ));
+}
+
+#[test]
+fn test_sriov_duplicate_vf_id() {
+    assert_invalid_argument(sanitize_sriov(
+        r#"
+        total-vfs: 2
+        vfs:
+        - id: 1
+        - id: 1
+        "#,
+    ));
+}
+
+#[test]
+fn test_sriov_vf_qos_without_vlan() {
+    assert_invalid_argument(sanitize_sriov(
+        r#"
+        total-vfs: 2
+        vfs:
+        - id: 0
+          vlan-id: 0
+          qos: 5
+        "#,
+    ));
+}
+
+#[test]
+fn test_sriov_vf_min_rate_bigger_than_max() {
+    assert_invalid_argument(sanitize_sriov(
+        r#"
+        total-vfs: 2
+        vfs:
+        - id: 0
+          min-tx-rate: 1000
+          max-tx-rate: 100
+        "#,
+    ));
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, MergedNetworkState, NetworkState, TunMode};
+
+#[test]
+fn test_new_tun_default_to_persistent_tun_mode() {
+    let merged = MergedNetworkState::new(
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: tun0
+              type: tun
+            "#,
+        )
+        .unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let iface = merged
+        .ifaces
+        .kernel_ifaces
+        .get("tun0")
+        .and_then(|i| i.for_apply.as_ref())
+        .unwrap();
+    if let Interface::Tun(iface) = iface {
+        let tun_conf = iface.tun.as_ref().unwrap();
+        assert_eq!(tun_conf.mode, Some(TunMode::Tun));
+        assert_eq!(tun_conf.persist, Some(true));
+    } else {
+        panic!("Expecting TUN interface, but got {iface:?}");
+    }
+}
+
+#[test]
+fn test_new_non_persistent_tun() {
+    let result = MergedNetworkState::new(
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: tap0
+              type: tun
+              tun:
+                mode: tap
+                persist: false
+            "#,
+        )
+        .unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, MergedNetworkState, NetworkState};
+
+const VRF_STATE_YAML: &str = r#"
+    version: 1
+    interfaces:
+    - name: vrf0
+      type: vrf
+      vrf:
+        ports:
+        - dummy1
+        route-table-id: 100
+    - name: dummy1
+      type: dummy
+      ipv4:
+        enabled: true
+        address:
+        - ip: 192.0.2.1
+          prefix-length: 24
+    "#;
+
+#[test]
+fn test_vrf_route_use_vrf_table() {
+    let mut desired: NetworkState =
+        serde_yaml::from_str(VRF_STATE_YAML).unwrap();
+    desired.routes = serde_yaml::from_str(
+        r#"
+        config:
+        - destination: 198.51.100.0/24
+          next-hop-interface: dummy1
+          next-hop-address: 192.0.2.254
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedNetworkState::new(
+        desired,
+        NetworkState::default(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let routes = merged.routes.merged.get("dummy1").unwrap();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].table_id, Some(100));
+    assert_eq!(routes[0].vrf_name.as_deref(), Some("vrf0"));
+}
+
+#[test]
+fn test_vrf_route_conflict_table() {
+    let mut desired: NetworkState =
+        serde_yaml::from_str(VRF_STATE_YAML).unwrap();
+    desired.routes = serde_yaml::from_str(
+        r#"
+        config:
+        - destination: 198.51.100.0/24
+          next-hop-interface: dummy1
+          next-hop-address: 192.0.2.254
+          table-id: 101
+        "#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(
+        desired,
+        NetworkState::default(),
+        Default::default(),
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_vrf_reject_main_route_table() {
+    let result = MergedNetworkState::new(
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: vrf0
+              type: vrf
+              vrf:
+                route-table-id: 254
+            "#,
+        )
+        .unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
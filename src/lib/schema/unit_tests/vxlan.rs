@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, MergedNetworkState, NetworkState, NipartInterface};
+
+fn get_up_priority(merged: &MergedNetworkState, iface_name: &str) -> u32 {
+    merged
+        .ifaces
+        .kernel_ifaces
+        .get(iface_name)
+        .and_then(|i| i.for_apply.as_ref())
+        .unwrap()
+        .base_iface()
+        .up_priority
+}
+
+#[test]
+fn test_vxlan_underlay_up_first() {
+    let merged = MergedNetworkState::new(
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: vxlan100
+              type: vxlan
+              vxlan:
+                base-iface: vlan10
+                id: 100
+                remote: 192.0.2.2
+            - name: vlan10
+              type: vlan
+              vlan:
+                base-iface: dummy1
+                id: 10
+            - name: dummy1
+              type: dummy
+            "#,
+        )
+        .unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let dummy_pri = get_up_priority(&merged, "dummy1");
+    let vlan_pri = get_up_priority(&merged, "vlan10");
+    let vxlan_pri = get_up_priority(&merged, "vxlan100");
+    assert!(dummy_pri < vlan_pri);
+    assert!(vlan_pri < vxlan_pri);
+}
+
+#[test]
+fn test_vxlan_over_new_vlan_port_up_after_vlan() {
+    let merged = MergedNetworkState::new(
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: vxlan100
+              type: vxlan
+              vxlan:
+                base-iface: vlan10
+                id: 100
+                remote: 192.0.2.2
+            - name: vlan10
+              type: vlan
+              vlan:
+                base-iface: eth1
+                id: 10
+            - name: bond0
+              type: bond
+              bond:
+                mode: active-backup
+                ports:
+                - name: vlan10
+            "#,
+        )
+        .unwrap(),
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: eth1
+              type: ethernet
+            "#,
+        )
+        .unwrap(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let bond_pri = get_up_priority(&merged, "bond0");
+    let vlan_pri = get_up_priority(&merged, "vlan10");
+    let vxlan_pri = get_up_priority(&merged, "vxlan100");
+    assert!(bond_pri < vlan_pri);
+    assert!(vlan_pri < vxlan_pri);
+}
+
+#[test]
+fn test_vxlan_new_without_id() {
+    let result = MergedNetworkState::new(
+        serde_yaml::from_str(
+            r#"
+            version: 1
+            interfaces:
+            - name: vxlan100
+              type: vxlan
+              vxlan:
+                base-iface: dummy1
+                remote: 192.0.2.2
+            "#,
+        )
+        .unwrap(),
+        NetworkState::default(),
+        Default::default(),
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
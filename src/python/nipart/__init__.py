@@ -8,6 +8,8 @@ from .apply import apply
 from .query import show
 from .schema.state_option import NipartApplyOption
 from .schema.state_option import NipartQueryOption
+from .schema.state_option import NipartRouteFilter
+from .schema.state_option import NipartRouteProtocol
 from .schema.state_option import NipartStateKind
 from .version import LATEST_SCHEMA_VERSION
 
@@ -18,6 +20,8 @@ __all__ = [
     "NipartError",
     "NipartLogEntry",
     "NipartQueryOption",
+    "NipartRouteFilter",
+    "NipartRouteProtocol",
     "NipartStateKind",
     "NipartValueError",
     "apply",
@@ -5,7 +5,9 @@ from .schema.state_option import NipartQueryOption
 from .schema.state_option import NipartStateKind
 
 
-def show():
+def show(route_filter=None):
     client = NipartClient()
-    opt = NipartQueryOption(kind=NipartStateKind.RUNNING)
+    opt = NipartQueryOption(
+        kind=NipartStateKind.RUNNING, route_filter=route_filter
+    )
     return client.query_network_state(opt)
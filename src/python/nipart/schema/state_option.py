@@ -11,15 +11,60 @@ class NipartStateKind(enum.StrEnum):
     DEFAULT = RUNNING
 
 
+class NipartRouteProtocol(enum.StrEnum):
+    BOOT = "boot"
+    STATIC = "static"
+    RA = "ra"
+    DHCP = "dhcp"
+    MROUTED = "mrouted"
+    KEEPALIVED = "keepalived"
+    BABEL = "babel"
+
+
+class NipartRouteFilter:
+    def __init__(
+        self,
+        protocols=None,
+        tables=None,
+        families=None,
+        max_entries=None,
+        skip_running=False,
+    ):
+        self.protocols = protocols
+        self.tables = tables
+        self.families = families
+        self.max_entries = max_entries
+        self.skip_running = skip_running
+
+    def to_dict(self):
+        ret = {"skip-running": self.skip_running}
+        if self.protocols is not None:
+            ret["protocols"] = list(self.protocols)
+        if self.tables is not None:
+            ret["tables"] = list(self.tables)
+        if self.families is not None:
+            ret["families"] = list(self.families)
+        if self.max_entries is not None:
+            ret["max-entries"] = self.max_entries
+        return ret
+
+
 class NipartQueryOption:
     def __init__(
-        self, version=LATEST_SCHEMA_VERSION, kind=NipartStateKind.DEFAULT
+        self,
+        version=LATEST_SCHEMA_VERSION,
+        kind=NipartStateKind.DEFAULT,
+        route_filter=None,
     ):
         self.version = version
         self.kind = kind
+        self.route_filter = route_filter
 
     def to_dict(self):
-        return {"version": self.version, "kind": self.kind}
+        ret = {"version": self.version, "kind": self.kind}
+        if self.route_filter is not None:
+            ret["route-filter"] = self.route_filter.to_dict()
+        return ret
 
     def running():
         return NipartQueryOption(kind=NipartStateKind.RUNNING)
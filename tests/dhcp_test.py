@@ -0,0 +1,483 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import glob
+import ipaddress
+import json
+
+import pytest
+
+import nipart
+from nipart import NipartClient
+from nipart import NipartQueryOption
+from nipart import NipartStateKind
+
+from .testlib.cmdlib import exec_cmd
+from .testlib.dhcp import DHCP_CLI_NIC
+from .testlib.dhcp import IPV4_CLASSLESS_ROUTE_DST_NET1
+from .testlib.dhcp import IPV4_CLASSLESS_ROUTE_NEXT_HOP1
+from .testlib.dhcp import DHCP_SRV_IP4_PREFIX
+from .testlib.dhcp import DHCP_SRV_IP6_PREFIX
+from .testlib.dhcp import dhcp_env  # noqa: F401
+from .testlib.retry import retry_till_true_or_timeout
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+from .testlib.veth import veth_interface
+
+DHCP_TIMEOUT = 30
+DHCPV4_LEASE_DIR = "/var/lib/nipart/dhcp"
+DHCP_DUID_PATH = "/etc/nipart/dhcp-duid.json"
+TEST_ROUTE_TABLE_ID = 100
+TEST_ROUTE_METRIC = 500
+TEST_VETH_BASE_METRIC = 300
+
+
+def dhcpv4_is_done():
+    ipv4_conf = show_only(DHCP_CLI_NIC).get("ipv4", {})
+    return ipv4_conf.get("dhcp-state") == "done"
+
+
+def get_dhcpv4_addrs():
+    ipv4_conf = show_only(DHCP_CLI_NIC).get("ipv4", {})
+    return [
+        addr["ip"]
+        for addr in ipv4_conf.get("address", [])
+        if addr["ip"].startswith(f"{DHCP_SRV_IP4_PREFIX}.")
+    ]
+
+
+def apply_dhcpv4(enabled):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv4:
+                  enabled: true
+                  dhcp: {"true" if enabled else "false"}"""))
+
+
+def test_dhcpv4_addr_origin(dhcp_env):  # noqa: F811
+    apply_dhcpv4(True)
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    addrs = [
+        addr
+        for addr in show_only(DHCP_CLI_NIC)["ipv4"]["address"]
+        if addr["ip"].startswith(f"{DHCP_SRV_IP4_PREFIX}.")
+    ]
+    assert len(addrs) == 1
+    assert addrs[0]["origin"] == "dhcp"
+
+    # Once DHCP disabled, the dynamic address becomes static
+    apply_dhcpv4(False)
+    ipv4_conf = show_only(DHCP_CLI_NIC)["ipv4"]
+    assert not ipv4_conf["dhcp"]
+    assert all(addr["origin"] == "static" for addr in ipv4_conf["address"])
+
+
+def test_manual_addr_with_life_time_is_not_dhcp():
+    with veth_interface("veth-test1", "veth-test1-ep"):
+        exec_cmd(
+            "ip addr add 192.0.2.100/24 dev veth-test1 valid_lft 300 "
+            "preferred_lft 300".split()
+        )
+        ipv4_conf = show_only("veth-test1")["ipv4"]
+        assert not ipv4_conf["dhcp"]
+        assert ipv4_conf["address"][0]["ip"] == "192.0.2.100"
+        assert ipv4_conf["address"][0]["origin"] == "static"
+
+
+def get_ipv4_link_local_addrs(iface_name):
+    return [
+        addr["ip"]
+        for addr in show_only(iface_name)["ipv4"].get("address", [])
+        if addr.get("origin") == "link-local"
+    ]
+
+
+def test_ipv4_link_local_enabled_with_static_addr():
+    with veth_interface("veth-test1", "veth-test1-ep"):
+        nipart.apply(load_yaml("""---
+            version: 1
+            interfaces:
+            - name: veth-test1
+              type: ethernet
+              ipv4:
+                enabled: true
+                dhcp: false
+                link-local: enabled
+                address:
+                - ip: 192.0.2.1
+                  prefix-length: 24
+            """))
+        assert retry_till_true_or_timeout(
+            DHCP_TIMEOUT, get_ipv4_link_local_addrs, "veth-test1"
+        )
+        ipv4_conf = show_only("veth-test1")["ipv4"]
+        assert ipv4_conf["link-local"] == "enabled"
+        assert "192.0.2.1" in [a["ip"] for a in ipv4_conf["address"]]
+        ll_addr = ipaddress.ip_address(
+            get_ipv4_link_local_addrs("veth-test1")[0]
+        )
+        assert ll_addr in ipaddress.ip_network("169.254.0.0/16")
+
+        nipart.apply(load_yaml("""---
+            version: 1
+            interfaces:
+            - name: veth-test1
+              type: ethernet
+              ipv4:
+                enabled: true
+                dhcp: false
+                link-local: disabled
+                address:
+                - ip: 192.0.2.1
+                  prefix-length: 24
+            """))
+        assert not get_ipv4_link_local_addrs("veth-test1")
+
+
+# No DHCP server on veth peer, link-local address should be assigned after
+# DHCP timeout.
+def test_ipv4_link_local_fallback_without_dhcp_server():
+    with veth_interface("veth-test1", "veth-test1-ep"):
+        nipart.apply(load_yaml("""---
+            version: 1
+            interfaces:
+            - name: veth-test1
+              type: ethernet
+              ipv4:
+                enabled: true
+                dhcp: true
+                link-local: fallback
+            """))
+        assert retry_till_true_or_timeout(
+            DHCP_TIMEOUT + 30, get_ipv4_link_local_addrs, "veth-test1"
+        )
+        ipv4_conf = show_only("veth-test1")["ipv4"]
+        assert ipv4_conf["dhcp"]
+        assert ipv4_conf["link-local"] == "fallback"
+
+
+def test_ipv4_link_local_fallback_removed_on_dhcp_lease(
+    dhcp_env,  # noqa: F811
+):
+    nipart.apply(load_yaml(f"""---
+        version: 1
+        interfaces:
+        - name: {DHCP_CLI_NIC}
+          type: ethernet
+          ipv4:
+            enabled: true
+            dhcp: true
+            link-local: fallback
+        """))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert not get_ipv4_link_local_addrs(DHCP_CLI_NIC)
+
+
+def test_dhcpv4_lease_stored_and_reused(dhcp_env):  # noqa: F811
+    apply_dhcpv4(True)
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert show_only(DHCP_CLI_NIC)["ipv4"].get("dhcp-lease-expiry")
+    assert glob.glob(f"{DHCPV4_LEASE_DIR}/v4-{DHCP_CLI_NIC}-*.json")
+    addrs = get_dhcpv4_addrs()
+    assert len(addrs) == 1
+
+    # Restart DHCP client by changing DHCP option, it should request the
+    # same address via INIT-REBOOT
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv4:
+                  enabled: true
+                  dhcp: true
+                  auto-route-metric: {TEST_ROUTE_METRIC}"""))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert get_dhcpv4_addrs() == addrs
+
+
+def test_dhcpv4_lease_removed_on_dhcp_disabled(dhcp_env):  # noqa: F811
+    apply_dhcpv4(True)
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert glob.glob(f"{DHCPV4_LEASE_DIR}/v4-{DHCP_CLI_NIC}-*.json")
+
+    apply_dhcpv4(False)
+    assert not glob.glob(f"{DHCPV4_LEASE_DIR}/v4-{DHCP_CLI_NIC}-*.json")
+
+
+def get_ipv4_routes(table_id):
+    output = exec_cmd(
+        f"ip -4 route show dev {DHCP_CLI_NIC} table {table_id}".split()
+    )[1]
+    return output.strip().split("\n")
+
+
+def has_classless_route(table_id):
+    return any(
+        rt.startswith(
+            f"{IPV4_CLASSLESS_ROUTE_DST_NET1} "
+            f"via {IPV4_CLASSLESS_ROUTE_NEXT_HOP1}"
+        )
+        for rt in get_ipv4_routes(table_id)
+    )
+
+
+def test_dhcpv4_classless_route_with_custom_table(dhcp_env):  # noqa: F811
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv4:
+                  enabled: true
+                  dhcp: true
+                  auto-route-table-id: {TEST_ROUTE_TABLE_ID}
+                  auto-route-metric: {TEST_ROUTE_METRIC}"""))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert retry_till_true_or_timeout(
+        DHCP_TIMEOUT, has_classless_route, TEST_ROUTE_TABLE_ID
+    )
+    assert any(
+        f"metric {TEST_ROUTE_METRIC}" in rt
+        for rt in get_ipv4_routes(TEST_ROUTE_TABLE_ID)
+    )
+    ipv4_conf = show_only(DHCP_CLI_NIC)["ipv4"]
+    assert ipv4_conf["auto-route-table-id"] == TEST_ROUTE_TABLE_ID
+    assert ipv4_conf["auto-route-metric"] == TEST_ROUTE_METRIC
+
+
+def test_dhcpv4_auto_routes_off(dhcp_env):  # noqa: F811
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv4:
+                  enabled: true
+                  dhcp: true
+                  auto-routes: false"""))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert len(get_dhcpv4_addrs()) == 1
+    assert not has_classless_route("main")
+    assert not any(
+        rt.startswith("default") for rt in get_ipv4_routes("main")
+    )
+
+
+def test_dhcpv4_client_id_by_host_duid(dhcp_env):  # noqa: F811
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv4:
+                  enabled: true
+                  dhcp: true
+                  dhcp-client-id: duid
+                  dhcp-duid: uuid"""))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    ipv4_conf = show_only(DHCP_CLI_NIC)["ipv4"]
+    assert ipv4_conf["dhcp-client-id"] == "duid"
+    assert ipv4_conf["dhcp-duid"] == "uuid"
+
+    with open(DHCP_DUID_PATH) as fd:
+        duid = json.load(fd)["uuid"]
+    assert duid.startswith("00:04:")
+    # Client identifier is type 255 followed by DUID
+    client_id = "ff" + duid.replace(":", "")
+    assert glob.glob(f"{DHCPV4_LEASE_DIR}/v4-{DHCP_CLI_NIC}-{client_id}.json")
+
+
+def show_route_metric_policy():
+    client = NipartClient()
+    state = client.query_network_state(
+        NipartQueryOption(kind=NipartStateKind.RUNNING)
+    )
+    return state.get("route-metric-policy", {})
+
+
+def apply_veth_base_metric(metric):
+    nipart.apply(load_yaml(f"""---
+            route-metric-policy:
+              base-metrics:
+                veth: {metric}
+                ethernet: 100
+                wifi-phy: 600
+                wireguard: 1000"""))
+
+
+def get_iface_index(iface_name):
+    with open(f"/sys/class/net/{iface_name}/ifindex") as fd:
+        return int(fd.read().strip())
+
+
+def has_dhcpv4_route_metric(metric):
+    return any(f"metric {metric}" in rt for rt in get_ipv4_routes("main"))
+
+
+@pytest.fixture
+def veth_base_metric():
+    apply_veth_base_metric(TEST_VETH_BASE_METRIC)
+    yield
+    nipart.apply(load_yaml("""---
+            route-metric-policy:
+              base-metrics:
+                bond: 100
+                ethernet: 100
+                veth: 100
+                vlan: 100
+                wifi-phy: 600
+                wireguard: 1000"""))
+
+
+def test_route_metric_policy_default(dhcp_env):  # noqa: F811
+    policy = show_route_metric_policy()
+    metrics = policy["base-metrics"]
+    assert metrics["ethernet"] < metrics["wifi-phy"]
+    assert metrics["wifi-phy"] < metrics["wireguard"]
+    assert policy["default-base-metric"]
+
+
+def test_dhcpv4_route_metric_by_policy(
+    dhcp_env, veth_base_metric  # noqa: F811
+):
+    assert (
+        show_route_metric_policy()["base-metrics"]["veth"]
+        == TEST_VETH_BASE_METRIC
+    )
+    apply_dhcpv4(True)
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert retry_till_true_or_timeout(
+        DHCP_TIMEOUT, has_classless_route, "main"
+    )
+    # Interface index is used as offset to base metric
+    assert has_dhcpv4_route_metric(
+        TEST_VETH_BASE_METRIC + get_iface_index(DHCP_CLI_NIC)
+    )
+
+
+def test_dhcpv4_routes_follow_route_metric_policy_change(
+    dhcp_env, veth_base_metric  # noqa: F811
+):
+    apply_veth_base_metric(100)
+    apply_dhcpv4(True)
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv4_is_done)
+    assert retry_till_true_or_timeout(
+        DHCP_TIMEOUT, has_classless_route, "main"
+    )
+    iface_index = get_iface_index(DHCP_CLI_NIC)
+    assert has_dhcpv4_route_metric(100 + iface_index)
+
+    apply_veth_base_metric(TEST_VETH_BASE_METRIC)
+    assert retry_till_true_or_timeout(
+        DHCP_TIMEOUT,
+        has_dhcpv4_route_metric,
+        TEST_VETH_BASE_METRIC + iface_index,
+    )
+    assert not has_dhcpv4_route_metric(100 + iface_index)
+
+
+def dhcpv6_is_done():
+    ipv6_conf = show_only(DHCP_CLI_NIC).get("ipv6", {})
+    return ipv6_conf.get("dhcp-state") == "done"
+
+
+def has_dhcpv6_addr():
+    ipv6_conf = show_only(DHCP_CLI_NIC).get("ipv6", {})
+    srv_net = ipaddress.ip_network(f"{DHCP_SRV_IP6_PREFIX}::/64")
+    return any(
+        addr["prefix-length"] == 128
+        and ipaddress.ip_address(addr["ip"]) in srv_net
+        for addr in ipv6_conf.get("address", [])
+    )
+
+
+def test_dhcpv6_stateful(dhcp_env):  # noqa: F811
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv6:
+                  enabled: true
+                  dhcp: true
+                  autoconf: true"""))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv6_is_done)
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, has_dhcpv6_addr)
+    assert show_only(DHCP_CLI_NIC)["ipv6"]["dhcp-mode"] == "stateful"
+
+
+def test_dhcpv6_info_request(dhcp_env):  # noqa: F811
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv6:
+                  enabled: true
+                  dhcp: true
+                  dhcp-mode: info-request
+                  autoconf: true"""))
+    assert retry_till_true_or_timeout(DHCP_TIMEOUT, dhcpv6_is_done)
+    assert show_only(DHCP_CLI_NIC)["ipv6"]["dhcp-mode"] == "info-request"
+    assert not has_dhcpv6_addr()
+
+
+def read_ipv6_sysctl(iface_name, key):
+    with open(f"/proc/sys/net/ipv6/conf/{iface_name}/{key}") as fd:
+        return fd.read().strip()
+
+
+def test_ipv6_ra_sysctl_restored_to_default(dhcp_env):  # noqa: F811
+    default_defrtr = read_ipv6_sysctl("default", "accept_ra_defrtr")
+    default_metric = read_ipv6_sysctl("default", "ra_defrtr_metric")
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv6:
+                  enabled: true
+                  autoconf: true
+                  auto-routes: false
+                  auto-route-metric: {TEST_ROUTE_METRIC}"""))
+    assert read_ipv6_sysctl(DHCP_CLI_NIC, "accept_ra_defrtr") == "0"
+    assert read_ipv6_sysctl(DHCP_CLI_NIC, "ra_defrtr_metric") == str(
+        TEST_ROUTE_METRIC
+    )
+    ipv6_conf = show_only(DHCP_CLI_NIC)["ipv6"]
+    assert not ipv6_conf["auto-gateway"]
+    assert ipv6_conf["auto-route-metric"] == TEST_ROUTE_METRIC
+
+    # Options not mentioned should be preserved
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv6:
+                  enabled: true
+                  autoconf: true"""))
+    assert read_ipv6_sysctl(DHCP_CLI_NIC, "accept_ra_defrtr") == "0"
+    assert read_ipv6_sysctl(DHCP_CLI_NIC, "ra_defrtr_metric") == str(
+        TEST_ROUTE_METRIC
+    )
+
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {DHCP_CLI_NIC}
+                type: veth
+                state: up
+                ipv6:
+                  enabled: true
+                  autoconf: true
+                  auto-routes: true
+                  auto-route-metric: {default_metric}"""))
+    assert (
+        read_ipv6_sysctl(DHCP_CLI_NIC, "accept_ra_defrtr") == default_defrtr
+    )
+    assert (
+        read_ipv6_sysctl(DHCP_CLI_NIC, "ra_defrtr_metric") == default_metric
+    )
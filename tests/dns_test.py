@@ -0,0 +1,59 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+from nipart import NipartClient
+from nipart import NipartQueryOption
+from nipart import NipartStateKind
+
+from .testlib.statelib import load_yaml
+
+IPV4_DNS_SRV = "192.0.2.1"
+IPV6_DNS_SRV = "2001:db8:f::1"
+DNS_SEARCH = "example.org"
+
+
+def show_dns():
+    client = NipartClient()
+    state = client.query_network_state(
+        NipartQueryOption(kind=NipartStateKind.RUNNING)
+    )
+    return state.get("dns-resolver", {})
+
+
+@pytest.fixture
+def static_dns():
+    nipart.apply(load_yaml(f"""---
+            dns-resolver:
+              config:
+                server:
+                  - {IPV6_DNS_SRV}
+                  - {IPV4_DNS_SRV}
+                search:
+                  - {DNS_SEARCH}
+            """))
+    yield
+    nipart.apply(load_yaml("""---
+            dns-resolver:
+              config: {}
+            """))
+
+
+def test_static_dns(static_dns):
+    dns = show_dns()
+    assert dns["config"]["server"] == [IPV6_DNS_SRV, IPV4_DNS_SRV]
+    assert dns["config"]["search"] == [DNS_SEARCH]
+    assert IPV6_DNS_SRV in dns["running"]["server"]
+    assert IPV4_DNS_SRV in dns["running"]["server"]
+    assert DNS_SEARCH in dns["running"]["search"]
+
+
+def test_purge_static_dns(static_dns):
+    nipart.apply(load_yaml("""---
+            dns-resolver:
+              config: {}
+            """))
+    dns = show_dns()
+    assert "config" not in dns
+    assert IPV4_DNS_SRV not in dns.get("running", {}).get("server", [])
@@ -0,0 +1,51 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+from nipart import NipartClient
+from nipart import NipartQueryOption
+from nipart import NipartStateKind
+
+from .testlib.statelib import load_yaml
+
+TEST_HOSTNAME = "nipart-test.example.org"
+
+
+def show_hostname():
+    client = NipartClient()
+    state = client.query_network_state(
+        NipartQueryOption(kind=NipartStateKind.RUNNING)
+    )
+    return state.get("hostname", {})
+
+
+@pytest.fixture
+def restore_hostname():
+    old = show_hostname()
+    yield
+    nipart.apply(load_yaml(f"""---
+            hostname:
+              config: "{old.get("config", "")}"
+              dhcp-override: {str(old.get("dhcp-override", False)).lower()}
+            """))
+
+
+def test_static_hostname(restore_hostname):
+    nipart.apply(load_yaml(f"""---
+            hostname:
+              config: {TEST_HOSTNAME}
+            """))
+    hostname = show_hostname()
+    assert hostname["config"] == TEST_HOSTNAME
+    assert hostname["running"] == TEST_HOSTNAME
+    with open("/etc/hostname") as fd:
+        assert fd.read().strip() == TEST_HOSTNAME
+
+
+def test_enable_dhcp_override(restore_hostname):
+    nipart.apply(load_yaml("""---
+            hostname:
+              dhcp-override: true
+            """))
+    assert show_hostname()["dhcp-override"]
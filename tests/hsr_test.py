@@ -0,0 +1,71 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_HSR_NIC = "hsr0"
+TEST_PORT1 = "dummy1"
+TEST_PORT2 = "dummy2"
+
+
+@pytest.fixture
+def hsr_over_dummies():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_HSR_NIC}
+                type: hsr
+                state: up
+                hsr:
+                  port1: {TEST_PORT1}
+                  port2: {TEST_PORT2}
+                  supervision-address: 01:15:4E:00:01:2D
+                  protocol: hsr
+              - name: {TEST_PORT1}
+                type: dummy
+                state: up
+              - name: {TEST_PORT2}
+                type: dummy
+                state: up
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_HSR_NIC}
+                type: hsr
+                state: absent
+              - name: {TEST_PORT1}
+                type: dummy
+                state: absent
+              - name: {TEST_PORT2}
+                type: dummy
+                state: absent
+            """))
+
+
+def test_create_and_remove_hsr(hsr_over_dummies):
+    hsr_iface = show_only(TEST_HSR_NIC)
+    assert hsr_iface["hsr"]["port1"] == TEST_PORT1
+    assert hsr_iface["hsr"]["port2"] == TEST_PORT2
+    assert hsr_iface["hsr"]["protocol"] == "hsr"
+    assert hsr_iface["hsr"]["supervision-address"] == "01:15:4E:00:01:2D"
+    assert show_only(TEST_PORT1)["controller"] == TEST_HSR_NIC
+    assert show_only(TEST_PORT2)["controller"] == TEST_HSR_NIC
+
+
+def test_change_hsr_supervision_address(hsr_over_dummies):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_HSR_NIC}
+                type: hsr
+                state: up
+                hsr:
+                  supervision-address: 01:15:4E:00:01:00
+            """))
+    hsr_iface = show_only(TEST_HSR_NIC)
+    assert hsr_iface["hsr"]["supervision-address"] == "01:15:4E:00:01:00"
+    assert hsr_iface["hsr"]["port1"] == TEST_PORT1
+    assert hsr_iface["hsr"]["port2"] == TEST_PORT2
@@ -0,0 +1,98 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_BASE_NIC = "dummy1"
+TEST_GRE_NIC = "gre1"
+TEST_IP6TNL_NIC = "ip6tnl1"
+
+
+@pytest.fixture
+def base_dummy():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 192.0.2.1
+                      prefix-length: 24
+                ipv6:
+                  enabled: true
+                  address:
+                    - ip: 2001:db8::1
+                      prefix-length: 64
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_GRE_NIC}
+                type: gre
+                state: absent
+              - name: {TEST_IP6TNL_NIC}
+                type: ip6tnl
+                state: absent
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: absent
+            """))
+
+
+def test_create_and_change_gre(base_dummy):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_GRE_NIC}
+                type: gre
+                state: up
+                ip-tunnel:
+                  base-iface: {TEST_BASE_NIC}
+                  local: 192.0.2.1
+                  remote: 192.0.2.2
+                  ttl: 64
+                  key: 100
+            """))
+    tunnel_conf = show_only(TEST_GRE_NIC)["ip-tunnel"]
+    assert tunnel_conf["base-iface"] == TEST_BASE_NIC
+    assert tunnel_conf["local"] == "192.0.2.1"
+    assert tunnel_conf["remote"] == "192.0.2.2"
+    assert tunnel_conf["ttl"] == 64
+    assert tunnel_conf["key"] == 100
+
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_GRE_NIC}
+                type: gre
+                state: up
+                ip-tunnel:
+                  remote: 192.0.2.3
+            """))
+    tunnel_conf = show_only(TEST_GRE_NIC)["ip-tunnel"]
+    assert tunnel_conf["remote"] == "192.0.2.3"
+    assert tunnel_conf["key"] == 100
+
+
+def test_create_ip6tnl(base_dummy):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_IP6TNL_NIC}
+                type: ip6tnl
+                state: up
+                ip-tunnel:
+                  base-iface: {TEST_BASE_NIC}
+                  local: 2001:db8::1
+                  remote: 2001:db8::2
+                  encap-limit: 4
+                  mode: ipip6
+            """))
+    tunnel_conf = show_only(TEST_IP6TNL_NIC)["ip-tunnel"]
+    assert tunnel_conf["local"] == "2001:db8::1"
+    assert tunnel_conf["remote"] == "2001:db8::2"
+    assert tunnel_conf["encap-limit"] == 4
+    assert tunnel_conf["mode"] == "ipip6"
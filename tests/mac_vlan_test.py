@@ -0,0 +1,117 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_BASE_NIC = "dummy1"
+TEST_MAC_VLAN_NIC = "macvlan0"
+TEST_MAC_VTAP_NIC = "macvtap0"
+TEST_IPVLAN_NIC = "ipvlan0"
+
+
+@pytest.fixture
+def base_nic():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: up
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_MAC_VLAN_NIC}
+                type: mac-vlan
+                state: absent
+              - name: {TEST_MAC_VTAP_NIC}
+                type: mac-vtap
+                state: absent
+              - name: {TEST_IPVLAN_NIC}
+                type: ipvlan
+                state: absent
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: absent
+            """))
+
+
+@pytest.mark.parametrize("mode", ["vepa", "bridge", "private", "source"])
+def test_create_mac_vlan(base_nic, mode):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_MAC_VLAN_NIC}
+                type: mac-vlan
+                state: up
+                mac-vlan:
+                  base-iface: {TEST_BASE_NIC}
+                  mode: {mode}
+            """))
+    iface = show_only(TEST_MAC_VLAN_NIC)
+    assert iface["mac-vlan"]["base-iface"] == TEST_BASE_NIC
+    assert iface["mac-vlan"]["mode"] == mode
+
+
+def test_create_mac_vtap_passthru_without_promiscuous(base_nic):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_MAC_VTAP_NIC}
+                type: mac-vtap
+                state: up
+                mac-vtap:
+                  base-iface: {TEST_BASE_NIC}
+                  mode: passthru
+                  promiscuous: false
+            """))
+    iface = show_only(TEST_MAC_VTAP_NIC)
+    assert iface["mac-vtap"]["mode"] == "passthru"
+    assert not iface["mac-vtap"]["promiscuous"]
+
+
+@pytest.mark.parametrize("mode", ["l2", "l3", "l3s"])
+def test_create_ipvlan(base_nic, mode):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_IPVLAN_NIC}
+                type: ipvlan
+                state: up
+                ipvlan:
+                  base-iface: {TEST_BASE_NIC}
+                  mode: {mode}
+                  private: true
+            """))
+    iface = show_only(TEST_IPVLAN_NIC)
+    assert iface["ipvlan"]["base-iface"] == TEST_BASE_NIC
+    assert iface["ipvlan"]["mode"] == mode
+    assert iface["ipvlan"]["private"]
+
+
+def test_create_mac_vlan_with_parent_in_same_state():
+    try:
+        nipart.apply(load_yaml(f"""---
+                interfaces:
+                  - name: {TEST_MAC_VLAN_NIC}
+                    type: mac-vlan
+                    state: up
+                    mac-vlan:
+                      base-iface: {TEST_BASE_NIC}
+                      mode: bridge
+                  - name: {TEST_BASE_NIC}
+                    type: dummy
+                    state: up
+                """))
+        iface = show_only(TEST_MAC_VLAN_NIC)
+        assert iface["mac-vlan"]["base-iface"] == TEST_BASE_NIC
+    finally:
+        nipart.apply(load_yaml(f"""---
+                interfaces:
+                  - name: {TEST_MAC_VLAN_NIC}
+                    type: mac-vlan
+                    state: absent
+                  - name: {TEST_BASE_NIC}
+                    type: dummy
+                    state: absent
+                """))
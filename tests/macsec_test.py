@@ -0,0 +1,67 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_BASE_NIC = "dummy1"
+TEST_MACSEC_NIC = "macsec0"
+TEST_CAK = "50b71a8ef0bd5751ea76de6d6c98c03a"
+TEST_CKN = "f2b4297d39da7330910a74abc0449feb45b5c0b9fc23df1430e1898fcf1c4550"
+HIDE_SECRET_STR = "<_hidden_>"
+
+
+@pytest.fixture
+def macsec_over_dummy():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_MACSEC_NIC}
+                type: macsec
+                state: up
+                macsec:
+                  base-iface: {TEST_BASE_NIC}
+                  port: 10
+                  cipher: gcm-aes-256
+                  encrypt: true
+                  protect: true
+                  validation: strict
+                  mka-cak: {TEST_CAK}
+                  mka-ckn: {TEST_CKN}
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: up
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_MACSEC_NIC}
+                type: macsec
+                state: absent
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: absent
+            """))
+
+
+def test_create_and_remove_macsec(macsec_over_dummy):
+    iface = show_only(TEST_MACSEC_NIC)
+    assert iface["macsec"]["base-iface"] == TEST_BASE_NIC
+    assert iface["macsec"]["port"] == 10
+    assert iface["macsec"]["cipher"] == "gcm-aes-256"
+    assert iface["macsec"]["encrypt"]
+    assert iface["macsec"]["validation"] == "strict"
+
+
+def test_macsec_keys_not_in_saved_state(macsec_over_dummy):
+    with open("/etc/nipart/states/internal/applied.yml") as fd:
+        content = fd.read()
+        assert TEST_CAK not in content
+        assert TEST_CKN not in content
+        assert HIDE_SECRET_STR in content
+    with open("/etc/nipart/states/internal/applied.secrets.yml") as fd:
+        content = fd.read()
+        assert TEST_CAK in content
+        assert TEST_CKN in content
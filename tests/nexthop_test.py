@@ -0,0 +1,186 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.cmdlib import exec_cmd
+from .testlib.statelib import load_yaml
+
+TEST_NIC1 = "dummy1"
+TEST_NIC2 = "dummy2"
+TEST_DST = "203.0.113.0/24"
+
+
+@pytest.fixture
+def two_dummy_nics():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_NIC1}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 192.0.2.1
+                      prefix-length: 24
+              - name: {TEST_NIC2}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 198.51.100.1
+                      prefix-length: 24
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            nexthops:
+              config:
+                - id: 10
+                  state: absent
+            interfaces:
+              - name: {TEST_NIC1}
+                type: dummy
+                state: absent
+              - name: {TEST_NIC2}
+                type: dummy
+                state: absent
+            """))
+
+
+def get_config_nexthops():
+    return nipart.show().get("nexthops", {}).get("config", [])
+
+
+def test_nexthop_group_with_route(two_dummy_nics):
+    nipart.apply(load_yaml(f"""---
+            nexthops:
+              config:
+                - id: 1
+                  gateway: 192.0.2.254
+                  device: {TEST_NIC1}
+                - id: 2
+                  gateway: 198.51.100.254
+                  device: {TEST_NIC2}
+                - id: 10
+                  group:
+                    - id: 1
+                      weight: 2
+                    - id: 2
+            routes:
+              config:
+                - destination: {TEST_DST}
+                  next-hop-id: 10
+            """))
+
+    nexthops = get_config_nexthops()
+    assert any(
+        nh.get("id") == 1
+        and nh.get("gateway") == "192.0.2.254"
+        and nh.get("device") == TEST_NIC1
+        for nh in nexthops
+    )
+    assert any(
+        nh.get("id") == 10
+        and nh.get("group")
+        == [{"id": 1, "weight": 2}, {"id": 2, "weight": 1}]
+        for nh in nexthops
+    )
+    assert any(
+        rt.get("destination") == TEST_DST and rt.get("next-hop-id") == 10
+        for rt in nipart.show()["routes"].get("config", [])
+    )
+    _, output, _ = exec_cmd(["ip", "nexthop", "show", "id", "10"])
+    assert "group 1,2/2" in output
+    _, output, _ = exec_cmd(["ip", "route", "show", TEST_DST])
+    assert "nhid 10" in output
+
+    nipart.apply(load_yaml("""---
+            nexthops:
+              config:
+                - id: 10
+                  state: absent
+                - id: 1
+                  state: absent
+                - id: 2
+                  state: absent
+            """))
+
+    assert not any(nh.get("id") in (1, 2, 10) for nh in get_config_nexthops())
+    _, output, _ = exec_cmd(["ip", "route", "show", TEST_DST])
+    assert output.strip() == ""
+
+
+def test_blackhole_nexthop():
+    nipart.apply(load_yaml("""---
+            nexthops:
+              config:
+                - id: 100
+                  blackhole: true
+                - id: 101
+                  blackhole: true
+                  family: ipv6
+            """))
+    try:
+        nexthops = get_config_nexthops()
+        assert any(
+            nh.get("id") == 100
+            and nh.get("blackhole")
+            and nh.get("family") == "ipv4"
+            for nh in nexthops
+        )
+        assert any(
+            nh.get("id") == 101
+            and nh.get("blackhole")
+            and nh.get("family") == "ipv6"
+            for nh in nexthops
+        )
+    finally:
+        nipart.apply(load_yaml("""---
+                nexthops:
+                  config:
+                    - id: 100
+                      state: absent
+                    - id: 101
+                      state: absent
+                """))
+
+
+def test_device_only_nexthop(two_dummy_nics):
+    nipart.apply(load_yaml(f"""---
+            nexthops:
+              config:
+                - id: 10
+                  device: {TEST_NIC1}
+                - id: 11
+                  device: {TEST_NIC2}
+                  family: ipv6
+            routes:
+              config:
+                - destination: {TEST_DST}
+                  next-hop-id: 10
+            """))
+    try:
+        nexthops = get_config_nexthops()
+        assert any(
+            nh.get("id") == 10
+            and nh.get("device") == TEST_NIC1
+            and nh.get("family") == "ipv4"
+            for nh in nexthops
+        )
+        assert any(
+            nh.get("id") == 11
+            and nh.get("device") == TEST_NIC2
+            and nh.get("family") == "ipv6"
+            for nh in nexthops
+        )
+        _, output, _ = exec_cmd(["ip", "route", "show", TEST_DST])
+        assert "nhid 10" in output
+    finally:
+        nipart.apply(load_yaml("""---
+                nexthops:
+                  config:
+                    - id: 11
+                      state: absent
+                """))
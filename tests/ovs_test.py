@@ -0,0 +1,66 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_PORT1 = "dummy1"
+TEST_OVS_BRIDGE = "ovsbr0"
+TEST_OVS_IFACE = "ovs0"
+
+
+@pytest.fixture
+def ovs_bridge_with_ports():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_OVS_BRIDGE}
+                type: ovs-bridge
+                state: up
+                bridge:
+                  ports:
+                    - name: {TEST_PORT1}
+                    - name: {TEST_OVS_IFACE}
+              - name: {TEST_OVS_IFACE}
+                type: ovs-interface
+                state: up
+              - name: {TEST_PORT1}
+                type: dummy
+                state: up
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_OVS_BRIDGE}
+                type: ovs-bridge
+                state: absent
+              - name: {TEST_OVS_IFACE}
+                type: ovs-interface
+                state: absent
+              - name: {TEST_PORT1}
+                type: dummy
+                state: absent
+            """))
+
+
+def test_create_and_remove_ovs_bridge(ovs_bridge_with_ports):
+    ovs_iface = show_only(TEST_OVS_IFACE)
+    assert ovs_iface["controller"] == TEST_OVS_BRIDGE
+    port_iface = show_only(TEST_PORT1)
+    assert port_iface["controller"] == TEST_OVS_BRIDGE
+
+
+def test_detach_ovs_bridge_port(ovs_bridge_with_ports):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_OVS_BRIDGE}
+                type: ovs-bridge
+                state: up
+                bridge:
+                  ports:
+                    - name: {TEST_OVS_IFACE}
+            """))
+    port_iface = show_only(TEST_PORT1)
+    assert port_iface.get("controller") is None
@@ -0,0 +1,94 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.cmdlib import exec_cmd
+from .testlib.statelib import load_yaml
+
+TEST_TABLE_ID = 500
+IPV4_FROM = "192.0.2.0/24"
+IPV6_FROM = "2001:db8:1::/64"
+
+
+def get_config_rules():
+    return nipart.show().get("route-rules", {}).get("config", [])
+
+
+@pytest.fixture
+def cleanup_route_rules():
+    yield
+    nipart.apply(load_yaml(f"""---
+            route-rules:
+              config:
+                - table-id: {TEST_TABLE_ID}
+                  state: absent
+            """))
+
+
+def test_add_and_remove_route_rules(cleanup_route_rules):
+    nipart.apply(load_yaml(f"""---
+            route-rules:
+              config:
+                - priority: 1000
+                  from: {IPV4_FROM}
+                  table-id: {TEST_TABLE_ID}
+                - priority: 1001
+                  from: {IPV6_FROM}
+                  fwmark: 0x10
+                  fwmask: 0xff
+                  table-id: {TEST_TABLE_ID}
+            """))
+
+    rules = get_config_rules()
+    assert any(
+        r.get("priority") == 1000
+        and r.get("from") == IPV4_FROM
+        and r.get("table-id") == TEST_TABLE_ID
+        for r in rules
+    )
+    assert any(
+        r.get("priority") == 1001
+        and r.get("family") == "ipv6"
+        and r.get("fwmark") == 0x10
+        and r.get("fwmask") == 0xFF
+        for r in rules
+    )
+    _, output, _ = exec_cmd(["ip", "rule", "show", "priority", "1000"])
+    assert f"from {IPV4_FROM} lookup {TEST_TABLE_ID}" in output
+
+    nipart.apply(load_yaml(f"""---
+            route-rules:
+              config:
+                - table-id: {TEST_TABLE_ID}
+                  state: absent
+            """))
+
+    assert not any(
+        r.get("table-id") == TEST_TABLE_ID for r in get_config_rules()
+    )
+
+
+def test_route_rule_blackhole_action(cleanup_route_rules):
+    nipart.apply(load_yaml("""---
+            route-rules:
+              config:
+                - priority: 1002
+                  to: 198.51.100.0/24
+                  action: blackhole
+            """))
+
+    assert any(
+        r.get("priority") == 1002 and r.get("action") == "blackhole"
+        for r in get_config_rules()
+    )
+
+    nipart.apply(load_yaml("""---
+            route-rules:
+              config:
+                - priority: 1002
+                  state: absent
+            """))
+
+    assert not any(r.get("priority") == 1002 for r in get_config_rules())
@@ -0,0 +1,201 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.cmdlib import exec_cmd
+from .testlib.statelib import load_yaml
+
+TEST_NIC1 = "dummy1"
+TEST_NIC2 = "dummy2"
+
+
+@pytest.fixture
+def two_dummy_nics():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_NIC1}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 192.0.2.1
+                      prefix-length: 24
+                ipv6:
+                  enabled: true
+                  address:
+                    - ip: 2001:db8:1::1
+                      prefix-length: 64
+              - name: {TEST_NIC2}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 198.51.100.1
+                      prefix-length: 24
+                ipv6:
+                  enabled: true
+                  address:
+                    - ip: 2001:db8:2::1
+                      prefix-length: 64
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_NIC1}
+                type: dummy
+                state: absent
+              - name: {TEST_NIC2}
+                type: dummy
+                state: absent
+            """))
+
+
+def get_config_routes(dst):
+    return [
+        rt
+        for rt in nipart.show()["routes"].get("config", [])
+        if rt.get("destination") == dst
+    ]
+
+
+@pytest.mark.parametrize(
+    "dst,gw1,gw2",
+    [
+        ("203.0.113.0/24", "192.0.2.254", "198.51.100.254"),
+        ("2001:db8:f::/64", "2001:db8:1::fe", "2001:db8:2::fe"),
+    ],
+    ids=["ipv4", "ipv6"],
+)
+def test_ecmp_route_with_weight(two_dummy_nics, dst, gw1, gw2):
+    nipart.apply(load_yaml(f"""---
+            routes:
+              config:
+                - destination: {dst}
+                  next-hop-interface: {TEST_NIC1}
+                  next-hop-address: {gw1}
+                  weight: 1
+                - destination: {dst}
+                  next-hop-interface: {TEST_NIC2}
+                  next-hop-address: {gw2}
+                  weight: 3
+            """))
+
+    routes = get_config_routes(dst)
+    assert len(routes) == 2
+    assert any(
+        rt["next-hop-address"] == gw1 and rt.get("weight") == 1
+        for rt in routes
+    )
+    assert any(
+        rt["next-hop-address"] == gw2 and rt.get("weight") == 3
+        for rt in routes
+    )
+    _, output, _ = exec_cmd(["ip", "route", "show", dst])
+    assert "weight 3" in output
+
+    nipart.apply(load_yaml(f"""---
+            routes:
+              config:
+                - destination: {dst}
+                  next-hop-address: {gw2}
+                  state: absent
+            """))
+
+    routes = get_config_routes(dst)
+    assert len(routes) == 1
+    assert routes[0]["next-hop-address"] == gw1
+
+
+def test_same_destination_routes_grouped_as_ecmp(two_dummy_nics):
+    nipart.apply(load_yaml(f"""---
+            routes:
+              config:
+                - destination: 0.0.0.0/0
+                  next-hop-interface: {TEST_NIC1}
+                  next-hop-address: 192.0.2.254
+                  metric: 500
+                - destination: 0.0.0.0/0
+                  next-hop-interface: {TEST_NIC2}
+                  next-hop-address: 198.51.100.254
+                  metric: 500
+            """))
+
+    routes = [
+        rt
+        for rt in get_config_routes("0.0.0.0/0")
+        if rt.get("metric") == 500
+    ]
+    assert len(routes) == 2
+    _, output, _ = exec_cmd(
+        ["ip", "route", "show", "default", "metric", "500"]
+    )
+    assert "nexthop" in output
+
+
+def test_route_metrics(two_dummy_nics):
+    nipart.apply(load_yaml(f"""---
+            routes:
+              config:
+                - destination: 203.0.113.0/24
+                  next-hop-interface: {TEST_NIC1}
+                  next-hop-address: 192.0.2.254
+                  cwnd: 10
+                  initcwnd: 20
+                  initrwnd: 30
+                  mtu: 1400
+                  quickack: true
+                  advmss: 1360
+            """))
+
+    routes = get_config_routes("203.0.113.0/24")
+    assert len(routes) == 1
+    route = routes[0]
+    assert route["cwnd"] == 10
+    assert route["initcwnd"] == 20
+    assert route["initrwnd"] == 30
+    assert route["mtu"] == 1400
+    assert route["quickack"] is True
+    assert route["advmss"] == 1360
+    _, output, _ = exec_cmd(["ip", "route", "show", "203.0.113.0/24"])
+    assert "cwnd lock 10" in output
+    assert "initcwnd 20" in output
+
+
+def test_query_with_route_filter(two_dummy_nics):
+    nipart.apply(load_yaml(f"""---
+            routes:
+              config:
+                - destination: 203.0.113.0/24
+                  next-hop-interface: {TEST_NIC1}
+                  next-hop-address: 192.0.2.254
+                  table-id: 500
+                - destination: 2001:db8:f::/64
+                  next-hop-interface: {TEST_NIC1}
+                  next-hop-address: 2001:db8:1::fe
+                  table-id: 500
+                - destination: 203.0.113.0/24
+                  next-hop-interface: {TEST_NIC2}
+                  next-hop-address: 198.51.100.254
+            """))
+
+    routes = nipart.show(
+        nipart.NipartRouteFilter(
+            tables=[500], families=["ipv4"], skip_running=True
+        )
+    )["routes"]
+    assert "running" not in routes
+    assert len(routes["config"]) == 1
+    assert routes["config"][0]["table-id"] == 500
+    assert routes["config"][0]["destination"] == "203.0.113.0/24"
+
+    routes = nipart.show(
+        nipart.NipartRouteFilter(
+            protocols=[nipart.NipartRouteProtocol.STATIC], max_entries=1
+        )
+    )["routes"]
+    assert len(routes["config"]) == 1
+    assert len(routes["running"]) == 1
@@ -0,0 +1,107 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import os
+import time
+
+import pytest
+
+import nipart
+
+from .testlib.cmdlib import exec_cmd
+from .testlib.env import has_kernel_module
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+NETDEVSIM_ID = 99
+NETDEVSIM_SYSFS = "/sys/bus/netdevsim"
+NETDEVSIM_DEV_SYSFS = f"{NETDEVSIM_SYSFS}/devices/netdevsim{NETDEVSIM_ID}"
+
+
+def _get_netdevsim_nic():
+    for _ in range(10):
+        net_dir = f"{NETDEVSIM_DEV_SYSFS}/net"
+        if os.path.isdir(net_dir) and os.listdir(net_dir):
+            return os.listdir(net_dir)[0]
+        time.sleep(0.5)
+    raise Exception("Failed to find the netdevsim interface")
+
+
+@pytest.fixture
+def sriov_pf():
+    exec_cmd("modprobe netdevsim".split())
+    with open(f"{NETDEVSIM_SYSFS}/new_device", "w") as fd:
+        fd.write(f"{NETDEVSIM_ID} 1")
+    pf_name = _get_netdevsim_nic()
+    yield pf_name
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {pf_name}
+                type: ethernet
+                state: up
+                ethernet:
+                  sr-iov:
+                    total-vfs: 0
+            """))
+    with open(f"{NETDEVSIM_SYSFS}/del_device", "w") as fd:
+        fd.write(f"{NETDEVSIM_ID}")
+
+
+@pytest.mark.skipif(
+    not has_kernel_module("netdevsim"),
+    reason=("Does not have 'netdevsim' module "),
+)
+class TestSrIov:
+    def test_sriov_vf_config(self, sriov_pf):
+        nipart.apply(load_yaml(f"""---
+                interfaces:
+                  - name: {sriov_pf}
+                    type: ethernet
+                    state: up
+                    ethernet:
+                      sr-iov:
+                        total-vfs: 2
+                        vfs:
+                          - id: 0
+                            mac-address: 00:11:22:33:44:00
+                            spoof-check: true
+                            trust: true
+                            vlan-id: 100
+                            qos: 5
+                          - id: 1
+                            max-tx-rate: 1000
+                            min-tx-rate: 100
+                """))
+        sriov_conf = show_only(sriov_pf)["ethernet"]["sr-iov"]
+        assert sriov_conf["total-vfs"] == 2
+        vf0 = sriov_conf["vfs"][0]
+        vf1 = sriov_conf["vfs"][1]
+        assert vf0["mac-address"] == "00:11:22:33:44:00"
+        assert vf0["spoof-check"]
+        assert vf0["trust"]
+        assert vf0["vlan-id"] == 100
+        assert vf0["qos"] == 5
+        assert vf1["max-tx-rate"] == 1000
+        assert vf1["min-tx-rate"] == 100
+
+    def test_change_sriov_total_vfs(self, sriov_pf):
+        nipart.apply(load_yaml(f"""---
+                interfaces:
+                  - name: {sriov_pf}
+                    type: ethernet
+                    state: up
+                    ethernet:
+                      sr-iov:
+                        total-vfs: 4
+                """))
+        assert show_only(sriov_pf)["ethernet"]["sr-iov"]["total-vfs"] == 4
+
+        nipart.apply(load_yaml(f"""---
+                interfaces:
+                  - name: {sriov_pf}
+                    type: ethernet
+                    state: up
+                    ethernet:
+                      sr-iov:
+                        total-vfs: 1
+                """))
+        assert show_only(sriov_pf)["ethernet"]["sr-iov"]["total-vfs"] == 1
@@ -3,6 +3,8 @@
 import os
 import signal
 
+import pytest
+
 from .cmdlib import exec_cmd
 
 DHCP_SRV_IP4_PREFIX = "192.0.2"
@@ -18,6 +20,8 @@ DNSMASQ_CONF_PATH = "/tmp/nipart_test_dnsmasq.conf"
 DNSMASQ_PID_PATH = "/tmp/nipart_test_dnsmasq.pid"
 
 DHCP_SRV_NIC = "dhcp_srv"
+DHCP_CLI_NIC = "dhcpcli"
+DHCP_TEST_NET_NS = "nipart_dhcp_test"
 
 
 def start_dhcp_server(net_ns):
@@ -62,3 +66,28 @@ def start_dhcp_server(net_ns):
 def stop_dhcp_server():
     with open(DNSMASQ_PID_PATH, "r") as fd:
         os.kill(int(fd.read()), signal.SIGTERM)
+
+
+@pytest.fixture
+def dhcp_env():
+    """
+    Veth pair with `DHCP_SRV_NIC` in network namespace `DHCP_TEST_NET_NS`
+    running dnsmasq, and `DHCP_CLI_NIC` in default network namespace.
+    """
+    exec_cmd(f"ip netns del {DHCP_TEST_NET_NS}".split(), check=False)
+    exec_cmd(f"ip netns add {DHCP_TEST_NET_NS}".split())
+    exec_cmd(
+        f"ip link add {DHCP_CLI_NIC} type veth peer name {DHCP_SRV_NIC} "
+        f"netns {DHCP_TEST_NET_NS}".split()
+    )
+    exec_cmd(
+        f"ip netns exec {DHCP_TEST_NET_NS} "
+        f"ip link set {DHCP_SRV_NIC} up".split()
+    )
+    start_dhcp_server(DHCP_TEST_NET_NS)
+    try:
+        yield
+    finally:
+        stop_dhcp_server()
+        exec_cmd(f"ip link del {DHCP_CLI_NIC}".split(), check=False)
+        exec_cmd(f"ip netns del {DHCP_TEST_NET_NS}".split())
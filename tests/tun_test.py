@@ -0,0 +1,86 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_TAP_NIC = "tap0"
+
+
+@pytest.fixture
+def tap0():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_TAP_NIC}
+                type: tun
+                state: up
+                tun:
+                  mode: tap
+                  owner: 1000
+                  group: 1000
+                  multi-queue: true
+                  vnet-hdr: true
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_TAP_NIC}
+                type: tun
+                state: absent
+            """))
+
+
+def test_create_and_remove_tap(tap0):
+    tun_conf = show_only(TEST_TAP_NIC)["tun"]
+    assert tun_conf["mode"] == "tap"
+    assert tun_conf["owner"] == 1000
+    assert tun_conf["group"] == 1000
+    assert tun_conf["multi-queue"]
+    assert tun_conf["vnet-hdr"]
+    assert tun_conf["persist"]
+
+
+def test_change_tap_owner(tap0):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_TAP_NIC}
+                type: tun
+                state: up
+                tun:
+                  owner: 1001
+            """))
+    tun_conf = show_only(TEST_TAP_NIC)["tun"]
+    assert tun_conf["owner"] == 1001
+    assert tun_conf["group"] == 1000
+
+
+def test_remove_tap_owner_and_group(tap0):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_TAP_NIC}
+                type: tun
+                state: up
+                tun:
+                  owner: 4294967295
+                  group: 4294967295
+            """))
+    tun_conf = show_only(TEST_TAP_NIC)["tun"]
+    assert "owner" not in tun_conf
+    assert "group" not in tun_conf
+
+
+def test_change_tap_to_tun(tap0):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_TAP_NIC}
+                type: tun
+                state: up
+                tun:
+                  mode: tun
+            """))
+    tun_conf = show_only(TEST_TAP_NIC)["tun"]
+    assert tun_conf["mode"] == "tun"
+    assert tun_conf["owner"] == 1000
@@ -5,7 +5,9 @@ import pytest
 import nipart
 from nipart import NipartValueError
 
+from .testlib.cmdlib import exec_cmd
 from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
 from .testlib.veth import veth_interface
 
 
@@ -31,3 +33,42 @@ def test_veth_exceeded_min_mtu():
                           type: ethernet
                           mtu: 1
                      """))
+
+
+def test_veth_ipv6_addr_gen_mode_token_privacy():
+    with veth_interface("veth-test1", "veth-test1-ep"):
+        nipart.apply(load_yaml("""---
+                    version: 1
+                    interfaces:
+                    - name: veth-test1
+                      type: ethernet
+                      ipv6:
+                        enabled: true
+                        autoconf: true
+                        addr-gen-mode: stable-privacy
+                        token: ::1:2:3:4
+                        privacy: prefer-temporary
+                 """))
+        ipv6 = show_only("veth-test1")["ipv6"]
+        assert ipv6["addr-gen-mode"] == "stable-privacy"
+        assert ipv6["token"] == "::1:2:3:4"
+        assert ipv6["privacy"] == "prefer-temporary"
+        assert exec_cmd(
+            "sysctl -n net.ipv6.conf.veth-test1.use_tempaddr".split()
+        )[1].strip() == "2"
+
+        nipart.apply(load_yaml("""---
+                    version: 1
+                    interfaces:
+                    - name: veth-test1
+                      type: ethernet
+                      ipv6:
+                        enabled: true
+                        addr-gen-mode: eui64
+                        token: "::"
+                        privacy: disabled
+                 """))
+        ipv6 = show_only("veth-test1")["ipv6"]
+        assert ipv6["addr-gen-mode"] == "eui64"
+        assert "token" not in ipv6
+        assert ipv6["privacy"] == "disabled"
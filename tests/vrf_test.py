@@ -0,0 +1,89 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_PORT1 = "dummy1"
+TEST_PORT2 = "dummy2"
+TEST_VRF_NIC = "vrf0"
+TEST_ROUTE_TABLE_ID = 100
+
+
+@pytest.fixture
+def vrf_over_dummy():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_VRF_NIC}
+                type: vrf
+                state: up
+                vrf:
+                  route-table-id: {TEST_ROUTE_TABLE_ID}
+                  ports:
+                    - {TEST_PORT1}
+              - name: {TEST_PORT1}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 192.0.2.1
+                      prefix-length: 24
+              - name: {TEST_PORT2}
+                type: dummy
+                state: up
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_VRF_NIC}
+                type: vrf
+                state: absent
+              - name: {TEST_PORT1}
+                type: dummy
+                state: absent
+              - name: {TEST_PORT2}
+                type: dummy
+                state: absent
+            """))
+
+
+def test_create_and_remove_vrf(vrf_over_dummy):
+    vrf_iface = show_only(TEST_VRF_NIC)
+    assert vrf_iface["vrf"]["route-table-id"] == TEST_ROUTE_TABLE_ID
+    assert vrf_iface["vrf"]["ports"] == [TEST_PORT1]
+    assert show_only(TEST_PORT1)["controller"] == TEST_VRF_NIC
+
+
+def test_vrf_add_port(vrf_over_dummy):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_VRF_NIC}
+                type: vrf
+                state: up
+                vrf:
+                  ports:
+                    - {TEST_PORT1}
+                    - {TEST_PORT2}
+            """))
+    vrf_iface = show_only(TEST_VRF_NIC)
+    assert vrf_iface["vrf"]["ports"] == [TEST_PORT1, TEST_PORT2]
+
+
+def test_route_to_vrf_port_use_vrf_table(vrf_over_dummy):
+    nipart.apply(load_yaml(f"""---
+            routes:
+              config:
+                - destination: 198.51.100.0/24
+                  next-hop-interface: {TEST_PORT1}
+                  next-hop-address: 192.0.2.254
+            """))
+    state = nipart.show()
+    assert any(
+        rt.get("table-id") == TEST_ROUTE_TABLE_ID
+        and rt.get("destination") == "198.51.100.0/24"
+        for rt in state["routes"]["config"]
+    )
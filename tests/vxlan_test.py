@@ -0,0 +1,68 @@
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+import nipart
+
+from .testlib.statelib import load_yaml
+from .testlib.statelib import show_only
+
+TEST_BASE_NIC = "dummy1"
+TEST_VXLAN_NIC = "vxlan100"
+
+
+@pytest.fixture
+def vxlan_over_dummy():
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_VXLAN_NIC}
+                type: vxlan
+                state: up
+                vxlan:
+                  id: 100
+                  base-iface: {TEST_BASE_NIC}
+                  remote: 192.0.2.2
+                  destination-port: 4789
+                  learning: false
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: up
+                ipv4:
+                  enabled: true
+                  address:
+                    - ip: 192.0.2.1
+                      prefix-length: 24
+            """))
+    yield
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_VXLAN_NIC}
+                type: vxlan
+                state: absent
+              - name: {TEST_BASE_NIC}
+                type: dummy
+                state: absent
+            """))
+
+
+def test_create_and_remove_vxlan(vxlan_over_dummy):
+    vxlan_iface = show_only(TEST_VXLAN_NIC)
+    assert vxlan_iface["vxlan"]["id"] == 100
+    assert vxlan_iface["vxlan"]["base-iface"] == TEST_BASE_NIC
+    assert vxlan_iface["vxlan"]["remote"] == "192.0.2.2"
+    assert vxlan_iface["vxlan"]["destination-port"] == 4789
+    assert not vxlan_iface["vxlan"]["learning"]
+
+
+def test_change_vxlan_id(vxlan_over_dummy):
+    nipart.apply(load_yaml(f"""---
+            interfaces:
+              - name: {TEST_VXLAN_NIC}
+                type: vxlan
+                state: up
+                vxlan:
+                  id: 101
+            """))
+    vxlan_iface = show_only(TEST_VXLAN_NIC)
+    assert vxlan_iface["vxlan"]["id"] == 101
+    assert vxlan_iface["vxlan"]["base-iface"] == TEST_BASE_NIC